@@ -1,21 +1,23 @@
+use crate::commands::validate::parse_error_excerpt;
 use crate::config::Config;
 use anyhow::{Context, Result};
 use shem_core::{
-    DatabaseDriver, Schema,
-    migration::{generate_migration, write_migration},
+    DatabaseDriver, QualifiedName, Schema,
+    migration::{Migration, format_migration, generate_migration_with, write_migration},
 };
 use parser::{
     ast::{
-        Statement as ParserStatement,
+        CreateIndex, Statement as ParserStatement,
     },
-    parse_file,
+    parse_file, parse_sql,
 };
 use shared_types::{
-    CheckOption, DataType, FunctionReturn, ParameterMode, PolicyCommand, TableConstraint, TriggerWhen,
+    CheckOption, DataType, ExclusionElement, Expression, ForeignKeyReference, FunctionReturn,
+    ParameterMode, PolicyCommand, ReferentialAction, SortOrder, TableConstraint, TriggerWhen,
 };
 use postgres::PostgresDriver;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
 pub async fn execute(
@@ -23,6 +25,7 @@ pub async fn execute(
     output: Option<PathBuf>,
     database_url: Option<String>,
     name: Option<String>,
+    idempotent: bool,
     config: &Config,
 ) -> Result<()> {
     // Try to load schema files from config first, fall back to provided path
@@ -41,11 +44,13 @@ pub async fn execute(
     info!("Target schema: {:?}", target_schema);
 
     // Get current database schema if URL provided
+    let driver = get_driver(config)?;
+    let generator = driver.sql_generator();
+    let options = config.generate_options(idempotent);
     let current_schema = if let Some(url) = database_url.or_else(|| config.database_url.clone()) {
         info!("Connecting to database to get current schema");
-        let driver = get_driver()?;
         let conn = driver.connect(&url).await?;
-        Some(conn.introspect().await?)
+        Some(conn.introspect_with_options(&config.introspect_options()).await?)
     } else {
         None
     };
@@ -53,16 +58,65 @@ pub async fn execute(
     // Generate migration
     let migration = if let Some(current) = current_schema {
         info!("Generating migration from database schema");
-        generate_migration(&current, &target_schema)?
+        generate_migration_with(&current, &target_schema, generator.as_ref(), &options)?
     } else {
         info!("Generating initial migration");
-        generate_migration(&Schema::new(), &target_schema)?
+        generate_migration_with(&Schema::new(), &target_schema, generator.as_ref(), &options)?
     };
 
-    // Write migration file
-    let output_path = output.unwrap_or_else(|| {
-        let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
-        let filename = if let Some(migration_name) = name {
+    write_migration_file(&migration, output, name)
+}
+
+/// Generate the migration that brings the `target` database in line with the `source` database
+pub async fn execute_live(
+    source: String,
+    target: String,
+    output: Option<PathBuf>,
+    name: Option<String>,
+    idempotent: bool,
+    config: &Config,
+) -> Result<()> {
+    let driver = get_driver(config)?;
+    let options = config.generate_options(idempotent);
+
+    info!("Introspecting source database");
+    let source_schema = driver
+        .connect(&source)
+        .await
+        .context("Failed to connect to source database")?
+        .introspect_with_options(&config.introspect_options())
+        .await?;
+
+    info!("Introspecting target database");
+    let target_schema = driver
+        .connect(&target)
+        .await
+        .context("Failed to connect to target database")?
+        .introspect_with_options(&config.introspect_options())
+        .await?;
+
+    info!("Generating migration from target to source");
+    let migration = generate_migration_with(
+        &target_schema,
+        &source_schema,
+        driver.sql_generator().as_ref(),
+        &options,
+    )?;
+
+    write_migration_file(&migration, output, name)
+}
+
+/// Write the migration to `output`, to a timestamped file under `migrations/`
+/// when only a name is given, or to stdout otherwise
+fn write_migration_file(
+    migration: &Migration,
+    output: Option<PathBuf>,
+    name: Option<String>,
+) -> Result<()> {
+    let output_path = match (output, name) {
+        (Some(path), _) => path,
+        (None, Some(migration_name)) => {
+            let timestamp = chrono::Utc::now().format("%Y%m%d%H%M%S");
             // Sanitize the name for use in filename
             let sanitized_name = migration_name
                 .chars()
@@ -74,70 +128,90 @@ pub async fn execute(
                     }
                 })
                 .collect::<String>();
-            format!("migrations/{}_{}.sql", timestamp, sanitized_name)
-        } else {
-            format!("migrations/{}.sql", timestamp)
-        };
-        PathBuf::from(filename)
-    });
+            PathBuf::from(format!("migrations/{}_{}.sql", timestamp, sanitized_name))
+        }
+        (None, None) => {
+            println!("{}", format_migration(migration));
+            return Ok(());
+        }
+    };
 
     // Create migrations directory if it doesn't exist
     if let Some(parent) = output_path.parent() {
         std::fs::create_dir_all(parent).context("Failed to create migrations directory")?;
     }
 
-    write_migration(&output_path, &migration)?;
+    write_migration(&output_path, migration)?;
     info!("Migration written to {}", output_path.display());
 
     Ok(())
 }
 
-fn load_schema_from_files(files: &[PathBuf]) -> Result<Schema> {
+/// Parse a schema file, quoting the offending line when its SQL is malformed
+fn parse_schema_file(path: &Path) -> Result<Vec<ParserStatement>> {
+    parse_file(path).map_err(|e| match parse_error_excerpt(&e) {
+        Some(excerpt) => {
+            anyhow::anyhow!("Failed to parse {}: {}\n{}", path.display(), e, excerpt)
+        }
+        None => e,
+    })
+}
+
+pub(crate) fn load_schema_from_files(files: &[PathBuf]) -> Result<Schema> {
     let mut schema = Schema::new();
 
     for file_path in files {
-        if file_path.is_file() {
-            // Load single schema file
+        if file_path.is_dir() {
+            info!("Loading schemas from directory: {}", file_path.display());
+        } else {
             info!("Loading schema from file: {}", file_path.display());
-            let statements = parse_file(file_path)?;
+        }
+        for filepath in schema_sql_files(file_path)? {
+            info!("Processing schema file: {}", filepath.display());
+            let statements = parse_schema_file(&filepath)?;
             for stmt in statements {
                 add_statement_to_schema(&mut schema, &stmt)?;
             }
-        } else if file_path.is_dir() {
-            // Load all .sql files in directory, ordered by filename
-            info!("Loading schemas from directory: {}", file_path.display());
+        }
+    }
 
-            // Use BTreeMap to maintain order by filename
-            let mut ordered_files = BTreeMap::new();
-
-            // First, collect all SQL files and their paths
-            for entry in walkdir::WalkDir::new(file_path)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .filter(|e| e.path().extension().map_or(false, |ext| ext == "sql"))
-            {
-                let path = entry.path().to_path_buf();
-                let filename = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .ok_or_else(|| anyhow::anyhow!("Invalid filename: {}", path.display()))?;
-
-                ordered_files.insert(filename.to_string(), path);
-            }
+    Ok(schema)
+}
 
-            // Then process them in order
-            for (filename, filepath) in ordered_files {
-                info!("Processing schema file: {}", filename);
-                let statements = parse_file(&filepath)?;
-                for stmt in statements {
-                    add_statement_to_schema(&mut schema, &stmt)?;
-                }
-            }
-        } else {
-            anyhow::bail!("Schema path does not exist: {}", file_path.display());
-        }
+/// The schema file at `path`, or every `.sql` file below the directory at
+/// `path` ordered by file name
+pub(crate) fn schema_sql_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        anyhow::bail!("Schema path does not exist: {}", path.display());
+    }
+
+    // Use BTreeMap to maintain order by filename
+    let mut ordered_files = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "sql"))
+    {
+        let path = entry.path().to_path_buf();
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid filename: {}", path.display()))?;
+
+        ordered_files.insert(filename.to_string(), path);
     }
+    Ok(ordered_files.into_values().collect())
+}
 
+/// Schema holding the objects `sql` creates
+pub(crate) fn load_schema_from_sql(sql: &str) -> Result<Schema> {
+    let mut schema = Schema::new();
+    for stmt in parse_sql(sql)? {
+        add_statement_to_schema(&mut schema, &stmt)?;
+    }
     Ok(schema)
 }
 
@@ -156,164 +230,44 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 inherits: Vec::new(),
                 partition_by: None,
                 storage_parameters: std::collections::HashMap::new(),
+                grants: Vec::new(),
+                owner: None,
+                unlogged: false,
+                partition_bound: None,
+                row_security: false,
+                force_row_security: false,
+                of_type: None,
             };
 
-            // Add columns
             for col in &create.columns {
-                let type_name = match &col.data_type {
-                    DataType::Text => "TEXT".to_string(),
-                    DataType::Integer => "INTEGER".to_string(),
-                    DataType::BigInt => "BIGINT".to_string(),
-                    DataType::SmallInt => "SMALLINT".to_string(),
-                    DataType::Serial => "SERIAL".to_string(),
-                    DataType::BigSerial => "BIGSERIAL".to_string(),
-                    DataType::SmallSerial => "SMALLSERIAL".to_string(),
-                    DataType::Boolean => "BOOLEAN".to_string(),
-                    DataType::Real => "REAL".to_string(),
-                    DataType::DoublePrecision => "DOUBLE PRECISION".to_string(),
-                    DataType::Decimal(precision, scale) => {
-                        if let (Some(p), Some(s)) = (precision, scale) {
-                            format!("DECIMAL({}, {})", p, s)
-                        } else if let Some(p) = precision {
-                            format!("DECIMAL({})", p)
-                        } else {
-                            "DECIMAL".to_string()
-                        }
-                    }
-                    DataType::Numeric(precision, scale) => {
-                        if let (Some(p), Some(s)) = (precision, scale) {
-                            format!("NUMERIC({}, {})", p, s)
-                        } else if let Some(p) = precision {
-                            format!("NUMERIC({})", p)
-                        } else {
-                            "NUMERIC".to_string()
-                        }
-                    }
-                    DataType::Date => "DATE".to_string(),
-                    DataType::Time(precision) => {
-                        if let Some(p) = precision {
-                            format!("TIME({})", p)
-                        } else {
-                            "TIME".to_string()
-                        }
-                    }
-                    DataType::Timestamp(precision) => {
-                        if let Some(p) = precision {
-                            format!("TIMESTAMP({})", p)
-                        } else {
-                            "TIMESTAMP".to_string()
-                        }
-                    }
-                    DataType::TimestampTz(precision) => {
-                        if let Some(p) = precision {
-                            format!("TIMESTAMPTZ({})", p)
-                        } else {
-                            "TIMESTAMPTZ".to_string()
-                        }
-                    }
-                    DataType::Interval(precision) => {
-                        if let Some(p) = precision {
-                            format!("INTERVAL({:?})", p)
-                        } else {
-                            "INTERVAL".to_string()
-                        }
-                    }
-                    DataType::Uuid => "UUID".to_string(),
-                    DataType::Json => "JSON".to_string(),
-                    DataType::JsonB => "JSONB".to_string(),
-                    DataType::ByteA => "BYTEA".to_string(),
-                    DataType::Character(length) => {
-                        if let Some(l) = length {
-                            format!("CHAR({})", l)
-                        } else {
-                            "CHAR".to_string()
-                        }
-                    }
-                    DataType::CharacterVarying(length) => {
-                        if let Some(l) = length {
-                            format!("VARCHAR({})", l)
-                        } else {
-                            "VARCHAR".to_string()
-                        }
-                    }
-                    DataType::Custom(name) => name.clone(),
-                    _ => format!("{:?}", col.data_type), // Fallback for other types
-                };
-
-                let column = shem_core::Column {
-                    name: col.name.clone(),
-                    type_name,
-                    nullable: !col.not_null,
-                    default: col.default.as_ref().map(|d| format!("{:?}", d)),
-                    identity: col.identity.as_ref().map(|i| shem_core::Identity {
-                        always: i.always,
-                        start: i.start.unwrap_or(1),
-                        increment: i.increment.unwrap_or(1),
-                        min_value: i.min_value,
-                        max_value: i.max_value,
-                        cache: None,
-                        cycle: false,
-                    }),
-                    generated: col.generated.as_ref().map(|g| shem_core::schema::GeneratedColumn {
-                        expression: format!("{:?}", g.expression),
-                        stored: g.stored,
-                    }),
-                    comment: None,
-                    collation: None,
-                    storage: None,
-                    compression: None,
-                };
-                table.columns.push(column);
+                table.columns.push(column_from_definition(col));
             }
 
-            // Add constraints
             for constraint in &create.constraints {
-                let definition = match constraint {
-                    TableConstraint::PrimaryKey { columns, .. } => {
-                        format!("PRIMARY KEY ({})", columns.join(", "))
-                    }
-                    TableConstraint::Unique { columns, .. } => {
-                        format!("UNIQUE ({})", columns.join(", "))
-                    }
-                    TableConstraint::ForeignKey { columns, .. } => {
-                        format!("FOREIGN KEY ({})", columns.join(", "))
-                    }
-                    TableConstraint::Check { .. } => "CHECK (...)".to_string(),
-                    TableConstraint::Exclusion { .. } => "EXCLUDE (...)".to_string(),
-                };
-                let constraint = shem_core::Constraint {
-                    name: match constraint {
-                        TableConstraint::PrimaryKey { name, .. } => {
-                            name.clone().unwrap_or_default()
-                        }
-                        TableConstraint::ForeignKey { name, .. } => {
-                            name.clone().unwrap_or_default()
-                        }
-                        TableConstraint::Unique { name, .. } => name.clone().unwrap_or_default(),
-                        TableConstraint::Check { name, .. } => name.clone().unwrap_or_default(),
-                        TableConstraint::Exclusion { name, .. } => name.clone().unwrap_or_default(),
-                    },
-                    kind: match constraint {
-                        TableConstraint::PrimaryKey { .. } => shem_core::ConstraintKind::PrimaryKey,
-                        TableConstraint::ForeignKey { .. } => {
-                            shem_core::ConstraintKind::ForeignKey {
-                                references: "".to_string(),
-                                on_delete: None,
-                                on_update: None,
-                            }
-                        }
-                        TableConstraint::Unique { .. } => shem_core::ConstraintKind::Unique,
-                        TableConstraint::Check { .. } => shem_core::ConstraintKind::Check,
-                        TableConstraint::Exclusion { .. } => shem_core::ConstraintKind::Exclusion,
-                    },
-                    definition,
-                    deferrable: false,
-                    initially_deferred: false,
-                };
-                table.constraints.push(constraint);
+                table
+                    .constraints
+                    .push(constraint_from_definition(&create.name, constraint));
             }
 
-            schema.tables.insert(table.name.clone(), table);
+            for index in &create.indexes {
+                table.indexes.push(index_from_statement(index)?);
+            }
+
+            schema.tables.insert(QualifiedName::key(&table.schema, &table.name), table);
+        }
+        ParserStatement::CreateIndex(create) => {
+            let index = index_from_statement(create)?;
+            let key = QualifiedName::key(&create.schema, &create.table);
+            if let Some(table) = schema.tables.get_mut(&key) {
+                table.indexes.push(index);
+            } else if let Some(view) = schema.materialized_views.get_mut(&key) {
+                view.indexes.push(index);
+            } else {
+                warn!(
+                    "Index {} references table {} which is not declared",
+                    create.name, create.table
+                );
+            }
         }
         ParserStatement::CreateView(create) => {
             let view = shem_core::View {
@@ -331,8 +285,10 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 comment: None,
                 security_barrier: false,
                 columns: Vec::new(),
+                owner: None,
+                column_comments: std::collections::HashMap::new(),
             };
-            schema.views.insert(view.name.clone(), view);
+            schema.views.insert(QualifiedName::key(&view.schema, &view.name), view);
         }
         ParserStatement::CreateMaterializedView(create) => {
             let view = shem_core::MaterializedView {
@@ -345,15 +301,19 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 storage_parameters: std::collections::HashMap::new(),
                 indexes: Vec::new(),
                 populate_with_data: true, // Default to WITH DATA for parsed statements
+                column_comments: std::collections::HashMap::new(),
+                owner: None,
             };
-            schema.materialized_views.insert(view.name.clone(), view);
+            schema
+                .materialized_views
+                .insert(QualifiedName::key(&view.schema, &view.name), view);
         }
         ParserStatement::CreateFunction(create) => {
             let mut parameters = Vec::new();
             for param in &create.parameters {
                 let parameter = shem_core::Parameter {
                     name: param.name.clone().unwrap_or_default(),
-                    type_name: format!("{:?}", param.data_type),
+                    type_name: data_type_sql(&param.data_type),
                     mode: param
                         .mode
                         .clone()
@@ -372,7 +332,7 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
             let returns = match &create.returns {
                 FunctionReturn::Type(t) => shem_core::ReturnType {
                     kind: shem_core::ReturnKind::Scalar,
-                    type_name: format!("{:?}", t),
+                    type_name: data_type_sql(t),
                     is_set: false,
                 },
                 FunctionReturn::Table(cols) => shem_core::ReturnType {
@@ -382,7 +342,7 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 },
                 FunctionReturn::SetOf(t) => shem_core::ReturnType {
                     kind: shem_core::ReturnKind::SetOf,
-                    type_name: format!("{:?}", t),
+                    type_name: data_type_sql(t),
                     is_set: true,
                 },
             };
@@ -401,15 +361,19 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 parallel_safety: shem_core::ParallelSafety::Unsafe,
                 cost: None,
                 rows: None,
+                owner: None,
             };
-            schema.functions.insert(function.name.clone(), function);
+            schema.functions.insert(
+                QualifiedName::key(&function.schema, &function.signature()),
+                function,
+            );
         }
         ParserStatement::CreateProcedure(create) => {
             let mut parameters = Vec::new();
             for param in &create.parameters {
                 let parameter = shem_core::Parameter {
                     name: param.name.clone().unwrap_or_default(),
-                    type_name: format!("{:?}", param.data_type),
+                    type_name: data_type_sql(&param.data_type),
                     mode: param
                         .mode
                         .clone()
@@ -434,7 +398,9 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 comment: None,
                 security_definer: false,
             };
-            schema.procedures.insert(procedure.name.clone(), procedure);
+            schema
+                .procedures
+                .insert(QualifiedName::key(&procedure.schema, &procedure.name), procedure);
         }
         ParserStatement::CreateEnum(create) => {
             let enum_type = shem_core::EnumType {
@@ -442,8 +408,9 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 schema: create.schema.clone(),
                 values: create.values.clone(),
                 comment: None,
+                owner: None,
             };
-            schema.enums.insert(enum_type.name.clone(), enum_type);
+            schema.enums.insert(QualifiedName::key(&enum_type.schema, &enum_type.name), enum_type);
         }
         ParserStatement::CreateType(_create) => {
             // Handle composite types - they can be stored in a separate collection if needed
@@ -458,8 +425,9 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 default: None,
                 not_null: false,
                 comment: None,
+                owner: None,
             };
-            schema.domains.insert(domain.name.clone(), domain);
+            schema.domains.insert(QualifiedName::key(&domain.schema, &domain.name), domain);
         }
         ParserStatement::CreateSequence(create) => {
             let sequence = shem_core::Sequence {
@@ -474,8 +442,9 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 cycle: create.cycle,
                 owned_by: None,
                 comment: None,
+                owner: None,
             };
-            schema.sequences.insert(sequence.name.clone(), sequence);
+            schema.sequences.insert(QualifiedName::key(&sequence.schema, &sequence.name), sequence);
         }
         ParserStatement::CreateExtension(create) => {
             let extension = shem_core::Extension {
@@ -498,6 +467,7 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                     TriggerWhen::InsteadOf => shem_core::TriggerTiming::InsteadOf,
                 },
                 events: vec![shem_core::schema::TriggerEvent::Insert], // Default
+                update_columns: Vec::new(),
                 function: create.function.clone(),
                 arguments: create.arguments.clone(),
                 condition: None,
@@ -505,7 +475,12 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 when: None,
                 comment: None,
             };
-            schema.triggers.insert(trigger.name.clone(), trigger);
+            // Keyed like introspected triggers, by table and name
+            let relation = QualifiedName::parse(&trigger.table);
+            let name = format!("{}.{}", relation.name, trigger.name);
+            schema
+                .triggers
+                .insert(QualifiedName::key(&relation.schema, &name), trigger);
         }
         ParserStatement::CreatePolicy(create) => {
             let policy = shem_core::Policy {
@@ -523,8 +498,13 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
                 roles: create.roles.clone(),
                 using: create.using.as_ref().map(|u| format!("{:?}", u)),
                 check: create.with_check.as_ref().map(|c| format!("{:?}", c)),
+                comment: None,
             };
-            schema.policies.insert(policy.name.clone(), policy);
+            let relation = QualifiedName::parse(&policy.table);
+            let name = format!("{}.{}", relation.name, policy.name);
+            schema
+                .policies
+                .insert(QualifiedName::key(&relation.schema, &name), policy);
         }
         ParserStatement::CreateServer(create) => {
             let server = shem_core::Server {
@@ -536,41 +516,21 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
             schema.servers.insert(server.name.clone(), server);
         }
         ParserStatement::AlterTable(alter) => {
-            // Find the table in the schema and add constraints
-            if let Some(table) = schema.tables.get_mut(&alter.name) {
+            // Find the table in the schema and add columns and constraints
+            let key = QualifiedName::key(&alter.schema, &alter.name);
+            if let Some(table) = schema.tables.get_mut(&key) {
                 for action in &alter.actions {
                     match action {
+                        parser::ast::AlterTableAction::AddColumn(col) => {
+                            table.columns.push(column_from_definition(col));
+                        }
+                        parser::ast::AlterTableAction::AddConstraint(
+                            TableConstraint::PrimaryKey { columns, .. }
+                            | TableConstraint::Unique { columns, .. },
+                        ) if columns.is_empty() => {}
                         parser::ast::AlterTableAction::AddConstraint(constraint) => {
-                            match constraint {
-                                TableConstraint::PrimaryKey { columns, name } => {
-                                    if !columns.is_empty() {
-                                        let c = shem_core::Constraint {
-                                            name: name.clone().unwrap_or_default(),
-                                            kind: shem_core::ConstraintKind::PrimaryKey,
-                                            definition: format!(
-                                                "PRIMARY KEY ({})",
-                                                columns.join(", ")
-                                            ),
-                                            deferrable: false,
-                                            initially_deferred: false,
-                                        };
-                                        table.constraints.push(c);
-                                    }
-                                }
-                                TableConstraint::Unique { columns, name } => {
-                                    if !columns.is_empty() {
-                                        let c = shem_core::Constraint {
-                                            name: name.clone().unwrap_or_default(),
-                                            kind: shem_core::ConstraintKind::Unique,
-                                            definition: format!("UNIQUE ({})", columns.join(", ")),
-                                            deferrable: false,
-                                            initially_deferred: false,
-                                        };
-                                        table.constraints.push(c);
-                                    }
-                                }
-                                _ => {}
-                            }
+                            let constraint = constraint_from_definition(&table.name, constraint);
+                            table.constraints.push(constraint);
                         }
                         _ => {}
                     }
@@ -585,6 +545,300 @@ fn add_statement_to_schema(schema: &mut Schema, stmt: &ParserStatement) -> Resul
     Ok(())
 }
 
-fn get_driver() -> Result<Box<dyn DatabaseDriver>> {
-    Ok(Box::new(PostgresDriver::new()))
+/// Schema column for a column parsed from `CREATE TABLE` or `ADD COLUMN`
+fn column_from_definition(col: &parser::ast::ColumnDefinition) -> shem_core::Column {
+    let type_name = data_type_sql(&col.data_type);
+
+    shem_core::Column {
+        name: col.name.clone(),
+        type_name,
+        nullable: !col.not_null,
+        default: col.default.as_ref().map(|d| format!("{:?}", d)),
+        identity: col.identity.as_ref().map(|i| shem_core::Identity {
+            always: i.always,
+            start: i.start.unwrap_or(1),
+            increment: i.increment.unwrap_or(1),
+            min_value: i.min_value,
+            max_value: i.max_value,
+            cache: None,
+            cycle: false,
+        }),
+        generated: col.generated.as_ref().map(|g| shem_core::schema::GeneratedColumn {
+            expression: format!("{:?}", g.expression),
+            stored: g.stored,
+        }),
+        comment: None,
+        collation: None,
+        storage: None,
+        compression: None,
+        inherited: false,
+        grants: Vec::new(),
+    }
+}
+
+/// Schema constraint for a constraint of `table` parsed from `CREATE TABLE`
+/// or `ADD CONSTRAINT`
+fn constraint_from_definition(table: &str, constraint: &TableConstraint) -> shem_core::Constraint {
+    let definition = match constraint {
+        TableConstraint::PrimaryKey {
+            columns,
+            deferrable,
+            initially_deferred,
+            ..
+        } => format!(
+            "PRIMARY KEY ({}){}",
+            columns.join(", "),
+            deferrable_clause(*deferrable, *initially_deferred)
+        ),
+        TableConstraint::Unique {
+            columns,
+            deferrable,
+            initially_deferred,
+            ..
+        } => format!(
+            "UNIQUE ({}){}",
+            columns.join(", "),
+            deferrable_clause(*deferrable, *initially_deferred)
+        ),
+        TableConstraint::ForeignKey {
+            columns,
+            references,
+            ..
+        } => foreign_key_definition(columns, references),
+        TableConstraint::Check { .. } => "CHECK (...)".to_string(),
+        TableConstraint::Exclusion {
+            elements, using, ..
+        } => exclusion_definition(elements, using),
+    };
+    let (deferrable, initially_deferred) = match constraint {
+        TableConstraint::PrimaryKey {
+            deferrable,
+            initially_deferred,
+            ..
+        }
+        | TableConstraint::Unique {
+            deferrable,
+            initially_deferred,
+            ..
+        } => (*deferrable, *initially_deferred),
+        _ => (false, false),
+    };
+    shem_core::Constraint {
+        name: match constraint {
+            TableConstraint::PrimaryKey { name, .. } => name.clone().unwrap_or_default(),
+            TableConstraint::ForeignKey { name, columns, .. } => name
+                .clone()
+                .unwrap_or_else(|| foreign_key_name(table, columns)),
+            TableConstraint::Unique { name, .. } => name.clone().unwrap_or_default(),
+            TableConstraint::Check { name, .. } => name.clone().unwrap_or_default(),
+            TableConstraint::Exclusion { name, .. } => name.clone().unwrap_or_default(),
+        },
+        kind: match constraint {
+            TableConstraint::PrimaryKey { .. } => shem_core::ConstraintKind::PrimaryKey,
+            TableConstraint::ForeignKey { references, .. } => {
+                shem_core::ConstraintKind::ForeignKey {
+                    references: references.table.clone(),
+                    on_delete: None,
+                    on_update: None,
+                }
+            }
+            TableConstraint::Unique { .. } => shem_core::ConstraintKind::Unique,
+            TableConstraint::Check { .. } => shem_core::ConstraintKind::Check,
+            TableConstraint::Exclusion { .. } => shem_core::ConstraintKind::Exclusion,
+        },
+        definition,
+        deferrable,
+        initially_deferred,
+    }
+}
+
+/// Exclusion constraint definition written the way `pg_get_constraintdef`
+/// reports one
+pub(crate) fn exclusion_definition(elements: &[ExclusionElement], using: &str) -> String {
+    let elements = elements
+        .iter()
+        .map(|element| match &element.expression {
+            Expression::Column(column) => format!("{} WITH {}", column, element.operator),
+            other => format!("{:?} WITH {}", other, element.operator),
+        })
+        .collect::<Vec<_>>();
+    format!("EXCLUDE USING {} ({})", using, elements.join(", "))
+}
+
+/// Name PostgreSQL gives a foreign key declared without one
+fn foreign_key_name(table: &str, columns: &[String]) -> String {
+    format!("{}_{}_fkey", table, columns.join("_"))
+}
+
+/// Foreign key definition written the way `pg_get_constraintdef` reports one
+fn foreign_key_definition(columns: &[String], references: &ForeignKeyReference) -> String {
+    let mut definition = format!(
+        "FOREIGN KEY ({}) REFERENCES {}",
+        columns.join(", "),
+        references.table
+    );
+    if !references.columns.is_empty() {
+        definition.push_str(&format!("({})", references.columns.join(", ")));
+    }
+    for (clause, action) in [
+        ("ON UPDATE", &references.on_update),
+        ("ON DELETE", &references.on_delete),
+    ] {
+        let action = match action {
+            Some(ReferentialAction::Restrict) => "RESTRICT",
+            Some(ReferentialAction::Cascade) => "CASCADE",
+            Some(ReferentialAction::SetNull) => "SET NULL",
+            Some(ReferentialAction::SetDefault) => "SET DEFAULT",
+            Some(ReferentialAction::NoAction) | None => continue,
+        };
+        definition.push_str(&format!(" {} {}", clause, action));
+    }
+    definition
+}
+
+/// SQL spelling of a parsed column, parameter or return type
+fn data_type_sql(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Text => "TEXT".to_string(),
+        DataType::Integer => "INTEGER".to_string(),
+        DataType::BigInt => "BIGINT".to_string(),
+        DataType::SmallInt => "SMALLINT".to_string(),
+        DataType::Serial => "SERIAL".to_string(),
+        DataType::BigSerial => "BIGSERIAL".to_string(),
+        DataType::SmallSerial => "SMALLSERIAL".to_string(),
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Real => "REAL".to_string(),
+        DataType::DoublePrecision => "DOUBLE PRECISION".to_string(),
+        DataType::Decimal(precision, scale) => {
+            if let (Some(p), Some(s)) = (precision, scale) {
+                format!("DECIMAL({}, {})", p, s)
+            } else if let Some(p) = precision {
+                format!("DECIMAL({})", p)
+            } else {
+                "DECIMAL".to_string()
+            }
+        }
+        DataType::Numeric(precision, scale) => {
+            if let (Some(p), Some(s)) = (precision, scale) {
+                format!("NUMERIC({}, {})", p, s)
+            } else if let Some(p) = precision {
+                format!("NUMERIC({})", p)
+            } else {
+                "NUMERIC".to_string()
+            }
+        }
+        DataType::Date => "DATE".to_string(),
+        DataType::Time(precision) => {
+            if let Some(p) = precision {
+                format!("TIME({})", p)
+            } else {
+                "TIME".to_string()
+            }
+        }
+        DataType::Timestamp(precision) => {
+            if let Some(p) = precision {
+                format!("TIMESTAMP({})", p)
+            } else {
+                "TIMESTAMP".to_string()
+            }
+        }
+        DataType::TimestampTz(precision) => {
+            if let Some(p) = precision {
+                format!("TIMESTAMPTZ({})", p)
+            } else {
+                "TIMESTAMPTZ".to_string()
+            }
+        }
+        DataType::Interval(precision) => {
+            if let Some(p) = precision {
+                format!("INTERVAL({:?})", p)
+            } else {
+                "INTERVAL".to_string()
+            }
+        }
+        DataType::Uuid => "UUID".to_string(),
+        DataType::Json => "JSON".to_string(),
+        DataType::JsonB => "JSONB".to_string(),
+        DataType::ByteA => "BYTEA".to_string(),
+        DataType::Character(length) => {
+            if let Some(l) = length {
+                format!("CHAR({})", l)
+            } else {
+                "CHAR".to_string()
+            }
+        }
+        DataType::CharacterVarying(length) => {
+            if let Some(l) = length {
+                format!("VARCHAR({})", l)
+            } else {
+                "VARCHAR".to_string()
+            }
+        }
+        DataType::Custom(name) => name.clone(),
+        _ => format!("{:?}", data_type), // Fallback for other types
+    }
+}
+
+/// Deferrability suffix of a constraint definition, written the way
+/// `pg_get_constraintdef` ends one so parsed and introspected definitions match
+pub(crate) fn deferrable_clause(deferrable: bool, initially_deferred: bool) -> &'static str {
+    match (deferrable, initially_deferred) {
+        (true, true) => " DEFERRABLE INITIALLY DEFERRED",
+        (true, false) => " DEFERRABLE",
+        (false, _) => "",
+    }
+}
+
+/// Convert a parsed CREATE INDEX into the schema model
+pub(crate) fn index_from_statement(create: &CreateIndex) -> Result<shem_core::Index> {
+    let method = match create.method.to_lowercase().as_str() {
+        "btree" => shem_core::IndexMethod::Btree,
+        "hash" => shem_core::IndexMethod::Hash,
+        "gist" => shem_core::IndexMethod::Gist,
+        "spgist" => shem_core::IndexMethod::Spgist,
+        "gin" => shem_core::IndexMethod::Gin,
+        "brin" => shem_core::IndexMethod::Brin,
+        other => anyhow::bail!("Unsupported index method {} on index {}", other, create.name),
+    };
+    let columns = create
+        .columns
+        .iter()
+        .map(|column| {
+            let order = match column.order {
+                Some(SortOrder::Desc) => shem_core::schema::SortOrder::Descending,
+                _ => shem_core::schema::SortOrder::Ascending,
+            };
+            shem_core::IndexColumn {
+                name: column.name.clone().unwrap_or_default(),
+                expression: column.expression.clone(),
+                // PostgreSQL puts nulls first by default only for descending keys
+                nulls_first: column
+                    .nulls_first
+                    .unwrap_or(order == shem_core::schema::SortOrder::Descending),
+                order,
+                opclass: None,
+            }
+        })
+        .collect();
+
+    Ok(shem_core::Index {
+        name: create.name.clone(),
+        columns,
+        unique: create.unique,
+        method,
+        where_clause: create.where_clause.clone(),
+        tablespace: None,
+        storage_parameters: std::collections::HashMap::new(),
+        include: create.include.clone(),
+    })
+}
+
+pub(crate) fn get_driver(config: &Config) -> Result<Box<dyn DatabaseDriver>> {
+    Ok(Box::new(
+        PostgresDriver::new()
+            .with_statement_timeout(config.database.statement_timeout_ms)
+            .with_ssl_root_cert(config.database.ssl_root_cert.clone())
+            .with_application_name(config.database.application_name.clone())
+            .with_options(config.database.options.clone()),
+    ))
 }
@@ -0,0 +1,150 @@
+use crate::commands::diff::{get_driver, schema_sql_files};
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use shem_core::migration::parse_migration;
+use shem_core::{DatabaseDriver, QualifiedName, Schema};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Print the fingerprint of the schema files when `schema` is given, of the
+/// database at `database_url` otherwise.
+///
+/// Schema files are read with less detail than introspection gives, so they
+/// are applied to a scratch database on the `database_url` server and
+/// fingerprinted from there. Their fingerprint then equals that of a database
+/// migrated to them.
+pub async fn execute(
+    schema: Option<PathBuf>,
+    database_url: Option<String>,
+    config: &Config,
+) -> Result<()> {
+    let url = database_url
+        .or_else(|| config.database_url.clone())
+        .context("No database URL given; pass --database-url or set database_url in the config")?;
+    let driver = get_driver(config)?;
+
+    let introspected = match schema {
+        Some(schema) => introspect_schema_files(driver.as_ref(), &url, &schema, config).await?,
+        None => {
+            info!("Introspecting database");
+            driver
+                .connect(&url)
+                .await
+                .context("Failed to connect to database")?
+                .introspect_with_options(&config.introspect_options())
+                .await?
+        }
+    };
+
+    println!("{}", fingerprint(&introspected, &config.migrations_table_name())?);
+    Ok(())
+}
+
+/// Apply the schema files at `path` to a scratch database created next to
+/// the one at `url`, introspect it and drop it again
+async fn introspect_schema_files(
+    driver: &dyn DatabaseDriver,
+    url: &str,
+    path: &Path,
+    config: &Config,
+) -> Result<Schema> {
+    let files = schema_sql_files(path)?;
+    let conn = driver
+        .connect(url)
+        .await
+        .context("Failed to connect to database")?;
+    let scratch = format!("shem_fingerprint_{}", uuid::Uuid::new_v4().simple());
+    info!("Applying schema files to scratch database {}", scratch);
+    conn.execute(&format!("CREATE DATABASE \"{}\"", scratch))
+        .await
+        .context("Failed to create a scratch database for the schema files")?;
+
+    let introspected = async {
+        let scratch_conn = driver.connect(&with_database(url, &scratch)).await?;
+        for file in &files {
+            let content = std::fs::read_to_string(file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            for statement in parse_migration(&content).statements {
+                scratch_conn
+                    .execute(&statement)
+                    .await
+                    .with_context(|| format!("Failed to apply {}", file.display()))?;
+            }
+        }
+        let introspected = scratch_conn
+            .introspect_with_options(&config.introspect_options())
+            .await?;
+        scratch_conn.close().await?;
+        Ok::<_, anyhow::Error>(introspected)
+    }
+    .await;
+
+    conn.execute(&format!("DROP DATABASE IF EXISTS \"{}\" WITH (FORCE)", scratch))
+        .await
+        .context("Failed to drop the scratch database")?;
+    introspected
+}
+
+/// `url` with its database replaced by `database`, keeping any query options
+fn with_database(url: &str, database: &str) -> String {
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    };
+    let host_start = base.find("://").map_or(0, |i| i + 3);
+    let server = match base[host_start..].find('/') {
+        Some(i) => &base[..host_start + i],
+        None => base,
+    };
+    match query {
+        Some(query) => format!("{}/{}?{}", server, database, query),
+        None => format!("{}/{}", server, database),
+    }
+}
+
+/// Hex SHA-256 over the normalized schema serialized with sorted keys.
+///
+/// Roles and tablespaces belong to the cluster rather than the database, so
+/// they are left out and databases sharing a server fingerprint independently.
+/// The `migrations_table` is left out too, with its id sequence and row array
+/// type, so a migrated database and one built straight from the schema compare
+/// equal.
+pub fn fingerprint(schema: &Schema, migrations_table: &QualifiedName) -> Result<String> {
+    let mut schema = schema.normalize();
+    schema.roles.clear();
+    schema.tablespaces.clear();
+    let table_key = QualifiedName::key(&migrations_table.schema, &migrations_table.name);
+    let sequence_name = format!("{}_id_seq", migrations_table.name);
+    schema.tables.remove(&table_key);
+    schema
+        .sequences
+        .remove(&QualifiedName::key(&migrations_table.schema, &sequence_name));
+    schema
+        .array_types
+        .retain(|_, array| QualifiedName::key(&array.element_schema, &array.element_type) != table_key);
+
+    let canonical = sort_keys(serde_json::to_value(&schema)?);
+    let digest = Sha256::digest(serde_json::to_vec(&canonical)?);
+    Ok(format!("{:x}", digest))
+}
+
+/// Rebuild every object with its keys in sorted order, since the maps in
+/// `Schema` iterate in arbitrary order
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
@@ -2,10 +2,12 @@ use crate::config::Config;
 use anyhow::{Result, bail};
 use parser::ast::Statement;
 use parser::parse_file;
+use postgres::PostgresDriver;
+use shem_core::{DatabaseConnection, DatabaseDriver, QualifiedName};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tracing::info;
+use tracing::{info, warn};
 
 fn resolve_and_check(path_str: &str, base_dir: &Path) -> Result<PathBuf> {
     let path = fs::canonicalize(path_str)?;
@@ -15,7 +17,7 @@ fn resolve_and_check(path_str: &str, base_dir: &Path) -> Result<PathBuf> {
     Ok(path)
 }
 
-pub async fn execute(path: &str, _config: &Config) -> Result<()> {
+pub async fn execute(path: &str, database_url: Option<String>, config: &Config) -> Result<()> {
     let base_dir = std::env::current_dir()?;
     let path = resolve_and_check(path, &base_dir)?;
 
@@ -43,6 +45,33 @@ pub async fn execute(path: &str, _config: &Config) -> Result<()> {
     }
 
     stats.print_summary();
+
+    if let Some(url) = database_url {
+        report_unused_sequences(&url, config).await?;
+    }
+    Ok(())
+}
+
+/// Warn about sequences that nothing owns or uses, usually leftovers of dropped serial columns
+async fn report_unused_sequences(url: &str, config: &Config) -> Result<()> {
+    let driver = PostgresDriver::new()
+        .with_statement_timeout(config.database.statement_timeout_ms)
+        .with_ssl_root_cert(config.database.ssl_root_cert.clone())
+        .with_application_name(config.database.application_name.clone())
+        .with_options(config.database.options.clone());
+    let conn = driver.connect(url).await?;
+    let schema = conn.introspect().await?;
+
+    let unused = schema.unused_sequences();
+    if unused.is_empty() {
+        info!("No unused sequences found");
+    }
+    for seq in unused {
+        warn!(
+            "Unused sequence {}: not owned by a column and not referenced by any column default",
+            QualifiedName::new(seq.schema.clone(), seq.name.as_str())
+        );
+    }
     Ok(())
 }
 
@@ -1,30 +1,37 @@
-use crate::config::Config;
+use crate::commands::diff::{deferrable_clause, exclusion_definition, index_from_statement};
+use crate::config::{Config, OutputFormat};
 use anyhow::{Result as AnyhowResult, anyhow};
 use async_trait::async_trait;
-use parser::{ast::Statement, parse_sql};
+use parser::{
+    ast::{Comment as ParserComment, ObjectType, Statement},
+    parse_sql,
+};
 use petgraph::algo::toposort;
 use petgraph::graph::DiGraph;
-use postgres::PostgresDriver;
+use postgres::{PostgresDriver, PostgresSqlGenerator};
 use regex;
 use shared_types::{
-    CheckOption as ParserCheckOption, FunctionReturn, ParameterMode as ParserParameterMode,
-    TableConstraint, TriggerEvent as ParserTriggerEvent, TriggerWhen,
+    CheckOption as ParserCheckOption, CollationProvider as ParserCollationProvider,
+    FunctionReturn, ParameterMode as ParserParameterMode, TableConstraint,
+    TriggerEvent as ParserTriggerEvent, TriggerWhen,
 };
 use shem_core::{
-    DatabaseConnection, DatabaseDriver, Error, Result, Schema,
+    DatabaseConnection, DatabaseDriver, Error, IntrospectOptions, QualifiedName, Result, Schema,
+    migration::storage_clause,
     schema::{
-        CheckOption, Collation, CollationProvider, Column, CompositeType, Constraint,
+        CheckOption, Collation, CollationProvider, Column, ColumnStorage, CompositeType, Constraint,
         ConstraintKind, ConstraintTrigger, Domain, EnumType, EventTrigger, EventTriggerEvent,
-        Extension, Function, GeneratedColumn, Identity, MaterializedView, NamedSchema,
+        Extension, Function, GeneratedColumn, Identity, Index, IndexMethod, MaterializedView,
+        NamedSchema, SortOrder,
         ParallelSafety, Parameter, ParameterMode, Policy, PolicyCommand, Procedure, RangeType,
         ReferentialAction, ReturnKind, ReturnType, Rule, RuleEvent, Sequence, Table, Trigger, TriggerEvent,
         TriggerLevel, TriggerTiming, View, Volatility, Server, Publication, Subscription, Role,
-        Tablespace, ForeignKeyConstraint, BaseType, ArrayType, MultirangeType,
+        Tablespace, ForeignKeyConstraint, ForeignTable, BaseType, MultirangeType, PartitionMethod,
     },
-    traits::SchemaSerializer,
+    traits::{SchemaSerializer, SqlGenerator},
 };
 use std::path::PathBuf;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 /// Represents all schema objects that can be created
 #[derive(Debug, Clone)]
@@ -49,13 +56,13 @@ enum SchemaObject<'a> {
     // Add missing objects that are introspected but not generated
     NamedSchema(&'a NamedSchema),
     Server(&'a Server),
+    ForeignTable(&'a ForeignTable),
     Publication(&'a Publication),
     Subscription(&'a Subscription),
     Role(&'a Role),
     Tablespace(&'a Tablespace),
     ForeignKeyConstraint(&'a ForeignKeyConstraint),
     BaseType(&'a BaseType),
-    ArrayType(&'a ArrayType),
     MultirangeType(&'a MultirangeType),
 }
 
@@ -81,13 +88,13 @@ impl<'a> SchemaObject<'a> {
             SchemaObject::Rule(r) => r.name.clone(),
             SchemaObject::NamedSchema(ns) => ns.name.clone(),
             SchemaObject::Server(s) => s.name.clone(),
+            SchemaObject::ForeignTable(t) => t.name.clone(),
             SchemaObject::Publication(p) => p.name.clone(),
             SchemaObject::Subscription(s) => s.name.clone(),
             SchemaObject::Role(r) => r.name.clone(),
             SchemaObject::Tablespace(t) => t.name.clone(),
             SchemaObject::ForeignKeyConstraint(fk) => fk.name.clone(),
             SchemaObject::BaseType(b) => b.name.clone(),
-            SchemaObject::ArrayType(a) => a.name.clone(),
             SchemaObject::MultirangeType(m) => m.name.clone(),
         }
     }
@@ -114,22 +121,78 @@ impl<'a> SchemaObject<'a> {
             // Objects that don't have schemas
             SchemaObject::NamedSchema(_) => None, // NamedSchema is the schema itself
             SchemaObject::Server(_) => None, // Servers don't have schemas
+            SchemaObject::ForeignTable(t) => t.schema.clone(),
             SchemaObject::Publication(_) => None, // Publications don't have schemas
             SchemaObject::Subscription(_) => None, // Subscriptions don't have schemas
             SchemaObject::Role(_) => None, // Roles don't have schemas
             SchemaObject::Tablespace(_) => None, // Tablespaces don't have schemas
             SchemaObject::ForeignKeyConstraint(fk) => fk.schema.clone(),
             SchemaObject::BaseType(b) => b.schema.clone(),
-            SchemaObject::ArrayType(a) => a.schema.clone(),
             SchemaObject::MultirangeType(m) => m.schema.clone(),
         }
     }
 
     fn get_full_name(&self) -> String {
-        if let Some(schema) = self.get_schema() {
-            format!("{}.{}", schema, self.get_name())
-        } else {
-            self.get_name()
+        QualifiedName::new(self.get_schema(), self.get_name()).to_sql(false)
+    }
+
+    /// File the object is written to when introspection output is split
+    fn category(&self) -> &'static str {
+        match self {
+            SchemaObject::Extension(_) => "extensions",
+            SchemaObject::Collation(_) => "collations",
+            SchemaObject::Enum(_)
+            | SchemaObject::CompositeType(_)
+            | SchemaObject::RangeType(_)
+            | SchemaObject::BaseType(_)
+            | SchemaObject::MultirangeType(_) => "types",
+            SchemaObject::Domain(_) => "domains",
+            SchemaObject::Sequence(_) => "sequences",
+            SchemaObject::Table(_) => "tables",
+            SchemaObject::View(_) | SchemaObject::MaterializedView(_) => "views",
+            SchemaObject::Function(_) => "functions",
+            SchemaObject::Procedure(_) => "procedures",
+            SchemaObject::Trigger(_)
+            | SchemaObject::ConstraintTrigger(_)
+            | SchemaObject::EventTrigger(_) => "triggers",
+            SchemaObject::Policy(_) => "policies",
+            SchemaObject::Rule(_) => "rules",
+            SchemaObject::NamedSchema(_) => "schemas",
+            SchemaObject::Server(_) => "servers",
+            SchemaObject::ForeignTable(_) => "foreign_tables",
+            SchemaObject::Publication(_) => "publications",
+            SchemaObject::Subscription(_) => "subscriptions",
+            SchemaObject::Role(_) => "roles",
+            SchemaObject::Tablespace(_) => "tablespaces",
+            SchemaObject::ForeignKeyConstraint(_) => "foreign_keys",
+        }
+    }
+}
+
+/// Which statements beyond the object definitions end up in the output
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OutputToggles {
+    /// Wrap owned objects in `SET ROLE <owner>; ... RESET ROLE;` so a replay keeps ownership
+    pub set_role: bool,
+    /// Never emit `AUTHORIZATION` on created schemas
+    pub no_owner: bool,
+    /// Leave out `COMMENT ON` statements
+    pub no_comments: bool,
+    /// Leave out `GRANT` statements
+    pub no_grants: bool,
+    /// Leave out `SECURITY LABEL` statements
+    pub no_security_labels: bool,
+}
+
+impl OutputToggles {
+    /// Every toggle set to keep only `CREATE` statements (`--objects-only`)
+    pub fn objects_only() -> Self {
+        OutputToggles {
+            set_role: false,
+            no_owner: true,
+            no_comments: true,
+            no_grants: true,
+            no_security_labels: true,
         }
     }
 }
@@ -138,8 +201,26 @@ pub async fn execute(
     database_url: Option<String>,
     output: PathBuf,
     config: &Config,
-    verbose: bool,
+    toggles: OutputToggles,
+    concurrency: Option<usize>,
+    redact_secrets: bool,
+    atomic: bool,
+    prefer_serial: bool,
+    with_drops: bool,
 ) -> AnyhowResult<()> {
+    // A transaction wraps a single SQL file, split output is replayed file by file
+    if atomic && (config.output.split || config.output.format != OutputFormat::Sql) {
+        return Err(anyhow!("--atomic requires single-file SQL output"));
+    }
+    // JSON always holds the whole introspected schema
+    if toggles != OutputToggles::default() && config.output.format != OutputFormat::Sql {
+        return Err(anyhow!(
+            "--set-role, --no-owner, --no-comments, --no-grants, --no-security-labels and \
+             --objects-only require SQL output"
+        ));
+    }
+    validate_object_order(&config.output.object_order)?;
+
     // Connect to database
     let driver = get_driver(config)?;
     let db_url = database_url.unwrap_or_else(|| {
@@ -152,7 +233,19 @@ pub async fn execute(
 
     // Introspect database
     info!("Introspecting database schema");
-    let schema = conn.introspect().await?;
+    let options = IntrospectOptions {
+        concurrency,
+        redact_secrets,
+        prefer_serial,
+        ..config.introspect_options()
+    };
+    let schema = conn.introspect_with_options(&options).await?;
+    debug!(
+        "Introspected {} tables, {} views, {} functions",
+        schema.tables.len(),
+        schema.views.len(),
+        schema.functions.len()
+    );
 
     // Create output directory if it doesn't exist
     if !output.exists() {
@@ -160,62 +253,138 @@ pub async fn execute(
             .map_err(|e| anyhow!("Failed to create output directory: {}", e))?;
     }
 
+    if config.output.split && config.output.format == OutputFormat::Sql {
+        let serializer = SqlSerializer {
+            toggles,
+            atomic: false,
+            with_drops,
+            object_order: config.output.object_order.clone(),
+        };
+        for (file_name, content) in serializer.serialize_split(&schema)? {
+            let path = output.join(file_name);
+            std::fs::write(&path, content)
+                .map_err(|e| anyhow!("Failed to write schema file: {}", e))?;
+        }
+        info!("Schema split into files under {}", output.display());
+        return Ok(());
+    }
+
     // Get serializer based on config
-    let serializer = get_serializer(config)?;
+    let serializer = get_serializer(config, toggles, atomic, with_drops)?;
 
     // Serialize schema
     let content = serializer.serialize(&schema).await?;
 
     // Write schema file
-    let schema_file = output.join("schema.sql");
+    let schema_file = output.join(format!("schema.{}", serializer.extension()));
     std::fs::write(&schema_file, content)
         .map_err(|e| anyhow!("Failed to write schema file: {}", e))?;
 
-    if verbose {
-        info!("Schema written to {}", schema_file.display());
-        info!(
-            "Introspected {} tables, {} views, {} functions",
-            schema.tables.len(),
-            schema.views.len(),
-            schema.functions.len()
-        );
-    } else {
-        info!("Schema written to {}", schema_file.display());
-    }
+    info!("Schema written to {}", schema_file.display());
 
     Ok(())
 }
 
-fn get_driver(_config: &Config) -> AnyhowResult<Box<dyn DatabaseDriver>> {
+fn get_driver(config: &Config) -> AnyhowResult<Box<dyn DatabaseDriver>> {
     // TODO: Support multiple database drivers
-    Ok(Box::new(PostgresDriver::new()))
+    Ok(Box::new(
+        PostgresDriver::new()
+            .with_statement_timeout(config.database.statement_timeout_ms)
+            .with_ssl_root_cert(config.database.ssl_root_cert.clone())
+            .with_application_name(config.database.application_name.clone())
+            .with_options(config.database.options.clone()),
+    ))
 }
 
-fn get_serializer(_config: &Config) -> AnyhowResult<Box<dyn SchemaSerializer>> {
-    Ok(Box::new(SqlSerializer))
+fn get_serializer(
+    config: &Config,
+    toggles: OutputToggles,
+    atomic: bool,
+    with_drops: bool,
+) -> AnyhowResult<Box<dyn SchemaSerializer>> {
+    match config.output.format {
+        OutputFormat::Sql => Ok(Box::new(SqlSerializer {
+            toggles,
+            atomic,
+            with_drops,
+            object_order: config.output.object_order.clone(),
+        })),
+        OutputFormat::Json => Ok(Box::new(JsonSerializer)),
+    }
 }
 
-pub struct SqlSerializer;
+/// Dumps the whole schema as pretty JSON, a snapshot independent of SQL formatting
+#[derive(Default)]
+pub struct JsonSerializer;
 
 #[async_trait]
-impl SchemaSerializer for SqlSerializer {
+impl SchemaSerializer for JsonSerializer {
     async fn serialize(&self, schema: &Schema) -> Result<String> {
-        let mut sql = String::new();
+        // Going through a `Value` sorts map keys, so the snapshot is stable across runs
+        Ok(serde_json::to_string_pretty(&serde_json::to_value(schema)?)?)
+    }
+
+    async fn deserialize(&self, content: &str) -> Result<Schema> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
+
+#[derive(Default)]
+pub struct SqlSerializer {
+    /// Ownership, comment and grant output
+    pub toggles: OutputToggles,
+    /// Wrap the output in `BEGIN; ... COMMIT;` so it applies all or nothing
+    pub atomic: bool,
+    /// Drop every object before creating it again, so the output resets a database
+    pub with_drops: bool,
+    /// Object categories created first, in this order (`[output] object_order`)
+    pub object_order: Vec<String>,
+}
+
+impl SqlSerializer {
+    /// Owner for `CREATE SCHEMA ... AUTHORIZATION`, kept only when the role is
+    /// introspected too and so will exist wherever the schema is replayed
+    fn schema_owner<'a>(&self, named_schema: &'a NamedSchema, schema: &Schema) -> Option<&'a str> {
+        named_schema
+            .owner
+            .as_deref()
+            .filter(|owner| !self.toggles.no_owner && schema.roles.contains_key(*owner))
+    }
+
+    /// Render every creation statement in dependency order, tagged with the
+    /// category file it belongs to when the output is split
+    fn render_statements(&self, schema: &Schema) -> Result<Vec<(&'static str, String)>> {
+        let mut statements = Vec::new();
 
         // Validate schema objects first
         validate_schema_objects(schema)?;
 
-        // Generate schema creation statements first
-        for (_, named_schema) in &schema.named_schemas {
-            sql.push_str(&generate_create_schema(named_schema)?);
-            sql.push_str(";\n\n");
+        // Resolve all object dependencies and get creation order
+        let creation_order =
+            apply_object_order(resolve_schema_dependencies(schema)?, &self.object_order);
+
+        // Tear down existing objects before anything is created
+        if self.with_drops {
+            statements.push(("drops", generate_drops(&creation_order)?));
         }
 
-        // Resolve all object dependencies and get creation order
-        let creation_order = resolve_schema_dependencies(schema)?;
+        // Generate schema creation statements first
+        for named_schema in sorted_by_key(&schema.named_schemas) {
+            let owner = self.schema_owner(named_schema, schema);
+            statements.push((
+                "schemas",
+                format!("{};\n\n", generate_create_schema(named_schema, owner)?),
+            ));
+        }
 
         // Generate SQL statements in dependency order
         for object in creation_order {
+            let category = object.category();
+            let mut sql = String::new();
             match object {
                 SchemaObject::Extension(ext) => {
                     sql.push_str(&generate_create_extension(ext)?);
@@ -226,40 +395,64 @@ impl SchemaSerializer for SqlSerializer {
                     sql.push_str(";\n\n");
                 }
                 SchemaObject::Enum(enum_type) => {
-                    sql.push_str(&generate_create_enum(enum_type)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(
+                        &mut sql,
+                        enum_type.owner.as_deref(),
+                        generate_create_enum(enum_type)?,
+                    );
                 }
                 SchemaObject::CompositeType(type_def) => {
-                    sql.push_str(&generate_create_composite_type(type_def)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(
+                        &mut sql,
+                        type_def.owner.as_deref(),
+                        generate_create_composite_type(type_def)?,
+                    );
                 }
                 SchemaObject::RangeType(range_type) => {
-                    sql.push_str(&generate_create_range_type(range_type)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(
+                        &mut sql,
+                        range_type.owner.as_deref(),
+                        generate_create_range_type(range_type)?,
+                    );
                 }
                 SchemaObject::Domain(domain) => {
-                    sql.push_str(&generate_create_domain(domain)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(&mut sql, domain.owner.as_deref(), generate_create_domain(domain)?);
                 }
                 SchemaObject::Sequence(seq) => {
-                    sql.push_str(&generate_create_sequence(seq)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(&mut sql, seq.owner.as_deref(), generate_create_sequence(seq)?);
                 }
                 SchemaObject::Table(table) => {
-                    sql.push_str(&generate_create_table(table)?);
-                    sql.push_str(";\n\n");
+                    let mut statement = generate_create_table(table, schema)?;
+                    push_sequence_ownership(&mut statement, table, schema);
+                    push_indexes(
+                        &mut statement,
+                        table.schema.as_deref(),
+                        &table.name,
+                        &table.indexes,
+                        &table.constraints,
+                    );
+                    self.push_owned(&mut sql, table.owner.as_deref(), statement);
                 }
                 SchemaObject::View(view) => {
-                    sql.push_str(&generate_create_view(view)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(&mut sql, view.owner.as_deref(), generate_create_view(view)?);
                 }
                 SchemaObject::MaterializedView(view) => {
-                    sql.push_str(&generate_create_materialized_view(view)?);
-                    sql.push_str(";\n\n");
+                    let mut statement = generate_create_materialized_view(view)?;
+                    push_indexes(
+                        &mut statement,
+                        view.schema.as_deref(),
+                        &view.name,
+                        &view.indexes,
+                        &[],
+                    );
+                    self.push_owned(&mut sql, view.owner.as_deref(), statement);
                 }
                 SchemaObject::Function(func) => {
-                    sql.push_str(&generate_create_function(func)?);
-                    sql.push_str(";\n\n");
+                    self.push_owned(
+                        &mut sql,
+                        func.owner.as_deref(),
+                        generate_create_function(func)?,
+                    );
                 }
                 SchemaObject::Procedure(proc) => {
                     sql.push_str(&generate_create_procedure(proc)?);
@@ -286,13 +479,18 @@ impl SchemaSerializer for SqlSerializer {
                     sql.push_str(";\n\n");
                 }
                 SchemaObject::NamedSchema(ns) => {
-                    sql.push_str(&generate_create_schema(ns)?);
+                    let owner = self.schema_owner(ns, schema);
+                    sql.push_str(&generate_create_schema(ns, owner)?);
                     sql.push_str(";\n\n");
                 }
                 SchemaObject::Server(s) => {
                     sql.push_str(&generate_create_server(s)?);
                     sql.push_str(";\n\n");
                 }
+                SchemaObject::ForeignTable(t) => {
+                    sql.push_str(&generate_create_foreign_table(t)?);
+                    sql.push_str(";\n\n");
+                }
                 SchemaObject::Publication(p) => {
                     sql.push_str(&generate_create_publication(p)?);
                     sql.push_str(";\n\n");
@@ -304,6 +502,14 @@ impl SchemaSerializer for SqlSerializer {
                 SchemaObject::Role(r) => {
                     sql.push_str(&generate_create_role(r)?);
                     sql.push_str(";\n\n");
+                    for (name, value) in &r.settings {
+                        sql.push_str(&format!(
+                            "ALTER ROLE {} SET {} TO {};\n\n",
+                            quote_ident(&r.name),
+                            name,
+                            PostgresSqlGenerator::role_setting_value(name, value)
+                        ));
+                    }
                 }
                 SchemaObject::Tablespace(t) => {
                     sql.push_str(&generate_create_tablespace(t)?);
@@ -317,21 +523,100 @@ impl SchemaSerializer for SqlSerializer {
                     sql.push_str(&generate_create_base_type(b)?);
                     sql.push_str(";\n\n");
                 }
-                SchemaObject::ArrayType(a) => {
-                    sql.push_str(&generate_create_array_type(a)?);
-                    sql.push_str(";\n\n");
-                }
                 SchemaObject::MultirangeType(m) => {
                     sql.push_str(&generate_create_multirange_type(m)?);
                     sql.push_str(";\n\n");
                 }
             }
+            statements.push((category, sql));
         }
 
         // Generate COMMENT statements at the end
-        sql.push_str(&generate_comments(schema)?);
+        if !self.toggles.no_comments {
+            statements.push(("comments", generate_comments(schema)?));
+        }
+
+        // Generate GRANT statements after all objects exist
+        if !self.toggles.no_grants {
+            statements.push(("grants", generate_grants(schema)?));
+        }
+
+        if !self.toggles.no_security_labels {
+            statements.push(("security_labels", generate_security_labels(schema)));
+        }
+
+        Ok(statements)
+    }
+
+    /// Serialize into one `<category>.sql` file per object category plus a
+    /// `schema.sql` entry point that includes them with `\ir`, following the
+    /// dependency order. A category that comes back after others, such as the
+    /// functions table checks call ahead of the tables, continues in a
+    /// numbered chunk (`functions_2.sql`) included at that point.
+    pub fn serialize_split(&self, schema: &Schema) -> Result<Vec<(String, String)>> {
+        let mut files: Vec<(String, String)> = Vec::new();
+        let mut chunks: std::collections::HashMap<&'static str, usize> =
+            std::collections::HashMap::new();
+        let mut last_category = None;
+        for (category, sql) in self.render_statements(schema)? {
+            if sql.is_empty() {
+                continue;
+            }
+            if last_category == Some(category)
+                && let Some((_, content)) = files.last_mut()
+            {
+                content.push_str(&sql);
+                continue;
+            }
+            let chunk = chunks.entry(category).or_insert(0);
+            *chunk += 1;
+            let name = match *chunk {
+                1 => category.to_string(),
+                n => format!("{}_{}", category, n),
+            };
+            files.push((name, sql));
+            last_category = Some(category);
+        }
 
-        Ok(sql)
+        let entry_point: String = files
+            .iter()
+            .map(|(name, _)| format!("\\ir {}.sql\n", name))
+            .collect();
+
+        let mut output: Vec<(String, String)> = files
+            .into_iter()
+            .map(|(name, content)| (format!("{}.sql", name), end_with_newline(content)))
+            .collect();
+        output.push(("schema.sql".to_string(), entry_point));
+        Ok(output)
+    }
+
+    /// Append a creation statement, switching to the owning role first when enabled
+    fn push_owned(&self, sql: &mut String, owner: Option<&str>, statement: String) {
+        match owner.filter(|_| self.toggles.set_role) {
+            Some(owner) => {
+                sql.push_str(&format!("SET ROLE {};\n", quote_ident(owner)));
+                sql.push_str(&statement);
+                sql.push_str(";\nRESET ROLE;\n\n");
+            }
+            None => {
+                sql.push_str(&statement);
+                sql.push_str(";\n\n");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SchemaSerializer for SqlSerializer {
+    async fn serialize(&self, schema: &Schema) -> Result<String> {
+        let statements = self.render_statements(schema)?;
+        if self.atomic {
+            return Ok(end_with_newline(wrap_in_transaction(statements)));
+        }
+        Ok(end_with_newline(
+            statements.into_iter().map(|(_, sql)| sql).collect(),
+        ))
     }
 
     async fn deserialize(&self, content: &str) -> Result<Schema> {
@@ -339,6 +624,8 @@ impl SchemaSerializer for SqlSerializer {
 
         // Parse SQL statements
         let statements = parse_sql(content).map_err(|e| Error::Schema(e.to_string()))?;
+        let mut indexes = Vec::new();
+        let mut comments = Vec::new();
 
         for stmt in statements {
             match stmt {
@@ -358,8 +645,11 @@ impl SchemaSerializer for SqlSerializer {
                         schema: create.schema,
                         values: create.values,
                         comment: None,
+                        owner: None,
                     };
-                    schema.enums.insert(enum_type.name.clone(), enum_type);
+                    schema
+                        .enums
+                        .insert(QualifiedName::key(&enum_type.schema, &enum_type.name), enum_type);
                 }
                 Statement::CreateType(_create) => {
                     // Handle composite types - they can be stored in a separate collection if needed
@@ -374,8 +664,9 @@ impl SchemaSerializer for SqlSerializer {
                         default: None,
                         not_null: false,
                         comment: None,
+                        owner: None,
                     };
-                    schema.domains.insert(domain.name.clone(), domain);
+                    schema.domains.insert(QualifiedName::key(&domain.schema, &domain.name), domain);
                 }
                 Statement::CreateSequence(create) => {
                     let sequence = Sequence {
@@ -390,8 +681,11 @@ impl SchemaSerializer for SqlSerializer {
                         cycle: create.cycle,
                         owned_by: None,
                         comment: None,
+                        owner: None,
                     };
-                    schema.sequences.insert(sequence.name.clone(), sequence);
+                    schema
+                        .sequences
+                        .insert(QualifiedName::key(&sequence.schema, &sequence.name), sequence);
                 }
                 Statement::CreateTable(create) => {
                     let table = Table {
@@ -422,25 +716,45 @@ impl SchemaSerializer for SqlSerializer {
                                 collation: None,
                                 storage: None,
                                 compression: None,
+                                inherited: false,
+                                grants: Vec::new(),
                             })
                             .collect(),
                         constraints: create
                             .constraints
                             .into_iter()
                             .map(|c| match c {
-                                TableConstraint::PrimaryKey { columns, name } => Constraint {
+                                TableConstraint::PrimaryKey {
+                                    columns,
+                                    name,
+                                    deferrable,
+                                    initially_deferred,
+                                } => Constraint {
                                     name: name.unwrap_or_default(),
                                     kind: ConstraintKind::PrimaryKey,
-                                    definition: format!("PRIMARY KEY ({})", columns.join(", ")),
-                                    deferrable: false,
-                                    initially_deferred: false,
+                                    definition: format!(
+                                        "PRIMARY KEY ({}){}",
+                                        columns.join(", "),
+                                        deferrable_clause(deferrable, initially_deferred)
+                                    ),
+                                    deferrable,
+                                    initially_deferred,
                                 },
-                                TableConstraint::Unique { columns, name } => Constraint {
+                                TableConstraint::Unique {
+                                    columns,
+                                    name,
+                                    deferrable,
+                                    initially_deferred,
+                                } => Constraint {
                                     name: name.unwrap_or_default(),
                                     kind: ConstraintKind::Unique,
-                                    definition: format!("UNIQUE ({})", columns.join(", ")),
-                                    deferrable: false,
-                                    initially_deferred: false,
+                                    definition: format!(
+                                        "UNIQUE ({}){}",
+                                        columns.join(", "),
+                                        deferrable_clause(deferrable, initially_deferred)
+                                    ),
+                                    deferrable,
+                                    initially_deferred,
                                 },
                                 TableConstraint::Check { expression, name } => Constraint {
                                     name: name.unwrap_or_default(),
@@ -480,31 +794,27 @@ impl SchemaSerializer for SqlSerializer {
                                 } => Constraint {
                                     name: name.unwrap_or_default(),
                                     kind: ConstraintKind::Exclusion,
-                                    definition: format!(
-                                        "EXCLUDE USING {} ({})",
-                                        using,
-                                        elements
-                                            .iter()
-                                            .map(|e| format!(
-                                                "{:?} WITH {}",
-                                                e.expression, e.operator
-                                            ))
-                                            .collect::<Vec<_>>()
-                                            .join(", ")
-                                    ),
+                                    definition: exclusion_definition(&elements, &using),
                                     deferrable: false,
                                     initially_deferred: false,
                                 },
                             })
                             .collect(),
-                        indexes: Vec::new(), // TODO: Extract indexes from CREATE INDEX statements
+                        indexes: Vec::new(), // Filled from CREATE INDEX statements below
                         comment: None,
                         tablespace: None,
                         inherits: Vec::new(),
                         partition_by: None,
                         storage_parameters: std::collections::HashMap::new(),
+                        grants: Vec::new(),
+                        owner: None,
+                        unlogged: false,
+                        partition_bound: None,
+                        row_security: false,
+                        force_row_security: false,
+                        of_type: None,
                     };
-                    schema.tables.insert(table.name.clone(), table);
+                    schema.tables.insert(QualifiedName::key(&table.schema, &table.name), table);
                 }
                 Statement::CreateView(create) => {
                     let view = View {
@@ -521,8 +831,10 @@ impl SchemaSerializer for SqlSerializer {
                         comment: None,
                         security_barrier: false,
                         columns: Vec::new(),
+                        owner: None,
+                        column_comments: std::collections::HashMap::new(),
                     };
-                    schema.views.insert(view.name.clone(), view);
+                    schema.views.insert(QualifiedName::key(&view.schema, &view.name), view);
                 }
                 Statement::CreateMaterializedView(create) => {
                     let view = MaterializedView {
@@ -535,8 +847,12 @@ impl SchemaSerializer for SqlSerializer {
                         storage_parameters: std::collections::HashMap::new(),
                         indexes: Vec::new(),
                         populate_with_data: true, // Default to WITH DATA for parsed statements
+                        column_comments: std::collections::HashMap::new(),
+                        owner: None,
                     };
-                    schema.materialized_views.insert(view.name.clone(), view);
+                    schema
+                        .materialized_views
+                        .insert(QualifiedName::key(&view.schema, &view.name), view);
                 }
                 Statement::CreateFunction(create) => {
                     let function = Function {
@@ -586,8 +902,12 @@ impl SchemaSerializer for SqlSerializer {
                         parallel_safety: ParallelSafety::Unsafe,
                         cost: None,
                         rows: None,
+                        owner: None,
                     };
-                    schema.functions.insert(function.name.clone(), function);
+                    schema.functions.insert(
+                        QualifiedName::key(&function.schema, &function.signature()),
+                        function,
+                    );
                 }
                 Statement::CreateProcedure(create) => {
                     let procedure = Procedure {
@@ -616,7 +936,9 @@ impl SchemaSerializer for SqlSerializer {
                         comment: None,
                         security_definer: false,
                     };
-                    schema.procedures.insert(procedure.name.clone(), procedure);
+                    schema
+                        .procedures
+                        .insert(QualifiedName::key(&procedure.schema, &procedure.name), procedure);
                 }
                 Statement::CreateTrigger(create) => {
                     let trigger = Trigger {
@@ -638,6 +960,7 @@ impl SchemaSerializer for SqlSerializer {
                                 ParserTriggerEvent::Truncate => TriggerEvent::Truncate,
                             })
                             .collect(),
+                        update_columns: Vec::new(),
                         function: create.function,
                         arguments: create.arguments,
                         condition: None,
@@ -645,7 +968,12 @@ impl SchemaSerializer for SqlSerializer {
                         comment: None,
                         when: None,
                     };
-                    schema.triggers.insert(trigger.name.clone(), trigger);
+                    // Keyed like introspected triggers, by table and name
+                    let relation = QualifiedName::parse(&trigger.table);
+                    let name = format!("{}.{}", relation.name, trigger.name);
+                    schema
+                        .triggers
+                        .insert(QualifiedName::key(&relation.schema, &name), trigger);
                 }
                 Statement::CreatePolicy(create) => {
                     let policy = Policy {
@@ -657,14 +985,62 @@ impl SchemaSerializer for SqlSerializer {
                         roles: create.roles,
                         using: create.using.map(|e| format!("{:?}", e)),
                         check: create.with_check.map(|e| format!("{:?}", e)),
+                        comment: None,
                     };
-                    schema.policies.insert(policy.name.clone(), policy);
+                    let relation = QualifiedName::parse(&policy.table);
+                    let name = format!("{}.{}", relation.name, policy.name);
+                    schema
+                        .policies
+                        .insert(QualifiedName::key(&relation.schema, &name), policy);
                 }
+                Statement::CreateCollation(create) => {
+                    let collation = Collation {
+                        name: create.name,
+                        schema: create.schema,
+                        locale: None,
+                        lc_collate: create.lc_collate,
+                        lc_ctype: create.lc_ctype,
+                        provider: match create.provider {
+                            ParserCollationProvider::Libc => CollationProvider::Libc,
+                            ParserCollationProvider::Icu => CollationProvider::Icu,
+                            ParserCollationProvider::Builtin => CollationProvider::Builtin,
+                        },
+                        deterministic: create.deterministic,
+                        comment: None,
+                    };
+                    schema
+                        .collations
+                        .insert(QualifiedName::key(&collation.schema, &collation.name), collation);
+                }
+                Statement::CreateIndex(create) => indexes.push(create),
+                Statement::Comment(comment) => comments.push(comment),
 
                 _ => {}
             }
         }
 
+        // Indexes may be declared before or after their table
+        for create in indexes {
+            let index =
+                index_from_statement(&create).map_err(|e| Error::Schema(e.to_string()))?;
+            let key = QualifiedName::key(&create.schema, &create.table);
+            if let Some(table) = schema.tables.get_mut(&key) {
+                table.indexes.push(index);
+            } else if let Some(view) = schema.materialized_views.get_mut(&key) {
+                view.indexes.push(index);
+            } else {
+                return Err(Error::Schema(format!(
+                    "Index {} references unknown table {}",
+                    create.name, create.table
+                )));
+            }
+        }
+
+        // Comments are emitted after every object, so apply them last
+        for comment in comments {
+            apply_comment(&mut schema, comment);
+        }
+
         Ok(schema)
     }
 
@@ -673,86 +1049,289 @@ impl SchemaSerializer for SqlSerializer {
     }
 }
 
+/// `DROP ... IF EXISTS ... CASCADE` for every object, in reverse creation order so
+/// dependents go before what they depend on. Roles and tablespaces are shared by the
+/// whole cluster and the `public` schema comes with every database, so they are kept.
+fn generate_drops(creation_order: &[SchemaObject]) -> Result<String> {
+    let generator = PostgresSqlGenerator;
+    let mut drops = String::new();
+    let mut seen = std::collections::HashSet::new();
+
+    for object in creation_order.iter().rev() {
+        let statement = match object {
+            SchemaObject::Extension(ext) => generator.drop_extension(ext)?,
+            SchemaObject::Collation(collation) => generator.drop_collation(collation)?,
+            SchemaObject::Enum(_) => {
+                let name = QualifiedName::new(object.get_schema(), object.get_name());
+                format!("DROP TYPE IF EXISTS {} CASCADE;", name.to_sql(true))
+            }
+            SchemaObject::CompositeType(t) => generator.drop_composite_type(t)?,
+            SchemaObject::RangeType(t) => generator.drop_range_type(t)?,
+            SchemaObject::Domain(domain) => generator.drop_domain(domain)?,
+            SchemaObject::Sequence(seq) => generator.drop_sequence(seq)?,
+            SchemaObject::Table(table) => generator.generate_drop_table(table)?,
+            SchemaObject::View(view) => generator.drop_view(view)?,
+            SchemaObject::MaterializedView(view) => generator.drop_materialized_view(view)?,
+            SchemaObject::Function(func) => generator.drop_function(func)?,
+            SchemaObject::Procedure(proc) => generator.drop_procedure(proc)?,
+            SchemaObject::Trigger(trigger) => generator.drop_trigger(trigger)?,
+            SchemaObject::ConstraintTrigger(trigger) => generator.drop_constraint_trigger(trigger)?,
+            SchemaObject::EventTrigger(trigger) => generator.drop_event_trigger(trigger)?,
+            SchemaObject::Policy(policy) => generator.drop_policy(policy)?,
+            SchemaObject::Rule(rule) => generator.drop_rule(rule)?,
+            SchemaObject::NamedSchema(ns) if ns.name == "public" => continue,
+            SchemaObject::NamedSchema(ns) => format!(
+                "DROP SCHEMA IF EXISTS {} CASCADE;",
+                QualifiedName::new(None, &ns.name).to_sql(true)
+            ),
+            SchemaObject::Server(s) => generator.drop_server(s)?,
+            SchemaObject::ForeignTable(t) => generator.drop_foreign_table(t)?,
+            SchemaObject::Publication(p) => generator.drop_publication(p)?,
+            SchemaObject::Subscription(s) => generator.drop_subscription(s)?,
+            SchemaObject::Role(_) | SchemaObject::Tablespace(_) => continue,
+            SchemaObject::ForeignKeyConstraint(fk) => format!(
+                "ALTER TABLE IF EXISTS {} DROP CONSTRAINT IF EXISTS {};",
+                QualifiedName::new(fk.schema.clone(), &fk.table).to_sql(true),
+                QualifiedName::new(None, &fk.name).to_sql(true)
+            ),
+            SchemaObject::BaseType(b) => generator.drop_base_type(b)?,
+            SchemaObject::MultirangeType(m) => generator.drop_multirange_type(m)?,
+        };
+        // Some objects are listed more than once in the creation order
+        if seen.insert(statement.clone()) {
+            drops.push_str(&statement);
+            drops.push_str("\n\n");
+        }
+    }
+
+    Ok(drops)
+}
+
+/// Statements are each followed by a blank line; drop the one after the last
+/// statement so files end in exactly one newline
+fn end_with_newline(sql: String) -> String {
+    let trimmed = sql.trim_end_matches('\n');
+    if trimmed.is_empty() {
+        return String::new();
+    }
+    format!("{}\n", trimmed)
+}
+
+/// Wrap rendered statements in one transaction. PostgreSQL refuses some statements inside
+/// a transaction block; tablespaces are created before `BEGIN` since tables may live in
+/// them, the others run after `COMMIT`.
+fn wrap_in_transaction(statements: Vec<(&'static str, String)>) -> String {
+    let mut before = String::new();
+    let mut body = String::from("BEGIN;\n\n");
+    let mut after = String::new();
+
+    for (category, sql) in statements {
+        if sql.is_empty() {
+            continue;
+        }
+        let upper = sql.to_uppercase();
+        if category == "tablespaces" {
+            warn!("Moving CREATE TABLESPACE before BEGIN, it cannot run inside a transaction");
+            before.push_str(&sql);
+        } else if category == "subscriptions"
+            || upper.contains("CREATE INDEX CONCURRENTLY")
+            || upper.contains("CREATE DATABASE")
+        {
+            warn!(
+                "Moving {} statements after COMMIT, they cannot run inside a transaction",
+                category
+            );
+            after.push_str(&sql);
+        } else {
+            body.push_str(&sql);
+        }
+    }
+
+    body.push_str("COMMIT;\n\n");
+    format!("{}{}{}", before, body, after)
+}
+
+/// Attach a parsed `COMMENT ON` to the object it names, matching on the key the
+/// object is stored under in `Schema`
+fn apply_comment(schema: &mut Schema, parsed: ParserComment) {
+    let (qualifier, name) = parsed
+        .object_name
+        .rsplit_once('.')
+        .unwrap_or(("", &parsed.object_name));
+    // Schema-scoped objects are keyed by qualified name, columns by their relation's
+    let key = QualifiedName::parse(&parsed.object_name).to_sql(false);
+    let relation = QualifiedName::parse(qualifier).to_sql(false);
+    let comment = parsed.comment;
+
+    let target = match parsed.object_type {
+        ObjectType::Table => schema.tables.get_mut(&key).map(|t| &mut t.comment),
+        ObjectType::View => schema.views.get_mut(&key).map(|v| &mut v.comment),
+        ObjectType::MaterializedView => {
+            schema.materialized_views.get_mut(&key).map(|v| &mut v.comment)
+        }
+        ObjectType::Function => {
+            let mut overloads = schema
+                .functions
+                .values_mut()
+                .filter(|f| QualifiedName::key(&f.schema, &f.name) == key);
+            let function = match &parsed.arguments {
+                Some(arguments) => {
+                    let types: Vec<String> =
+                        arguments.iter().map(|t| format!("{:?}", t)).collect();
+                    overloads.find(|f| f.argument_types() == types.join(", "))
+                }
+                // Without an argument list the name has to pick out a single function
+                None => match (overloads.next(), overloads.next()) {
+                    (Some(function), None) => Some(function),
+                    _ => None,
+                },
+            };
+            function.map(|f| &mut f.comment)
+        }
+        ObjectType::Procedure => schema.procedures.get_mut(&key).map(|p| &mut p.comment),
+        ObjectType::Domain => schema.domains.get_mut(&key).map(|d| &mut d.comment),
+        ObjectType::Sequence => schema.sequences.get_mut(&key).map(|s| &mut s.comment),
+        ObjectType::Extension => schema.extensions.get_mut(name).map(|e| &mut e.comment),
+        ObjectType::Type => match schema.enums.get_mut(&key) {
+            Some(enum_type) => Some(&mut enum_type.comment),
+            None => schema.composite_types.get_mut(&key).map(|c| &mut c.comment),
+        },
+        ObjectType::Collation => schema.collations.get_mut(&key).map(|c| &mut c.comment),
+        // Triggers, policies and rules are keyed by their table followed by their name
+        ObjectType::Trigger => schema.triggers.get_mut(&key).map(|t| &mut t.comment),
+        ObjectType::Policy => schema.policies.get_mut(&key).map(|p| &mut p.comment),
+        ObjectType::Rule => schema.rules.get_mut(&key).map(|r| &mut r.comment),
+        ObjectType::Column => {
+            // Views keep column comments in a map rather than on a column
+            let view_columns = match schema.views.get_mut(&relation) {
+                Some(view) => Some(&mut view.column_comments),
+                None => schema
+                    .materialized_views
+                    .get_mut(&relation)
+                    .map(|v| &mut v.column_comments),
+            };
+            if let Some(column_comments) = view_columns {
+                match comment {
+                    Some(comment) => column_comments.insert(name.to_string(), comment),
+                    None => column_comments.remove(name),
+                };
+                return;
+            }
+            let columns = match schema.tables.get_mut(&relation) {
+                Some(table) => Some(&mut table.columns),
+                None => schema
+                    .composite_types
+                    .get_mut(&relation)
+                    .map(|c| &mut c.attributes),
+            };
+            columns
+                .and_then(|columns| columns.iter_mut().find(|c| c.name == name))
+                .map(|c| &mut c.comment)
+        }
+        _ => None,
+    };
+
+    match target {
+        Some(target) => *target = comment,
+        None => warn!(
+            "Ignoring comment on {:?} {} which is not declared",
+            parsed.object_type, parsed.object_name
+        ),
+    }
+}
+
 /// Resolve all schema object dependencies using petgraph for robust topological sorting
 /// with predefined hierarchy for objects without explicit dependencies
 fn resolve_schema_dependencies(schema: &Schema) -> Result<Vec<SchemaObject>> {
     let mut ordered_objects = Vec::new();
 
     // 1. Named Schemas (must come first as other objects depend on them)
-    for (_, named_schema) in &schema.named_schemas {
+    for named_schema in sorted_by_key(&schema.named_schemas) {
         ordered_objects.push(SchemaObject::NamedSchema(named_schema));
     }
 
     // 2. Extensions
-    for (_, ext) in &schema.extensions {
+    for ext in sorted_by_key(&schema.extensions) {
         ordered_objects.push(SchemaObject::Extension(ext));
     }
 
     // 3. Roles (needed for ownership)
-    for (_, role) in &schema.roles {
+    for role in sorted_by_key(&schema.roles) {
         ordered_objects.push(SchemaObject::Role(role));
     }
 
     // 4. Tablespaces (needed for storage)
-    for (_, tablespace) in &schema.tablespaces {
+    for tablespace in sorted_by_key(&schema.tablespaces) {
         ordered_objects.push(SchemaObject::Tablespace(tablespace));
     }
 
     // 5. Servers (needed for foreign data)
-    for (_, server) in &schema.servers {
+    for server in sorted_by_key(&schema.servers) {
         ordered_objects.push(SchemaObject::Server(server));
     }
 
     // 6. Base Types (fundamental types)
-    for (_, base_type) in &schema.base_types {
+    for base_type in sorted_by_key(&schema.base_types) {
         ordered_objects.push(SchemaObject::BaseType(base_type));
     }
 
     // 7. Enums
-    for (_, enum_type) in &schema.enums {
+    for enum_type in sorted_by_key(&schema.enums) {
         ordered_objects.push(SchemaObject::Enum(enum_type));
     }
 
     // 8. Domains
-    for (_, domain) in &schema.domains {
+    for domain in sorted_by_key(&schema.domains) {
         ordered_objects.push(SchemaObject::Domain(domain));
     }
 
     // 9. Composite types - moved before tables
-    for (_, composite_type) in &schema.composite_types {
+    for composite_type in sorted_by_key(&schema.composite_types) {
         ordered_objects.push(SchemaObject::CompositeType(composite_type));
     }
 
     // 10. Range types
-    for (_, range_type) in &schema.range_types {
+    for range_type in sorted_by_key(&schema.range_types) {
         ordered_objects.push(SchemaObject::RangeType(range_type));
     }
 
-    // 11. Array types
-    for (_, array_type) in &schema.array_types {
-        ordered_objects.push(SchemaObject::ArrayType(array_type));
-    }
+    // 11. Array types are created by PostgreSQL along with their element types
 
     // 12. Multirange types
-    for (_, multirange_type) in &schema.multirange_types {
+    for multirange_type in sorted_by_key(&schema.multirange_types) {
         ordered_objects.push(SchemaObject::MultirangeType(multirange_type));
     }
 
     // 13. Collations
-    for (_, collation) in &schema.collations {
+    for collation in sorted_by_key(&schema.collations) {
         ordered_objects.push(SchemaObject::Collation(collation));
     }
 
     // 14. Sequences (moved before tables)
-    for (_, seq) in &schema.sequences {
+    for seq in sorted_by_key(&schema.sequences) {
         ordered_objects.push(SchemaObject::Sequence(seq));
     }
 
+    // Functions called from table check constraints must exist before the tables.
+    // Every overload of a called name is hoisted, so the set is keyed by signature.
+    let mut check_functions = std::collections::HashSet::new();
+    for table in sorted_by_key(&schema.tables) {
+        let dependencies = get_object_dependencies(&SchemaObject::Table(table), schema);
+        for func in sorted_by_key(&schema.functions)
+            .into_iter()
+            .filter(|f| dependencies.contains(&f.name))
+        {
+            if check_functions.insert(QualifiedName::key(&func.schema, &func.signature())) {
+                ordered_objects.push(SchemaObject::Function(func));
+            }
+        }
+    }
+
     // 15. Tables (petgraph order)
     let mut table_graph = DiGraph::new();
     let mut table_name_to_index = std::collections::HashMap::new();
     let mut table_objs = Vec::new();
-    for (_, table) in &schema.tables {
+    for table in sorted_by_key(&schema.tables) {
         let obj = SchemaObject::Table(table);
         let idx = table_graph.add_node(obj.clone());
         let full_name = obj.get_full_name();
@@ -763,30 +1342,9 @@ fn resolve_schema_dependencies(schema: &Schema) -> Result<Vec<SchemaObject>> {
     for (obj, idx) in &table_objs {
         let dependencies = get_object_dependencies(obj, schema);
         for dep in dependencies {
-            // Only add edges for tables - normalize the dependency name
-            // Since extract_fk_referenced_table now returns just table names,
-            // we need to construct the full name for lookup
-            let dep_key = if dep.contains('.') {
-                // If it's already schema-qualified, use as-is
-                if table_name_to_index.contains_key(&dep) {
-                    dep.clone()
-                } else {
-                    continue; // Skip if we can't find the table
-                }
-            } else {
-                // If it's just a table name, try to find the table and get its full name
-                if let Some(table) = schema.tables.get(&dep) {
-                    if let Some(schema_name) = &table.schema {
-                        format!("{}.{}", schema_name, dep)
-                    } else {
-                        dep.clone() // No schema, use as-is
-                    }
-                } else {
-                    continue; // Skip if we can't find the table
-                }
-            };
-
-            if let Some(&dep_idx) = table_name_to_index.get(&dep_key) {
+            // Only add edges for tables; table dependencies are already keyed
+            // the same way as the table map
+            if let Some(&dep_idx) = table_name_to_index.get(&dep) {
                 table_graph.add_edge(dep_idx, *idx, ());
             }
         }
@@ -797,72 +1355,256 @@ fn resolve_schema_dependencies(schema: &Schema) -> Result<Vec<SchemaObject>> {
             .iter()
             .filter_map(|&idx| table_graph.node_weight(idx).cloned())
             .collect::<Vec<_>>(),
-        Err(_) => schema
-            .tables
-            .values()
+        Err(_) => sorted_by_key(&schema.tables)
+            .into_iter()
             .map(|t| SchemaObject::Table(t))
             .collect(),
     };
     ordered_objects.extend(sorted_tables);
 
+    // Foreign tables come after their servers (5) and the types their columns use (6-13)
+    for foreign_table in sorted_by_key(&schema.foreign_tables) {
+        ordered_objects.push(SchemaObject::ForeignTable(foreign_table));
+    }
+
     // 16. Foreign Key Constraints (after tables)
-    for (_, fk) in &schema.foreign_key_constraints {
+    for fk in sorted_by_key(&schema.foreign_key_constraints) {
         ordered_objects.push(SchemaObject::ForeignKeyConstraint(fk));
     }
 
     // 17. Views
-    for (_, view) in &schema.views {
+    for view in sorted_by_key(&schema.views) {
         ordered_objects.push(SchemaObject::View(view));
     }
 
     // 18. Materialized views
-    for (_, view) in &schema.materialized_views {
+    for view in sorted_by_key(&schema.materialized_views) {
         ordered_objects.push(SchemaObject::MaterializedView(view));
     }
 
     // 19. Publications (after tables and views)
-    for (_, publication) in &schema.publications {
+    for publication in sorted_by_key(&schema.publications) {
         ordered_objects.push(SchemaObject::Publication(publication));
     }
 
     // 20. Subscriptions (after publications)
-    for (_, subscription) in &schema.subscriptions {
+    for subscription in sorted_by_key(&schema.subscriptions) {
         ordered_objects.push(SchemaObject::Subscription(subscription));
     }
 
     // 21. Policies
-    for (_, policy) in &schema.policies {
+    for policy in sorted_by_key(&schema.policies) {
         ordered_objects.push(SchemaObject::Policy(policy));
     }
 
     // 22. Rules
-    for (_, rule) in &schema.rules {
+    for rule in sorted_by_key(&schema.rules) {
         ordered_objects.push(SchemaObject::Rule(rule));
     }
 
-    // 23. Functions
-    for (_, func) in &schema.functions {
-        ordered_objects.push(SchemaObject::Function(func));
+    // 23. Functions (those used by check constraints were placed before tables)
+    for func in sorted_by_key(&schema.functions) {
+        if !check_functions.contains(&QualifiedName::key(&func.schema, &func.signature())) {
+            ordered_objects.push(SchemaObject::Function(func));
+        }
+    }
+
+    // Procedures, which like functions only resolve names once called
+    for procedure in sorted_by_key(&schema.procedures) {
+        ordered_objects.push(SchemaObject::Procedure(procedure));
     }
 
     // 24. Event triggers
-    for (_, trigger) in &schema.event_triggers {
+    for trigger in sorted_by_key(&schema.event_triggers) {
         ordered_objects.push(SchemaObject::EventTrigger(trigger));
     }
 
     // 25. Triggers
-    for (_, trigger) in &schema.triggers {
+    for trigger in sorted_by_key(&schema.triggers) {
         ordered_objects.push(SchemaObject::Trigger(trigger));
     }
 
     // 26. Constraint triggers
-    for (_, trigger) in &schema.constraint_triggers {
+    for trigger in sorted_by_key(&schema.constraint_triggers) {
         ordered_objects.push(SchemaObject::ConstraintTrigger(trigger));
     }
 
     Ok(ordered_objects)
 }
 
+/// Objects of one kind ordered by the schema-qualified name they are keyed by,
+/// so output doesn't depend on `HashMap` iteration order
+fn sorted_by_key<V>(objects: &std::collections::HashMap<String, V>) -> Vec<&V> {
+    let mut entries: Vec<_> = objects.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries.into_iter().map(|(_, object)| object).collect()
+}
+
+/// Object categories `[output] object_order` can name, in the order they are
+/// created by default
+const OBJECT_CATEGORIES: &[&str] = &[
+    "schemas",
+    "extensions",
+    "roles",
+    "tablespaces",
+    "servers",
+    "types",
+    "domains",
+    "collations",
+    "sequences",
+    "tables",
+    "foreign_tables",
+    "foreign_keys",
+    "views",
+    "publications",
+    "subscriptions",
+    "policies",
+    "rules",
+    "functions",
+    "procedures",
+    "triggers",
+];
+
+/// Category pairs whose first member must always be created before the second.
+/// Materialized views share the views category, which keeps them after views.
+const REQUIRED_ORDER: &[(&str, &str)] = &[
+    ("schemas", "types"),
+    ("schemas", "domains"),
+    ("schemas", "collations"),
+    ("schemas", "sequences"),
+    ("schemas", "tables"),
+    ("schemas", "views"),
+    ("schemas", "functions"),
+    ("schemas", "procedures"),
+    ("types", "tables"),
+    ("domains", "tables"),
+    ("collations", "tables"),
+    ("sequences", "tables"),
+    ("tables", "foreign_keys"),
+    ("tables", "views"),
+    ("tables", "publications"),
+    ("tables", "policies"),
+    ("tables", "rules"),
+    ("tables", "triggers"),
+    ("functions", "triggers"),
+    ("servers", "foreign_tables"),
+];
+
+/// Full category order a configured `object_order` results in: the listed
+/// categories take the places they hold by default, in the configured order,
+/// and unlisted categories stay where they are
+fn implied_object_order(order: &[String]) -> Vec<&'static str> {
+    let mut listed = order
+        .iter()
+        .filter_map(|c| OBJECT_CATEGORIES.iter().find(|known| *known == c))
+        .copied();
+    OBJECT_CATEGORIES
+        .iter()
+        .map(|&category| match order.iter().any(|c| c == category) {
+            true => listed.next().unwrap_or(category),
+            false => category,
+        })
+        .collect()
+}
+
+/// Check a configured `object_order` names known categories once each and that
+/// the order it implies keeps the orderings creation depends on
+pub fn validate_object_order(order: &[String]) -> AnyhowResult<()> {
+    for (i, category) in order.iter().enumerate() {
+        if !OBJECT_CATEGORIES.contains(&category.as_str()) {
+            return Err(anyhow!(
+                "Unknown category '{}' in [output] object_order, expected one of: {}",
+                category,
+                OBJECT_CATEGORIES.join(", ")
+            ));
+        }
+        if order[..i].contains(category) {
+            return Err(anyhow!(
+                "Category '{}' is listed twice in [output] object_order",
+                category
+            ));
+        }
+    }
+
+    let implied = implied_object_order(order);
+    let position = |category: &str| implied.iter().position(|c| *c == category);
+    for (before, after) in REQUIRED_ORDER {
+        if position(before) > position(after) {
+            return Err(anyhow!(
+                "[output] object_order must keep {} before {} (resulting order: {})",
+                before,
+                after,
+                implied.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Move whole listed categories to the places `implied_object_order` gives
+/// them, keeping the dependency order of the objects within each category.
+/// Objects of unlisted categories keep their relative order untouched.
+fn apply_object_order<'a>(
+    objects: Vec<SchemaObject<'a>>,
+    order: &[String],
+) -> Vec<SchemaObject<'a>> {
+    if order.is_empty() {
+        return objects;
+    }
+
+    // Default places are spaced out so the functions check constraints call,
+    // created just ahead of the tables, get one of their own
+    let place = |category: &str| {
+        2 * OBJECT_CATEGORIES
+            .iter()
+            .position(|c| *c == category)
+            .unwrap_or(OBJECT_CATEGORIES.len())
+    };
+    let mut groups: Vec<(&'static str, Vec<SchemaObject<'a>>)> = Vec::new();
+    let mut unlisted = Vec::new();
+    let mut tables_reached = false;
+    for object in objects {
+        let category = object.category();
+        tables_reached |= category == "tables";
+        if order.iter().any(|c| c == category) {
+            match groups.iter_mut().find(|(name, _)| *name == category) {
+                Some((_, group)) => group.push(object),
+                None => groups.push((category, vec![object])),
+            }
+        } else if category == "functions" && !tables_reached {
+            unlisted.push((place("tables") - 1, object));
+        } else {
+            unlisted.push((place(category), object));
+        }
+    }
+
+    // Each listed category goes into the next place a listed category holds
+    // by default, ahead of the first unlisted object created after that place
+    let mut slots = OBJECT_CATEGORIES
+        .iter()
+        .zip(implied_object_order(order))
+        .filter(|(default, _)| order.iter().any(|c| c == *default))
+        .map(|(default, category)| (place(default), category))
+        .peekable();
+    let mut ordered = Vec::new();
+    let mut reached = 0;
+    for (object_place, object) in unlisted {
+        reached = reached.max(object_place);
+        while let Some((_, category)) = slots.next_if(|(slot, _)| *slot < reached) {
+            if let Some(index) = groups.iter().position(|(name, _)| *name == category) {
+                ordered.extend(groups.swap_remove(index).1);
+            }
+        }
+        ordered.push(object);
+    }
+    for (_, category) in slots {
+        if let Some(index) = groups.iter().position(|(name, _)| *name == category) {
+            ordered.extend(groups.swap_remove(index).1);
+        }
+    }
+    ordered
+}
+
 /// Validate schema objects for potential issues
 fn validate_schema_objects(schema: &Schema) -> Result<()> {
     let mut errors = Vec::new();
@@ -931,14 +1673,26 @@ fn validate_schema_objects(schema: &Schema) -> Result<()> {
                 ));
             }
         }
+
+        // PostgreSQL rejects a partitioned table that also inherits, partitions aside
+        if table.partition_by.is_some()
+            && !table.inherits.is_empty()
+            && table.partition_bound.is_none()
+        {
+            errors.push(format!(
+                "Table '{}' can't both inherit from other tables and be partitioned",
+                name
+            ));
+        }
     }
 
     // Check functions
     for (name, _func) in &schema.functions {
+        // Keyed by signature, so overloads of one name are distinct
         let key = format!("function:{}", name);
         if let Some(existing) = object_names.get(&key) {
             errors.push(format!(
-                "Duplicate function name: {} (already used by {})",
+                "Duplicate function signature: {} (already used by {})",
                 name, existing
             ));
         } else {
@@ -1150,6 +1904,14 @@ fn get_object_dependencies(obj: &SchemaObject, schema: &Schema) -> Vec<String> {
                     dependencies.push(ref_table);
                 }
             }
+            // Check constraints can call functions and cast to user types
+            for constraint in &table.constraints {
+                if matches!(constraint.kind, ConstraintKind::Check) {
+                    dependencies.extend(extract_check_dependencies(&constraint.definition, schema));
+                }
+            }
+            // Partitions and inheriting tables depend on their parents
+            dependencies.extend(table.inherits.iter().cloned());
         }
         SchemaObject::View(view) => {
             // Views depend on tables and other objects referenced in their definition
@@ -1180,53 +1942,33 @@ fn get_object_dependencies(obj: &SchemaObject, schema: &Schema) -> Vec<String> {
         }
         SchemaObject::Trigger(trigger) => {
             // Triggers depend on their table and function
-            let table_name = if let Some(schema) = &trigger.schema {
-                format!("{}.{}", schema, trigger.table)
-            } else {
-                trigger.table.clone()
-            };
-            dependencies.push(table_name);
-
-            let func_name = if let Some(schema) = &trigger.schema {
-                format!("{}.{}", schema, trigger.function)
-            } else {
-                trigger.function.clone()
-            };
-            dependencies.push(func_name);
+            dependencies.push(
+                QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(false),
+            );
+            dependencies.push(
+                QualifiedName::new(trigger.schema.clone(), &trigger.function).to_sql(false),
+            );
         }
         SchemaObject::ConstraintTrigger(trigger) => {
             // Constraint triggers depend on their table and function
-            let table_name = if let Some(schema) = &trigger.schema {
-                format!("{}.{}", schema, trigger.table)
-            } else {
-                trigger.table.clone()
-            };
-            dependencies.push(table_name);
-
-            let func_name = if let Some(schema) = &trigger.schema {
-                format!("{}.{}", schema, trigger.function)
-            } else {
-                trigger.function.clone()
-            };
-            dependencies.push(func_name);
+            dependencies.push(
+                QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(false),
+            );
+            dependencies.push(
+                QualifiedName::new(trigger.schema.clone(), &trigger.function).to_sql(false),
+            );
         }
         SchemaObject::Policy(policy) => {
             // Policies depend on their table
-            let table_name = if let Some(schema) = &policy.schema {
-                format!("{}.{}", schema, policy.table)
-            } else {
-                policy.table.clone()
-            };
-            dependencies.push(table_name);
+            dependencies.push(
+                QualifiedName::new(policy.schema.clone(), &policy.table).to_sql(false),
+            );
         }
         SchemaObject::Rule(rule) => {
             // Rules depend on their table
-            let table_name = if let Some(schema) = &rule.schema {
-                format!("{}.{}", schema, rule.table)
-            } else {
-                rule.table.clone()
-            };
-            dependencies.push(table_name);
+            dependencies.push(
+                QualifiedName::new(rule.schema.clone(), &rule.table).to_sql(false),
+            );
         }
         SchemaObject::Sequence(seq) => {
             // Sequences might depend on their owned_by table/column
@@ -1248,6 +1990,15 @@ fn get_object_dependencies(obj: &SchemaObject, schema: &Schema) -> Vec<String> {
                 dependencies.push(type_dep);
             }
         }
+        SchemaObject::ForeignTable(table) => {
+            // Foreign tables depend on their server and the types used in columns
+            dependencies.push(table.server.clone());
+            for column in &table.columns {
+                if let Some(type_dep) = extract_type_dependency(&column.type_name) {
+                    dependencies.push(type_dep);
+                }
+            }
+        }
         _ => {
             // Other objects don't have explicit dependencies
         }
@@ -1258,26 +2009,53 @@ fn get_object_dependencies(obj: &SchemaObject, schema: &Schema) -> Vec<String> {
 
 /// Extract referenced table name from a FOREIGN KEY constraint definition
 fn extract_fk_referenced_table(constraint_def: &str, schema: &Schema) -> Option<String> {
-    // Look for REFERENCES <table> or REFERENCES <schema>.<table>
-    let re = regex::Regex::new(r"REFERENCES ([\w\.]+)").ok()?;
+    // Look for REFERENCES <table> or REFERENCES <schema>.<table>, either part quoted
+    let re = regex::Regex::new(r#"REFERENCES ((?:"[^"]*"|\w+)(?:\.(?:"[^"]*"|\w+))?)"#).ok()?;
     if let Some(caps) = re.captures(constraint_def) {
-        let ref_name = caps.get(1)?.as_str();
-
-        // Extract just the table name (without schema)
-        let table_name = if ref_name.contains('.') {
-            ref_name.split('.').last().unwrap_or(ref_name).to_string()
-        } else {
-            ref_name.to_string()
-        };
+        // Tables are keyed by unquoted schema-qualified name, with `public` left implicit
+        let reference = caps.get(1)?.as_str().replace('"', "");
+        let key = QualifiedName::parse(&reference).to_sql(false);
 
         // Check if this table exists in our schema
-        if schema.tables.contains_key(&table_name) {
-            return Some(table_name);
+        if schema.tables.contains_key(&key) {
+            return Some(key);
         }
     }
     None
 }
 
+/// Extract functions and types referenced from a CHECK constraint definition
+fn extract_check_dependencies(definition: &str, schema: &Schema) -> Vec<String> {
+    let mut dependencies = Vec::new();
+
+    // Function calls, optionally schema-qualified
+    let call_re =
+        regex::Regex::new(r#"\b(?:[a-zA-Z_][a-zA-Z0-9_]*\.)?([a-zA-Z_][a-zA-Z0-9_]*)\s*\("#)
+            .unwrap();
+    for cap in call_re.captures_iter(definition) {
+        let func_name = &cap[1];
+        if schema.functions.values().any(|f| f.name == func_name) {
+            dependencies.push(func_name.to_string());
+        }
+    }
+
+    // Casts to user-defined types such as 'x'::status
+    let cast_re =
+        regex::Regex::new(r#"::\s*((?:[a-zA-Z_][a-zA-Z0-9_]*\.)?[a-zA-Z_][a-zA-Z0-9_]*)"#)
+            .unwrap();
+    for cap in cast_re.captures_iter(definition) {
+        let type_key = QualifiedName::parse(&cap[1]).to_sql(false);
+        if schema.enums.contains_key(&type_key)
+            || schema.domains.contains_key(&type_key)
+            || schema.composite_types.contains_key(&type_key)
+        {
+            dependencies.push(type_key);
+        }
+    }
+
+    dependencies
+}
+
 /// Extract type dependencies from a type name
 fn extract_type_dependency(type_name: &str) -> Option<String> {
     // Handle array types
@@ -1451,18 +2229,9 @@ fn extract_view_dependencies(definition: &str, schema: &Schema) -> Vec<String> {
         .unwrap();
     for cap in re.captures_iter(definition) {
         if let Some(table_name) = cap.get(1) {
-            let table_name = table_name.as_str();
-
-            // Handle schema-qualified names
-            if table_name.contains('.') {
-                if schema.tables.contains_key(table_name) {
-                    dependencies.push(table_name.to_string());
-                }
-            } else {
-                // Check if this table exists in our schema
-                if schema.tables.contains_key(table_name) {
-                    dependencies.push(table_name.to_string());
-                }
+            let key = QualifiedName::parse(table_name.as_str()).to_sql(false);
+            if schema.tables.contains_key(&key) {
+                dependencies.push(key);
             }
         }
     }
@@ -1473,16 +2242,9 @@ fn extract_view_dependencies(definition: &str, schema: &Schema) -> Vec<String> {
             .unwrap();
     for cap in join_re.captures_iter(definition) {
         if let Some(table_name) = cap.get(1) {
-            let table_name = table_name.as_str();
-
-            if table_name.contains('.') {
-                if schema.tables.contains_key(table_name) {
-                    dependencies.push(table_name.to_string());
-                }
-            } else {
-                if schema.tables.contains_key(table_name) {
-                    dependencies.push(table_name.to_string());
-                }
+            let key = QualifiedName::parse(table_name.as_str()).to_sql(false);
+            if schema.tables.contains_key(&key) {
+                dependencies.push(key);
             }
         }
     }
@@ -1490,6 +2252,28 @@ fn extract_view_dependencies(definition: &str, schema: &Schema) -> Vec<String> {
     dependencies
 }
 
+/// Quote an identifier only where PostgreSQL requires it, so reserved words and
+/// mixed-case names survive a replay
+fn quote_ident(identifier: &str) -> String {
+    PostgresSqlGenerator::quote_identifier(identifier)
+}
+
+fn quote_idents(identifiers: &[String]) -> String {
+    identifiers
+        .iter()
+        .map(|identifier| quote_ident(identifier))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `schema.name` with each part quoted where needed
+fn quoted_name(schema: Option<&str>, name: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quote_ident(schema), quote_ident(name)),
+        None => quote_ident(name),
+    }
+}
+
 // These are similar to the ones in migration.rs but without the down migrations
 fn generate_create_extension(ext: &Extension) -> Result<String> {
     let mut sql = format!("CREATE EXTENSION IF NOT EXISTS \"{}\"", ext.name);
@@ -1509,10 +2293,10 @@ fn generate_create_extension(ext: &Extension) -> Result<String> {
     Ok(sql)
 }
 
-fn generate_create_schema(schema: &NamedSchema) -> Result<String> {
-    let mut sql = format!("CREATE SCHEMA IF NOT EXISTS {}", schema.name);
+fn generate_create_schema(schema: &NamedSchema, owner: Option<&str>) -> Result<String> {
+    let mut sql = format!("CREATE SCHEMA IF NOT EXISTS {}", quote_ident(&schema.name));
 
-    if let Some(owner) = &schema.owner {
+    if let Some(owner) = owner {
         sql.push_str(&format!(" AUTHORIZATION {}", owner));
     }
 
@@ -1520,11 +2304,10 @@ fn generate_create_schema(schema: &NamedSchema) -> Result<String> {
 }
 
 fn generate_create_enum(type_def: &EnumType) -> Result<String> {
-    let mut sql = format!("CREATE TYPE {}", type_def.name);
-
-    if let Some(schema) = &type_def.schema {
-        sql = format!("CREATE TYPE {}.{}", schema, type_def.name);
-    }
+    let mut sql = format!(
+        "CREATE TYPE {}",
+        quoted_name(type_def.schema.as_deref(), &type_def.name)
+    );
 
     sql.push_str(" AS ENUM (");
 
@@ -1542,17 +2325,16 @@ fn generate_create_enum(type_def: &EnumType) -> Result<String> {
 }
 
 fn generate_create_type(type_def: &CompositeType) -> Result<String> {
-    let mut sql = format!("CREATE TYPE {}", type_def.name);
-
-    if let Some(schema) = &type_def.schema {
-        sql = format!("CREATE TYPE {}.{}", schema, type_def.name);
-    }
+    let mut sql = format!(
+        "CREATE TYPE {}",
+        quoted_name(type_def.schema.as_deref(), &type_def.name)
+    );
 
     sql.push_str(" AS (");
     let attrs = type_def
         .attributes
         .iter()
-        .map(|attr| format!("{} {}", attr.name, attr.type_name))
+        .map(|attr| format!("{} {}", quote_ident(&attr.name), attr.type_name))
         .collect::<Vec<_>>()
         .join(", ");
     sql.push_str(&attrs);
@@ -1562,14 +2344,20 @@ fn generate_create_type(type_def: &CompositeType) -> Result<String> {
 }
 
 fn generate_create_domain(domain: &Domain) -> Result<String> {
-    let mut sql = format!("CREATE DOMAIN {}", domain.name);
-
-    if let Some(schema) = &domain.schema {
-        sql = format!("CREATE DOMAIN {}.{}", schema, domain.name);
-    }
+    let mut sql = format!(
+        "CREATE DOMAIN {}",
+        quoted_name(domain.schema.as_deref(), &domain.name)
+    );
 
     sql.push_str(&format!(" AS {}", domain.base_type));
 
+    if let Some(default) = &domain.default {
+        sql.push_str(&format!(" DEFAULT {}", default));
+    }
+    if domain.not_null {
+        sql.push_str(" NOT NULL");
+    }
+
     for constraint in &domain.constraints {
         // Remove "CHECK" prefix if it exists in the constraint expression
         let check_expr = if constraint.check.starts_with("CHECK (") {
@@ -1579,6 +2367,9 @@ fn generate_create_domain(domain: &Domain) -> Result<String> {
         } else {
             &constraint.check
         };
+        if let Some(name) = &constraint.name {
+            sql.push_str(&format!(" CONSTRAINT {}", quote_ident(name)));
+        }
         sql.push_str(&format!(" CHECK ({})", check_expr));
     }
 
@@ -1586,10 +2377,13 @@ fn generate_create_domain(domain: &Domain) -> Result<String> {
 }
 
 fn generate_create_sequence(seq: &Sequence) -> Result<String> {
-    let mut sql = format!("CREATE SEQUENCE {}", seq.name);
+    let mut sql = format!(
+        "CREATE SEQUENCE {}",
+        quoted_name(seq.schema.as_deref(), &seq.name)
+    );
 
-    if let Some(schema) = &seq.schema {
-        sql = format!("CREATE SEQUENCE {}.{}", schema, seq.name);
+    if !seq.data_type.is_empty() {
+        sql.push_str(&format!(" AS {}", seq.data_type));
     }
 
     if seq.start != 1 {
@@ -1619,20 +2413,52 @@ fn generate_create_sequence(seq: &Sequence) -> Result<String> {
     Ok(sql)
 }
 
-fn generate_create_table(table: &Table) -> Result<String> {
-    let mut sql = format!("CREATE TABLE {}", table.name);
+fn generate_create_table(table: &Table, schema: &Schema) -> Result<String> {
+    let persistence = if table.unlogged { "UNLOGGED " } else { "" };
+    let mut sql = format!(
+        "CREATE {}TABLE {}",
+        persistence,
+        quoted_name(table.schema.as_deref(), &table.name)
+    );
+
+    let table_name = quoted_name(
+        table.schema.as_deref().filter(|schema| *schema != "public"),
+        &table.name,
+    );
 
-    if let Some(schema) = &table.schema {
-        sql = format!("CREATE TABLE {}.{}", schema, table.name);
+    // Partitions take their columns from the parent, only the bound is declared
+    if let (Some(bound), Some(parent)) = (&table.partition_bound, table.inherits.first()) {
+        sql.push_str(&format!(" PARTITION OF {} {}", parent, bound));
+        push_table_options(&mut sql, table);
+        push_row_security(&mut sql, table, &table_name);
+        return Ok(sql);
     }
 
-    sql.push_str(" (");
+    // Typed tables take their columns from the composite type
+    if let Some(of_type) = &table.of_type {
+        sql.push_str(&format!(" OF {}", of_type));
+    }
 
     let mut columns = Vec::new();
 
-    // Add columns
-    for column in &table.columns {
-        let mut col_def = format!("{} {}", column.name, column.type_name);
+    // Add columns, leaving inherited ones to the INHERITS clause
+    for column in table.columns.iter().filter(|c| !c.inherited) {
+        let mut col_def = match &table.of_type {
+            // Only the options a typed table adds to the type's column are declared
+            Some(_) => format!("{} WITH OPTIONS", quote_ident(&column.name)),
+            None => format!("{} {}", quote_ident(&column.name), column.type_name),
+        };
+        let declared = col_def.len();
+
+        if let Some(compression) =
+            column.compression.as_ref().filter(|_| table.of_type.is_none())
+        {
+            col_def.push_str(&format!(" COMPRESSION {}", compression));
+        }
+
+        if let Some(collation) = column.collation.as_deref().filter(|c| *c != "default") {
+            col_def.push_str(&format!(" COLLATE {}", quote_ident(collation)));
+        }
 
         if !column.nullable {
             col_def.push_str(" NOT NULL");
@@ -1668,32 +2494,236 @@ fn generate_create_table(table: &Table) -> Result<String> {
             }
         }
 
+        if table.of_type.is_some() && col_def.len() == declared {
+            continue;
+        }
         columns.push(col_def);
     }
 
     // Add constraints (excluding redundant NOT NULL constraints)
     for constraint in &table.constraints {
         // Skip redundant NOT NULL constraints that are already declared in column definitions
-        if !constraint.definition.contains("IS NOT NULL") {
-            columns.push(constraint.definition.clone());
+        if constraint.definition.contains("IS NOT NULL") {
+            continue;
+        }
+        // Foreign keys are added once every table exists, so they aren't repeated here
+        if matches!(constraint.kind, ConstraintKind::ForeignKey { .. })
+            && schema.foreign_key_constraints.values().any(|fk| {
+                fk.name == constraint.name && fk.table == table.name && fk.schema == table.schema
+            })
+        {
+            continue;
+        }
+        // Introspected definitions already end with the clause, built ones may not
+        let mut definition = constraint.definition.clone();
+        if !definition.contains("DEFERRABLE") {
+            definition.push_str(deferrable_clause(
+                constraint.deferrable,
+                constraint.initially_deferred,
+            ));
+        }
+        // Keep the name so it survives a replay instead of being regenerated
+        if !definition.starts_with("CONSTRAINT ") {
+            definition = format!("CONSTRAINT {} {}", quote_ident(&constraint.name), definition);
         }
+        columns.push(definition);
     }
 
-    sql.push_str(&columns.join(",\n    "));
-    sql.push_str("\n)");
+    // A table whose columns were all dropped is still valid with an empty list,
+    // a typed table without options leaves the list out entirely
+    if columns.is_empty() {
+        if table.of_type.is_none() {
+            sql.push_str(" ()");
+        }
+    } else {
+        sql.push_str(&format!(" ({}\n)", columns.join(",\n    ")));
+    }
+
+    if !table.inherits.is_empty() {
+        sql.push_str(&format!(" INHERITS ({})", table.inherits.join(", ")));
+    }
+
+    push_table_options(&mut sql, table);
+
+    // Inherited columns keep the parent's default unless the child overrides it
+    for column in table.columns.iter().filter(|c| c.inherited) {
+        if let Some(default) = &column.default {
+            sql.push_str(&format!(
+                ";\nALTER TABLE {} ALTER COLUMN {} SET DEFAULT {}",
+                table_name,
+                quote_ident(&column.name),
+                default
+            ));
+        }
+    }
+
+    // Storage can't be declared inline, so overrides follow the CREATE TABLE
+    for column in &table.columns {
+        if let Some(storage) = &column.storage {
+            let storage = match storage {
+                ColumnStorage::Plain => "PLAIN",
+                ColumnStorage::External => "EXTERNAL",
+                ColumnStorage::Extended => "EXTENDED",
+                ColumnStorage::Main => "MAIN",
+            };
+            sql.push_str(&format!(
+                ";\nALTER TABLE {} ALTER COLUMN {} SET STORAGE {}",
+                table_name,
+                quote_ident(&column.name),
+                storage
+            ));
+        }
+    }
+
+    push_row_security(&mut sql, table, &table_name);
 
     Ok(sql)
 }
 
-fn generate_create_view(view: &View) -> Result<String> {
-    let mut sql = format!("CREATE VIEW {}", view.name);
+/// Clauses following the column list or partition bound, in the order PostgreSQL
+/// expects them: PARTITION BY, WITH (...), TABLESPACE
+fn push_table_options(sql: &mut String, table: &Table) {
+    if let Some(partition_by) = &table.partition_by {
+        let method = match partition_by.method {
+            PartitionMethod::Range => "RANGE",
+            PartitionMethod::List => "LIST",
+            PartitionMethod::Hash => "HASH",
+        };
+        sql.push_str(&format!(" PARTITION BY {} ({})", method, partition_by.columns.join(", ")));
+    }
+
+    sql.push_str(&storage_clause(&table.storage_parameters));
+
+    if let Some(tablespace) = &table.tablespace {
+        sql.push_str(&format!(" TABLESPACE {}", quote_ident(tablespace)));
+    }
+}
+
+/// Row level security is switched on after creation; FORCE also applies policies to the owner
+fn push_row_security(sql: &mut String, table: &Table, table_name: &str) {
+    if table.row_security {
+        sql.push_str(&format!(";\nALTER TABLE {} ENABLE ROW LEVEL SECURITY", table_name));
+    }
+    if table.force_row_security {
+        sql.push_str(&format!(";\nALTER TABLE {} FORCE ROW LEVEL SECURITY", table_name));
+    }
+}
+
+/// Sequences are created before tables, so the ones owned by a column of `table`
+/// are attached once the table exists
+fn push_sequence_ownership(sql: &mut String, table: &Table, schema: &Schema) {
+    let table_key = QualifiedName::key(&table.schema, &table.name);
+    let mut sequences: Vec<&Sequence> = schema.sequences.values().collect();
+    sequences.sort_by(|a, b| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)));
+
+    for seq in sequences {
+        let Some((owner_table, column)) = seq.owned_by.as_deref().and_then(|o| o.rsplit_once('.'))
+        else {
+            continue;
+        };
+        if QualifiedName::parse(owner_table).to_sql(false) != table_key {
+            continue;
+        }
+        sql.push_str(&format!(
+            ";\nALTER SEQUENCE {} OWNED BY {}.{}",
+            quoted_name(seq.schema.as_deref(), &seq.name),
+            quoted_name(table.schema.as_deref(), &table.name),
+            quote_ident(column)
+        ));
+    }
+}
+
+/// Indexes follow the table or materialized view they belong to; the ones backing
+/// a primary key, unique or exclusion constraint come with the constraint itself
+fn push_indexes(
+    sql: &mut String,
+    schema: Option<&str>,
+    table: &str,
+    indexes: &[Index],
+    constraints: &[Constraint],
+) {
+    let mut indexes: Vec<&Index> = indexes
+        .iter()
+        .filter(|index| !constraints.iter().any(|c| c.name == index.name))
+        .collect();
+    indexes.sort_by(|a, b| a.name.cmp(&b.name));
+    for index in indexes {
+        sql.push_str(";\n");
+        sql.push_str(&generate_create_index(index, schema, table));
+    }
+}
+
+fn generate_create_index(index: &Index, schema: Option<&str>, table: &str) -> String {
+    let method = match index.method {
+        IndexMethod::Btree => "btree",
+        IndexMethod::Hash => "hash",
+        IndexMethod::Gist => "gist",
+        IndexMethod::Spgist => "spgist",
+        IndexMethod::Gin => "gin",
+        IndexMethod::Brin => "brin",
+    };
+    let mut sql = format!(
+        "CREATE {}INDEX {} ON {} USING {}",
+        if index.unique { "UNIQUE " } else { "" },
+        quote_ident(&index.name),
+        quoted_name(schema, table),
+        method
+    );
+
+    let columns: Vec<String> = index
+        .columns
+        .iter()
+        .map(|column| {
+            let mut definition = match &column.expression {
+                Some(expression) => format!("({})", expression),
+                None => quote_ident(&column.name),
+            };
+            // The operator class has to precede the ordering options
+            if let Some(opclass) = &column.opclass {
+                definition.push_str(&format!(" {}", opclass));
+            }
+            let descending = column.order == SortOrder::Descending;
+            if descending {
+                definition.push_str(" DESC");
+            }
+            // Nulls sort last ascending and first descending unless told otherwise
+            if column.nulls_first != descending {
+                definition.push_str(if column.nulls_first {
+                    " NULLS FIRST"
+                } else {
+                    " NULLS LAST"
+                });
+            }
+            definition
+        })
+        .collect();
+    sql.push_str(&format!(" ({})", columns.join(", ")));
 
-    if let Some(schema) = &view.schema {
-        sql = format!("CREATE VIEW {}.{}", schema, view.name);
+    if !index.include.is_empty() {
+        sql.push_str(&format!(" INCLUDE ({})", quote_idents(&index.include)));
     }
 
+    // PostgreSQL only accepts these clauses in this order
+    sql.push_str(&storage_clause(&index.storage_parameters));
+    if let Some(tablespace) = &index.tablespace {
+        sql.push_str(&format!(" TABLESPACE {}", quote_ident(tablespace)));
+    }
+    if let Some(where_clause) = &index.where_clause {
+        sql.push_str(&format!(" WHERE {}", where_clause));
+    }
+
+    sql
+}
+
+fn generate_create_view(view: &View) -> Result<String> {
+    let mut sql = format!(
+        "CREATE VIEW {}",
+        quoted_name(view.schema.as_deref(), &view.name)
+    );
+
+    // Introspected definitions end with their own semicolon
     sql.push_str(" AS ");
-    sql.push_str(&view.definition);
+    sql.push_str(view.definition.trim_end().trim_end_matches(';'));
 
     match view.check_option {
         CheckOption::Local => sql.push_str(" WITH LOCAL CHECK OPTION"),
@@ -1705,14 +2735,13 @@ fn generate_create_view(view: &View) -> Result<String> {
 }
 
 fn generate_create_materialized_view(view: &MaterializedView) -> Result<String> {
-    let mut sql = format!("CREATE MATERIALIZED VIEW {}", view.name);
-
-    if let Some(schema) = &view.schema {
-        sql = format!("CREATE MATERIALIZED VIEW {}.{}", schema, view.name);
-    }
+    let mut sql = format!(
+        "CREATE MATERIALIZED VIEW {}",
+        quoted_name(view.schema.as_deref(), &view.name)
+    );
 
     sql.push_str(" AS ");
-    sql.push_str(&view.definition);
+    sql.push_str(view.definition.trim_end().trim_end_matches(';'));
 
     Ok(sql)
 }
@@ -1789,6 +2818,10 @@ fn generate_create_function(func: &Function) -> Result<String> {
         ReturnKind::Scalar => {
             sql.push_str(&func.returns.type_name);
         }
+        // Introspected result types already read `TABLE(...)`
+        ReturnKind::Table if func.returns.type_name.to_uppercase().starts_with("TABLE") => {
+            sql.push_str(&func.returns.type_name);
+        }
         ReturnKind::Table => {
             sql.push_str(&format!("TABLE ({})", func.returns.type_name));
         }
@@ -1800,8 +2833,9 @@ fn generate_create_function(func: &Function) -> Result<String> {
         }
     }
 
-    // Add language
+    // Add language and the attributes that differ from the defaults
     sql.push_str(&format!(" LANGUAGE {}", func.language));
+    sql.push_str(&func.attributes());
 
     // Add function body
     sql.push_str(" AS $$");
@@ -1889,10 +2923,17 @@ fn generate_create_procedure(proc: &Procedure) -> Result<String> {
 }
 
 fn generate_create_trigger(trigger: &Trigger) -> Result<String> {
-    let events: Vec<&str> = trigger
+    let events: Vec<String> = trigger
         .events
         .iter()
-        .map(|e| trigger_event_to_str(e))
+        .map(|e| match e {
+            TriggerEvent::Update if !trigger.update_columns.is_empty() => {
+                let columns: Vec<String> =
+                    trigger.update_columns.iter().map(|c| quote_ident(c)).collect();
+                format!("UPDATE OF {}", columns.join(", "))
+            }
+            _ => trigger_event_to_str(e).to_string(),
+        })
         .collect();
 
     let timing = match trigger.timing {
@@ -1901,19 +2942,33 @@ fn generate_create_trigger(trigger: &Trigger) -> Result<String> {
         TriggerTiming::InsteadOf => "INSTEAD OF",
     };
 
+    let level = match trigger.for_each {
+        TriggerLevel::Row => "ROW",
+        TriggerLevel::Statement => "STATEMENT",
+    };
+
     let events_str = events.join(" OR ");
     let function = &trigger.function;
 
-    let args = if !trigger.arguments.is_empty() {
-        format!("({})", trigger.arguments.join(", "))
-    } else {
-        String::new()
-    };
+    let mut sql = format!(
+        "CREATE TRIGGER {} {} {} ON {} FOR EACH {}",
+        trigger.name,
+        timing,
+        events_str,
+        quoted_name(trigger.schema.as_deref(), &trigger.table),
+        level
+    );
 
-    Ok(format!(
-        "CREATE TRIGGER {} {} {} ON {} FOR EACH ROW EXECUTE FUNCTION {}(){}",
-        trigger.name, timing, events_str, trigger.table, function, args
-    ))
+    if let Some(condition) = trigger.when.as_ref().or(trigger.condition.as_ref()) {
+        sql.push_str(&format!(" WHEN ({})", condition));
+    }
+
+    sql.push_str(&format!(
+        " EXECUTE FUNCTION {}({})",
+        function,
+        trigger.arguments.join(", ")
+    ));
+    Ok(sql)
 }
 
 fn generate_create_policy(policy: &Policy) -> Result<String> {
@@ -1978,19 +3033,21 @@ fn generate_create_collation(collation: &Collation) -> Result<String> {
     }
     let mut options = Vec::new();
 
-    // Always include locale if available (either from locale or lc_collate field)
-    if let Some(locale) = &collation.locale {
-        options.push(format!("LOCALE = '{}'", locale));
-    } else if let Some(lc_collate) = &collation.lc_collate {
-        options.push(format!("LOCALE = '{}'", lc_collate));
-    } else {
-        // If no locale is available, we need to provide a default or skip this collation
-        // For now, let's use a default locale to avoid the error
-        options.push("LOCALE = 'C'".to_string());
-    }
-
-    if let Some(lc_ctype) = &collation.lc_ctype {
-        options.push(format!("CTYPE = '{}'", lc_ctype));
+    // LOCALE sets both categories, so it only stands in when they agree
+    match (&collation.lc_collate, &collation.lc_ctype) {
+        (Some(lc_collate), Some(lc_ctype)) if lc_collate != lc_ctype => {
+            options.push(format!("LC_COLLATE = '{}'", lc_collate));
+            options.push(format!("LC_CTYPE = '{}'", lc_ctype));
+        }
+        _ => {
+            // If no locale is available, fall back to C so the statement stays valid
+            let locale = collation
+                .locale
+                .as_deref()
+                .or(collation.lc_collate.as_deref())
+                .unwrap_or("C");
+            options.push(format!("LOCALE = '{}'", locale));
+        }
     }
     match collation.provider {
         CollationProvider::Libc => options.push("PROVIDER = 'libc'".to_string()),
@@ -2125,12 +3182,12 @@ fn generate_comments(schema: &Schema) -> Result<String> {
     let mut comments = String::new();
 
     // Table comments
-    for (_, table) in &schema.tables {
+    for table in sorted_by_key(&schema.tables) {
         if let Some(comment) = &table.comment {
             comments.push_str(&format!(
-                "COMMENT ON TABLE {} IS '{}';\n",
-                table.name,
-                comment.replace("'", "''")
+                "COMMENT ON TABLE {} IS {};\n\n",
+                quoted_name(table.schema.as_deref(), &table.name),
+                comment_literal(comment)
             ));
         }
 
@@ -2138,86 +3195,287 @@ fn generate_comments(schema: &Schema) -> Result<String> {
         for column in &table.columns {
             if let Some(comment) = &column.comment {
                 comments.push_str(&format!(
-                    "COMMENT ON COLUMN {}.{} IS '{}';\n",
-                    table.name,
-                    column.name,
-                    comment.replace("'", "''")
+                    "COMMENT ON COLUMN {}.{} IS {};\n\n",
+                    quoted_name(table.schema.as_deref(), &table.name),
+                    quote_ident(&column.name),
+                    comment_literal(comment)
                 ));
             }
         }
     }
 
     // View comments
-    for (_, view) in &schema.views {
+    for view in sorted_by_key(&schema.views) {
         if let Some(comment) = &view.comment {
             comments.push_str(&format!(
-                "COMMENT ON VIEW {} IS '{}';\n",
-                view.name,
-                comment.replace("'", "''")
+                "COMMENT ON VIEW {} IS {};\n\n",
+                quoted_name(view.schema.as_deref(), &view.name),
+                comment_literal(comment)
+            ));
+        }
+
+        // View column comments
+        let mut column_comments: Vec<_> = view.column_comments.iter().collect();
+        column_comments.sort();
+        for (column, comment) in column_comments {
+            comments.push_str(&format!(
+                "COMMENT ON COLUMN {}.{} IS {};\n\n",
+                quoted_name(view.schema.as_deref(), &view.name),
+                quote_ident(column),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Materialized view and column comments
+    for view in sorted_by_key(&schema.materialized_views) {
+        if let Some(comment) = &view.comment {
+            comments.push_str(&format!(
+                "COMMENT ON MATERIALIZED VIEW {} IS {};\n\n",
+                quoted_name(view.schema.as_deref(), &view.name),
+                comment_literal(comment)
+            ));
+        }
+
+        let mut column_comments: Vec<_> = view.column_comments.iter().collect();
+        column_comments.sort();
+        for (column, comment) in column_comments {
+            comments.push_str(&format!(
+                "COMMENT ON COLUMN {}.{} IS {};\n\n",
+                quoted_name(view.schema.as_deref(), &view.name),
+                quote_ident(column),
+                comment_literal(comment)
             ));
         }
     }
 
     // Function comments
-    for (_, function) in &schema.functions {
+    for function in sorted_by_key(&schema.functions) {
         if let Some(comment) = &function.comment {
             comments.push_str(&format!(
-                "COMMENT ON FUNCTION {} IS '{}';\n",
-                function.name,
-                comment.replace("'", "''")
+                "COMMENT ON FUNCTION {}({}) IS {};\n\n",
+                quoted_name(function.schema.as_deref(), &function.name),
+                function.argument_types(),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Procedure comments
+    for procedure in sorted_by_key(&schema.procedures) {
+        if let Some(comment) = &procedure.comment {
+            comments.push_str(&format!(
+                "COMMENT ON PROCEDURE {}({}) IS {};\n\n",
+                quoted_name(procedure.schema.as_deref(), &procedure.name),
+                procedure.argument_types(),
+                comment_literal(comment)
             ));
         }
     }
 
     // Type comments
-    for (_, enum_type) in &schema.enums {
+    for enum_type in sorted_by_key(&schema.enums) {
         if let Some(comment) = &enum_type.comment {
             comments.push_str(&format!(
-                "COMMENT ON TYPE {} IS '{}';\n",
-                enum_type.name,
-                comment.replace("'", "''")
+                "COMMENT ON TYPE {} IS {};\n\n",
+                quoted_name(enum_type.schema.as_deref(), &enum_type.name),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Composite type and attribute comments
+    for composite_type in sorted_by_key(&schema.composite_types) {
+        let type_name = quoted_name(composite_type.schema.as_deref(), &composite_type.name);
+        if let Some(comment) = &composite_type.comment {
+            comments.push_str(&format!(
+                "COMMENT ON TYPE {} IS {};\n\n",
+                type_name,
+                comment_literal(comment)
             ));
         }
+        for attr in &composite_type.attributes {
+            if let Some(comment) = &attr.comment {
+                comments.push_str(&format!(
+                    "COMMENT ON COLUMN {}.{} IS {};\n\n",
+                    type_name,
+                    quote_ident(&attr.name),
+                    comment_literal(comment)
+                ));
+            }
+        }
     }
 
     // Domain comments
-    for (_, domain) in &schema.domains {
+    for domain in sorted_by_key(&schema.domains) {
         if let Some(comment) = &domain.comment {
             comments.push_str(&format!(
-                "COMMENT ON DOMAIN {} IS '{}';\n",
-                domain.name,
-                comment.replace("'", "''")
+                "COMMENT ON DOMAIN {} IS {};\n\n",
+                quoted_name(domain.schema.as_deref(), &domain.name),
+                comment_literal(comment)
             ));
         }
     }
 
     // Sequence comments
-    for (_, sequence) in &schema.sequences {
+    for sequence in sorted_by_key(&schema.sequences) {
         if let Some(comment) = &sequence.comment {
             comments.push_str(&format!(
-                "COMMENT ON SEQUENCE {} IS '{}';\n",
-                sequence.name,
-                comment.replace("'", "''")
+                "COMMENT ON SEQUENCE {} IS {};\n\n",
+                quoted_name(sequence.schema.as_deref(), &sequence.name),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Policy comments
+    for policy in sorted_by_key(&schema.policies) {
+        if let Some(comment) = &policy.comment {
+            comments.push_str(&format!(
+                "COMMENT ON POLICY {} ON {} IS {};\n\n",
+                quote_ident(&policy.name),
+                quoted_name(policy.schema.as_deref(), &policy.table),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Trigger comments
+    for trigger in sorted_by_key(&schema.triggers) {
+        if let Some(comment) = &trigger.comment {
+            comments.push_str(&format!(
+                "COMMENT ON TRIGGER {} ON {} IS {};\n\n",
+                quote_ident(&trigger.name),
+                quoted_name(trigger.schema.as_deref(), &trigger.table),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Rule comments
+    for rule in sorted_by_key(&schema.rules) {
+        if let Some(comment) = &rule.comment {
+            comments.push_str(&format!(
+                "COMMENT ON RULE {} ON {} IS {};\n\n",
+                quote_ident(&rule.name),
+                quoted_name(rule.schema.as_deref(), &rule.table),
+                comment_literal(comment)
+            ));
+        }
+    }
+
+    // Collation comments
+    for collation in sorted_by_key(&schema.collations) {
+        if let Some(comment) = &collation.comment {
+            comments.push_str(&format!(
+                "COMMENT ON COLLATION {} IS {};\n\n",
+                quoted_name(collation.schema.as_deref(), &collation.name),
+                comment_literal(comment)
             ));
         }
     }
 
     // Extension comments
-    for (_, extension) in &schema.extensions {
+    for extension in sorted_by_key(&schema.extensions) {
         if let Some(comment) = &extension.comment {
             comments.push_str(&format!(
-                "COMMENT ON EXTENSION \"{}\" IS '{}';\n",
+                "COMMENT ON EXTENSION \"{}\" IS {};\n\n",
                 extension.name,
-                comment.replace("'", "''")
+                comment_literal(comment)
             ));
         }
     }
 
-    if !comments.is_empty() {
-        comments.push('\n');
+    Ok(comments)
+}
+
+/// Quote a comment as a SQL string literal. Multi-line comments are dollar-quoted
+/// so newlines survive verbatim; the tag is extended until the first occurrence
+/// is the closing one. Single-line comments use a standard literal, where backslashes are
+/// literal under `standard_conforming_strings`.
+fn comment_literal(comment: &str) -> String {
+    if !comment.contains('\n') && !comment.contains('\r') {
+        return format!("'{}'", comment.replace("'", "''"));
+    }
+
+    let mut tag = "$comment$".to_string();
+    let mut suffix = 0;
+    while format!("{}{}", comment, tag).find(&tag) != Some(comment.len()) {
+        suffix += 1;
+        tag = format!("$comment{}$", suffix);
     }
+    format!("{}{}{}", tag, comment, tag)
+}
 
-    Ok(comments)
+/// `SECURITY LABEL` statements; replaying them needs the same label providers loaded
+fn generate_security_labels(schema: &Schema) -> String {
+    sorted_by_key(&schema.security_labels)
+        .into_iter()
+        .map(|label| {
+            format!(
+                "SECURITY LABEL FOR {} ON {} {} IS {};\n\n",
+                quote_ident(&label.provider),
+                label.object_type.to_uppercase(),
+                label.object_name,
+                comment_literal(&label.label)
+            )
+        })
+        .collect()
+}
+
+fn generate_grants(schema: &Schema) -> Result<String> {
+    let mut grants = String::new();
+
+    // Table grants
+    for table in sorted_by_key(&schema.tables) {
+        let table_name = quoted_name(table.schema.as_deref(), &table.name);
+        for grant in &table.grants {
+            grants.push_str(&format!(
+                "GRANT {} ON TABLE {} TO {}",
+                grant.privileges.join(", "),
+                table_name,
+                grantee_sql(&grant.grantee)
+            ));
+            if grant.with_grant_option {
+                grants.push_str(" WITH GRANT OPTION");
+            }
+            grants.push_str(";\n\n");
+        }
+
+        // Column grants
+        for column in &table.columns {
+            for grant in &column.grants {
+                let privileges: Vec<String> = grant
+                    .privileges
+                    .iter()
+                    .map(|privilege| {
+                        format!("{} ({})", privilege, quote_ident(&column.name))
+                    })
+                    .collect();
+                grants.push_str(&format!(
+                    "GRANT {} ON TABLE {} TO {}",
+                    privileges.join(", "),
+                    table_name,
+                    grantee_sql(&grant.grantee)
+                ));
+                if grant.with_grant_option {
+                    grants.push_str(" WITH GRANT OPTION");
+                }
+                grants.push_str(";\n\n");
+            }
+        }
+    }
+
+    Ok(grants)
+}
+
+/// A grantee role, quoted where needed; `PUBLIC` is a keyword rather than a role
+fn grantee_sql(grantee: &str) -> String {
+    if grantee == "PUBLIC" {
+        grantee.to_string()
+    } else {
+        quote_ident(grantee)
+    }
 }
 
 fn trigger_event_to_str(event: &TriggerEvent) -> &'static str {
@@ -2230,17 +3488,16 @@ fn trigger_event_to_str(event: &TriggerEvent) -> &'static str {
 }
 
 fn generate_create_composite_type(composite_type: &CompositeType) -> Result<String> {
-    let mut sql = format!("CREATE TYPE {}", composite_type.name);
-
-    if let Some(schema) = &composite_type.schema {
-        sql = format!("CREATE TYPE {}.{}", schema, composite_type.name);
-    }
+    let mut sql = format!(
+        "CREATE TYPE {}",
+        quoted_name(composite_type.schema.as_deref(), &composite_type.name)
+    );
 
     sql.push_str(" AS (");
     let attrs = composite_type
         .attributes
         .iter()
-        .map(|attr| format!("{} {}", attr.name, attr.type_name))
+        .map(|attr| format!("{} {}", quote_ident(&attr.name), attr.type_name))
         .collect::<Vec<_>>()
         .join(", ");
     sql.push_str(&attrs);
@@ -2250,6 +3507,43 @@ fn generate_create_composite_type(composite_type: &CompositeType) -> Result<Stri
 }
 
 // SQL generation functions for the new objects
+fn generate_create_foreign_table(table: &ForeignTable) -> Result<String> {
+    let mut sql = format!(
+        "CREATE FOREIGN TABLE {} (",
+        quoted_name(table.schema.as_deref(), &table.name)
+    );
+
+    let columns: Vec<String> = table
+        .columns
+        .iter()
+        .map(|column| {
+            let mut col_def = format!("{} {}", quote_ident(&column.name), column.type_name);
+            if !column.nullable {
+                col_def.push_str(" NOT NULL");
+            }
+            if let Some(default) = &column.default {
+                col_def.push_str(&format!(" DEFAULT {}", default));
+            }
+            col_def
+        })
+        .collect();
+    sql.push_str(&columns.join(",\n    "));
+    sql.push_str(&format!("\n) SERVER {}", table.server));
+
+    if !table.options.is_empty() {
+        // Sort options so the output is stable across runs
+        let mut options: Vec<String> = table
+            .options
+            .iter()
+            .map(|(k, v)| format!("{} '{}'", k, v.replace('\'', "''")))
+            .collect();
+        options.sort();
+        sql.push_str(&format!(" OPTIONS ({})", options.join(", ")));
+    }
+
+    Ok(sql)
+}
+
 fn generate_create_server(server: &Server) -> Result<String> {
     let mut sql = format!("CREATE SERVER {}", server.name);
     
@@ -2260,10 +3554,12 @@ fn generate_create_server(server: &Server) -> Result<String> {
     sql.push_str(&format!(" FOREIGN DATA WRAPPER {}", server.foreign_data_wrapper));
     
     if !server.options.is_empty() {
-        let options: Vec<String> = server.options
+        // Sort options so the output is stable across runs
+        let mut options: Vec<String> = server.options
             .iter()
-            .map(|(k, v)| format!("{} '{}'", k, v))
+            .map(|(k, v)| format!("{} '{}'", k, v.replace('\'', "''")))
             .collect();
+        options.sort();
         sql.push_str(&format!(" OPTIONS ({})", options.join(", ")));
     }
     
@@ -2295,31 +3591,39 @@ fn generate_create_publication(publication: &Publication) -> Result<String> {
 fn generate_create_subscription(subscription: &Subscription) -> Result<String> {
     let mut sql = format!("CREATE SUBSCRIPTION {}", subscription.name);
     
-    sql.push_str(&format!(" CONNECTION '{}'", subscription.connection));
+    sql.push_str(&format!(" CONNECTION '{}'", subscription.connection.replace('\'', "''")));
     sql.push_str(&format!(" PUBLICATION {}", subscription.publication.join(", ")));
     
-    if let Some(slot_name) = &subscription.slot_name {
-        sql.push_str(&format!(" SLOT NAME {}", slot_name));
+    let mut options = Vec::new();
+    if !subscription.enabled {
+        // A disabled subscription must not reach out to the publisher when recreated
+        options.push("connect = false".to_string());
+    }
+    match &subscription.slot_name {
+        Some(slot_name) => options.push(format!("slot_name = '{}'", slot_name)),
+        None if !subscription.enabled => options.push("slot_name = NONE".to_string()),
+        None => {}
     }
     
-    if !subscription.enabled {
-        sql.push_str(" WITH (enabled = false)");
+    if !options.is_empty() {
+        sql.push_str(&format!(" WITH ({})", options.join(", ")));
     }
     
     Ok(sql)
 }
 
 fn generate_create_role(role: &Role) -> Result<String> {
-    let mut sql = format!("CREATE ROLE {}", role.name);
+    let mut sql = format!("CREATE ROLE {}", quote_ident(&role.name));
     
     let mut options = Vec::new();
     
     if role.superuser { options.push("SUPERUSER".to_string()); }
     if role.createdb { options.push("CREATEDB".to_string()); }
     if role.createrole { options.push("CREATEROLE".to_string()); }
-    if role.inherit { options.push("INHERIT".to_string()); }
+    if role.inherit { options.push("INHERIT".to_string()); } else { options.push("NOINHERIT".to_string()); }
     if role.login { options.push("LOGIN".to_string()); }
     if role.replication { options.push("REPLICATION".to_string()); }
+    if role.bypass_rls { options.push("BYPASSRLS".to_string()); }
     
     if let Some(limit) = role.connection_limit {
         options.push(format!("CONNECTION LIMIT {}", limit));
@@ -2347,10 +3651,12 @@ fn generate_create_tablespace(tablespace: &Tablespace) -> Result<String> {
     sql.push_str(&format!(" LOCATION '{}'", tablespace.location));
     
     if !tablespace.options.is_empty() {
-        let options: Vec<String> = tablespace.options
+        // Sort options so the output is stable across runs
+        let mut options: Vec<String> = tablespace.options
             .iter()
             .map(|(k, v)| format!("{} = {}", k, v))
             .collect();
+        options.sort();
         sql.push_str(&format!(" WITH ({})", options.join(", ")));
     }
     
@@ -2358,20 +3664,17 @@ fn generate_create_tablespace(tablespace: &Tablespace) -> Result<String> {
 }
 
 fn generate_create_foreign_key_constraint(fk: &ForeignKeyConstraint) -> Result<String> {
-    let mut sql = format!("ALTER TABLE {}", fk.table);
-    
-    if let Some(schema) = &fk.schema {
-        sql = format!("ALTER TABLE {}.{}", schema, fk.table);
-    }
+    let mut sql = format!("ALTER TABLE {}", quoted_name(fk.schema.as_deref(), &fk.table));
     
     sql.push_str(&format!(" ADD CONSTRAINT {} FOREIGN KEY ({})", 
-        fk.name, fk.columns.join(", ")));
+        quote_ident(&fk.name), quote_idents(&fk.columns)));
     
-    sql.push_str(&format!(" REFERENCES {}", fk.references_table));
-    if let Some(ref_schema) = &fk.references_schema {
-        sql = format!("{}.{}", ref_schema, fk.references_table);
-    }
-    sql.push_str(&format!(" ({})", fk.references_columns.join(", ")));
+    let references = quoted_name(
+        fk.references_schema.as_deref().filter(|schema| *schema != "public"),
+        &fk.references_table,
+    );
+    sql.push_str(&format!(" REFERENCES {}", references));
+    sql.push_str(&format!(" ({})", quote_idents(&fk.references_columns)));
     
     if let Some(on_delete) = &fk.on_delete {
         sql.push_str(&format!(" ON DELETE {}", referential_action_to_str(on_delete)));
@@ -2438,26 +3741,6 @@ fn generate_create_base_type(base_type: &BaseType) -> Result<String> {
     Ok(sql)
 }
 
-fn generate_create_array_type(array_type: &ArrayType) -> Result<String> {
-    let mut sql = format!("CREATE TYPE {}", array_type.name);
-    
-    if let Some(schema) = &array_type.schema {
-        sql = format!("CREATE TYPE {}.{}", schema, array_type.name);
-    }
-    
-    sql.push_str(&format!(" AS {}[]", array_type.element_type));
-    
-    if let Some(element_schema) = &array_type.element_schema {
-        sql = format!("CREATE TYPE {}.{} AS {}.{}[]", 
-            array_type.schema.as_deref().unwrap_or("public"), 
-            array_type.name, 
-            element_schema, 
-            array_type.element_type);
-    }
-    
-    Ok(sql)
-}
-
 fn generate_create_multirange_type(multirange_type: &MultirangeType) -> Result<String> {
     let mut sql = format!("CREATE TYPE {}", multirange_type.name);
     
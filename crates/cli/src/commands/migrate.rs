@@ -1,22 +1,30 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
+use std::io::{BufRead, IsTerminal, Write};
 use std::path::PathBuf;
 use tracing::info;
-use crate::config::Config;
+use crate::commands::diff::load_schema_from_sql;
+use crate::config::{Config, LogFormat};
 use shem_core::{
     DatabaseDriver,
     DatabaseConnection,
-    migration::Migration,
+    QualifiedName,
+    Schema,
+    migration::parse_migration,
     traits::Transaction,
 };
 use postgres::PostgresDriver;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use serde_json;
 
 pub async fn execute(
-    migrations: PathBuf,
+    migrations: Vec<PathBuf>,
     database_url: Option<String>,
     dry_run: bool,
+    log_format: LogFormat,
     config: &Config,
 ) -> Result<()> {
     let url = database_url.or_else(|| config.database_url.clone())
@@ -25,25 +33,25 @@ pub async fn execute(
     info!("Connecting to database...");
     
     // Connect to database
-    let driver = get_driver()?;
+    let driver = get_driver(config)?;
     let conn = driver.connect(&url).await?;
+    let table = config.migrations_table_name();
     
     // Create migrations table if it doesn't exist
     if !dry_run {
         info!("Creating migrations table if it doesn't exist...");
-        create_migrations_table(&conn).await?;
+        create_migrations_table(&conn, &table).await?;
     }
     
     // Get applied migrations (only after ensuring table exists)
     let applied = if !dry_run {
         info!("Getting applied migrations...");
-        get_applied_migrations(&conn).await?
+        get_applied_migrations(&conn, &table).await?
     } else {
         vec![]
     };
     
     // Find migration files
-    info!("Finding migration files in: {}", migrations.display());
     let migration_files = find_migration_files(&migrations)?;
     
     if migration_files.is_empty() {
@@ -53,14 +61,29 @@ pub async fn execute(
     
     info!("Found {} migration files", migration_files.len());
     
+    // Applied migrations edited since would leave the database out of step with them
+    if !dry_run {
+        let changed = changed_migrations(&get_tracked_checksums(&conn, &table).await?, &migration_files)?;
+        if !changed.is_empty() {
+            anyhow::bail!(
+                "Migrations changed after they were applied: {}. Restore them, or run `shem repair` to accept the edits",
+                changed.join(", ")
+            );
+        }
+    }
+    
+    let progress = migration_progress(migration_files.len(), log_format)?;
+    
     // Apply pending migrations
     for file in migration_files {
         let name = file.file_stem()
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("Invalid migration filename"))?;
+        progress.set_message(name.to_string());
             
         if applied.contains(&name.to_string()) {
             info!("Migration {} already applied, skipping", name);
+            progress.inc(1);
             continue;
         }
         
@@ -68,73 +91,113 @@ pub async fn execute(
         
         // Read and parse migration
         let content = fs::read_to_string(&file)?;
-        let migration = parse_migration(&content)?;
+        let migration = parse_migration(&content);
         
         if dry_run {
             info!("Would apply migration {}:", name);
             for stmt in &migration.statements {
                 info!("  {}", stmt);
             }
+            progress.inc(1);
             continue;
         }
         
         // Begin transaction
-        let tx = conn.begin().await?;
+        let mut tx = conn.begin().await?;
         
         // Apply migration
         for stmt in &migration.statements {
             info!("Executing: {}", stmt);
             tx.execute(stmt).await?;
+            // Later statements can only use an added enum value once it is committed
+            if adds_enum_value(stmt) {
+                tx.commit().await?;
+                tx = conn.begin().await?;
+            }
         }
         
         // Record migration
-        record_migration(&tx, name, &migration).await?;
+        record_migration(&tx, &table, name, &migration_checksum(&content)).await?;
         
         // Commit transaction
         tx.commit().await?;
         
         info!("Migration {} applied successfully", name);
+        progress.inc(1);
     }
     
+    progress.finish_and_clear();
     Ok(())
 }
 
-async fn create_migrations_table(conn: &Box<dyn DatabaseConnection>) -> Result<()> {
-    let sql = r#"
-        CREATE TABLE IF NOT EXISTS schema_migrations (
+/// Progress bar over the migration set, hidden when stderr is not a terminal or logs are JSON
+fn migration_progress(total: usize, log_format: LogFormat) -> Result<ProgressBar> {
+    if log_format == LogFormat::Json || !std::io::stderr().is_terminal() {
+        return Ok(ProgressBar::hidden());
+    }
+    
+    let style = ProgressStyle::with_template(
+        "[{elapsed_precise}] {bar:40} {pos}/{len} (eta {eta}) {msg}",
+    )?;
+    let progress = ProgressBar::new(total as u64);
+    progress.set_style(style);
+    Ok(progress)
+}
+
+async fn create_migrations_table(conn: &dyn DatabaseConnection, table: &QualifiedName) -> Result<()> {
+    let sql = format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {} (
             id SERIAL PRIMARY KEY,
             name TEXT NOT NULL UNIQUE,
-            applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+            applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            checksum TEXT
         )
-    "#;
-    conn.execute(sql).await?;
+    "#,
+        table.to_sql(true)
+    );
+    conn.execute(&sql).await?;
+    // Tables created before checksums were tracked
+    conn.execute(&format!(
+        "ALTER TABLE {} ADD COLUMN IF NOT EXISTS checksum TEXT",
+        table.to_sql(true)
+    ))
+    .await?;
     Ok(())
 }
 
-async fn get_applied_migrations(conn: &Box<dyn DatabaseConnection>) -> Result<Vec<String>> {
-    // Check if table exists first
-    let check_sql = r#"
+/// Whether the migrations table has been created yet
+async fn migrations_table_exists(conn: &dyn DatabaseConnection, table: &QualifiedName) -> Result<bool> {
+    let check_sql = format!(
+        r#"
         SELECT EXISTS (
             SELECT FROM information_schema.tables 
-            WHERE table_schema = 'public' 
-            AND table_name = 'schema_migrations'
+            WHERE table_schema = '{}' 
+            AND table_name = '{}'
         )
-    "#;
+    "#,
+        table.schema.as_deref().unwrap_or("public").replace('\'', "''"),
+        table.name.replace('\'', "''")
+    );
     
-    let table_exists = conn.query(check_sql).await?;
-    let exists = match table_exists.first() {
+    let table_exists = conn.query(&check_sql).await?;
+    Ok(match table_exists.first() {
         Some(serde_json::Value::Object(obj)) => {
             obj.get("exists").and_then(|v| v.as_bool()).unwrap_or(false)
         }
         _ => false,
-    };
-    
-    if !exists {
-        info!("schema_migrations table does not exist yet");
+    })
+}
+
+async fn get_applied_migrations(conn: &dyn DatabaseConnection, table: &QualifiedName) -> Result<Vec<String>> {
+    if !migrations_table_exists(conn, table).await? {
+        info!("{} table does not exist yet", table);
         return Ok(vec![]);
     }
     
-    let rows = conn.query("SELECT name FROM schema_migrations ORDER BY id").await?;
+    let rows = conn
+        .query(&format!("SELECT name FROM {} ORDER BY id", table.to_sql(true)))
+        .await?;
     let mut migrations = Vec::with_capacity(rows.len());
     for row in rows {
         match row {
@@ -149,76 +212,421 @@ async fn get_applied_migrations(conn: &Box<dyn DatabaseConnection>) -> Result<Ve
     Ok(migrations)
 }
 
-fn find_migration_files(migrations_dir: &Path) -> Result<Vec<PathBuf>> {
-    if !migrations_dir.exists() {
-        anyhow::bail!("Migrations directory does not exist: {}", migrations_dir.display());
+/// Collect the migration files of every directory, ordered by file name so the
+/// timestamp prefixes interleave across directories
+fn find_migration_files(migrations_dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for migrations_dir in migrations_dirs {
+        if !migrations_dir.exists() {
+            anyhow::bail!("Migrations directory does not exist: {}", migrations_dir.display());
+        }
+        
+        info!("Finding migration files in: {}", migrations_dir.display());
+        files.extend(
+            fs::read_dir(migrations_dir)?
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "sql"))
+                .map(|e| e.path()),
+        );
     }
     
-    let mut files: Vec<_> = fs::read_dir(migrations_dir)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "sql"))
-        .map(|e| e.path())
-        .collect();
-        
-    files.sort();
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()).then_with(|| a.cmp(b)));
+    check_version_collisions(&files)?;
     Ok(files)
 }
 
-fn parse_migration(content: &str) -> Result<Migration> {
-    // Split content into up and down migrations
-    let parts: Vec<_> = content.split("-- Down Migration").collect();
-    let up = parts[0].trim();
-    let down = parts.get(1).map(|s| s.trim()).unwrap_or("");
-    
-    // Parse up migration statements
-    let up_statements: Vec<_> = up.lines()
-        .map(|line| line.trim())
-        .filter(|line| {
-            !line.is_empty() && 
-            !line.starts_with("--") && 
-            !line.starts_with("/*") &&
-            !line.starts_with("*/")
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-        .split(';')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(String::from)
-        .collect();
+/// Refuse two directories providing the same version, their order would be arbitrary
+fn check_version_collisions(files: &[PathBuf]) -> Result<()> {
+    let mut versions: HashMap<&str, &Path> = HashMap::new();
+    for file in files {
+        let Some(stem) = file.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let version = migration_version(stem);
+        let dir = file.parent().unwrap_or(Path::new(""));
+        if let Some(other) = versions.insert(version, dir)
+            && other != dir
+        {
+            anyhow::bail!(
+                "Migration version {} exists in both {} and {}",
+                version,
+                other.display(),
+                dir.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Version prefix of a migration file stem, the part before the first `_`
+fn migration_version(stem: &str) -> &str {
+    stem.split('_').next().unwrap_or(stem)
+}
+
+async fn record_migration(
+    tx: &dyn Transaction,
+    table: &QualifiedName,
+    name: &str,
+    checksum: &str,
+) -> Result<()> {
+    let sql = format!(
+        "INSERT INTO {} (name, checksum) VALUES ('{}', '{}')",
+        table.to_sql(true),
+        name.replace('\'', "''"),
+        checksum
+    );
+    tx.execute(&sql).await?;
+    Ok(())
+}
+
+/// Whether `statement` is an `ALTER TYPE ... ADD VALUE`
+fn adds_enum_value(statement: &str) -> bool {
+    let statement = statement.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase();
+    statement.starts_with("ALTER TYPE ") && statement.contains(" ADD VALUE ")
+}
+
+/// SHA-256 of a migration file's content, hex encoded
+fn migration_checksum(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Tracked migrations whose file no longer matches the recorded checksum; rows
+/// recorded before checksums were tracked have none to compare
+fn changed_migrations(
+    tracked: &[(String, Option<String>)],
+    files: &[PathBuf],
+) -> Result<Vec<String>> {
+    let mut changed = Vec::new();
+    for file in files {
+        let Some(name) = file.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let recorded = tracked
+            .iter()
+            .find(|(tracked, _)| tracked == name)
+            .and_then(|(_, checksum)| checksum.as_deref());
+        if let Some(recorded) = recorded
+            && recorded != migration_checksum(&fs::read_to_string(file)?)
+        {
+            changed.push(name.to_string());
+        }
+    }
+    Ok(changed)
+}
+
+/// Roll back the last `steps` applied migrations, newest first, using the down
+/// section of each migration file
+pub async fn rollback(
+    migrations: Vec<PathBuf>,
+    database_url: Option<String>,
+    steps: usize,
+    dry_run: bool,
+    config: &Config,
+) -> Result<()> {
+    let url = database_url.or_else(|| config.database_url.clone())
+        .ok_or_else(|| anyhow::anyhow!("No database URL provided"))?;
+    
+    info!("Connecting to database...");
+    let driver = get_driver(config)?;
+    let conn = driver.connect(&url).await?;
+    let table = config.migrations_table_name();
+    
+    let applied = get_applied_migrations(&conn, &table).await?;
+    if applied.is_empty() {
+        info!("No applied migrations to roll back");
+        return Ok(());
+    }
+    
+    for name in applied.iter().rev().take(steps) {
+        let file = migrations
+            .iter()
+            .map(|dir| dir.join(format!("{}.sql", name)))
+            .find(|file| file.exists())
+            .unwrap_or_else(|| migrations[0].join(format!("{}.sql", name)));
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Migration file not found: {}", file.display()))?;
+        let migration = parse_migration(&content);
         
-    // Parse down migration statements
-    let down_statements: Vec<_> = down.lines()
-        .map(|line| line.trim())
-        .filter(|line| {
-            !line.is_empty() && 
-            !line.starts_with("--") && 
-            !line.starts_with("/*") &&
-            !line.starts_with("*/")
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-        .split(';')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .map(String::from)
+        if migration.rollback_statements.is_empty() {
+            anyhow::bail!("Migration {} has no down section to roll back", name);
+        }
+        
+        if dry_run {
+            info!("Would roll back migration {}:", name);
+            for stmt in &migration.rollback_statements {
+                info!("  {}", stmt);
+            }
+            continue;
+        }
+        
+        info!("Rolling back migration {}", name);
+        let tx = conn.begin().await?;
+        for stmt in &migration.rollback_statements {
+            info!("Executing: {}", stmt);
+            tx.execute(stmt).await?;
+        }
+        forget_migration(&tx, &table, name).await?;
+        tx.commit().await?;
+        
+        info!("Migration {} rolled back successfully", name);
+    }
+    
+    Ok(())
+}
+
+/// List migration files as applied, pending or changed since they were applied,
+/// skipping those older than `since`
+pub async fn status(
+    migrations: Vec<PathBuf>,
+    database_url: Option<String>,
+    since: Option<u64>,
+    config: &Config,
+) -> Result<()> {
+    let url = database_url.or_else(|| config.database_url.clone())
+        .ok_or_else(|| anyhow::anyhow!("No database URL provided"))?;
+    
+    info!("Connecting to database...");
+    let driver = get_driver(config)?;
+    let conn = driver.connect(&url).await?;
+    let table = config.migrations_table_name();
+    
+    let tracked = get_tracked_checksums(&conn, &table).await?;
+    let files = find_migration_files(&migrations)?;
+    let changed = changed_migrations(&tracked, &files)?;
+    for file in &files {
+        let name = file.file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid migration filename"))?;
+        if let Some(since) = since {
+            // Compared as numbers, `9_x` sorts before `10_x` whatever the padding
+            let version = migration_version(name);
+            let version: u64 = version.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Migration {} has no numeric version to compare with --since",
+                    name
+                )
+            })?;
+            if version < since {
+                continue;
+            }
+        }
+        
+        let state = if changed.iter().any(|c| c == name) {
+            "changed"
+        } else if tracked.iter().any(|(tracked, _)| tracked == name) {
+            "applied"
+        } else {
+            "pending"
+        };
+        println!("{:<8} {}", state, name);
+    }
+    
+    Ok(())
+}
+
+/// Reconcile the migrations table with the migration files: drop rows whose file
+/// is gone (after confirmation), refresh the checksums of edited files and record
+/// the `mark_applied` migrations whose changes were made by hand, once the
+/// database is found to hold every object they create
+pub async fn repair(
+    migrations: Vec<PathBuf>,
+    database_url: Option<String>,
+    mark_applied: Vec<String>,
+    yes: bool,
+    config: &Config,
+) -> Result<()> {
+    let url = database_url.or_else(|| config.database_url.clone())
+        .ok_or_else(|| anyhow::anyhow!("No database URL provided"))?;
+    
+    info!("Connecting to database...");
+    let driver = get_driver(config)?;
+    let conn = driver.connect(&url).await?;
+    let table = config.migrations_table_name();
+    create_migrations_table(&conn, &table).await?;
+    
+    let mut files = HashMap::new();
+    for file in find_migration_files(&migrations)? {
+        let name = file.file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| anyhow::anyhow!("Invalid migration filename"))?;
+        files.insert(name.to_string(), file.clone());
+    }
+    let tracked = get_tracked_checksums(&conn, &table).await?;
+    
+    let stale: Vec<&String> = tracked
+        .iter()
+        .map(|(name, _)| name)
+        .filter(|name| !files.contains_key(*name))
         .collect();
+    if !stale.is_empty() {
+        println!("Tracked migrations missing on disk:");
+        for name in &stale {
+            println!("  {}", name);
+        }
+        let confirmed = yes
+            || !config.declarative.safety_checks.require_confirmation
+            || confirm("Remove their tracking rows?")?;
+        if confirmed {
+            let tx = conn.begin().await?;
+            for name in &stale {
+                forget_migration(&tx, &table, name).await?;
+            }
+            tx.commit().await?;
+            for name in &stale {
+                println!("{:<8} {}", "removed", name);
+            }
+        } else {
+            info!("Keeping the tracking rows of missing migrations");
+        }
+    }
     
-    Ok(Migration {
-        version: chrono::Utc::now().format("%Y%m%d%H%M%S").to_string(),
-        description: "Migration".to_string(),
-        statements: up_statements,
-        rollback_statements: down_statements,
-        created_at: chrono::Utc::now(),
-    })
+    for (name, checksum) in &tracked {
+        let Some(file) = files.get(name) else {
+            continue;
+        };
+        let actual = migration_checksum(&fs::read_to_string(file)?);
+        if checksum.as_deref() != Some(actual.as_str()) {
+            conn.execute(&format!(
+                "UPDATE {} SET checksum = '{}' WHERE name = '{}'",
+                table.to_sql(true),
+                actual,
+                name.replace('\'', "''")
+            ))
+            .await?;
+            println!("{:<8} {}", "updated", name);
+        }
+    }
+    
+    let mut current: Option<Schema> = None;
+    for name in &mark_applied {
+        let file = files
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Migration file not found for {}", name))?;
+        if tracked.iter().any(|(tracked, _)| tracked == name) {
+            info!("Migration {} is already recorded as applied", name);
+            continue;
+        }
+        let content = fs::read_to_string(file)?;
+        let created = load_schema_from_sql(&parse_migration(&content).statements.join("\n"))
+            .with_context(|| format!("Failed to read the objects {} creates", name))?;
+        let current = match &mut current {
+            Some(current) => current,
+            None => current.insert(conn.introspect_with_options(&config.introspect_options()).await?),
+        };
+        let missing = missing_objects(&created, current);
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Migration {} has not been applied by hand, the database lacks {}",
+                name,
+                missing.join(", ")
+            );
+        }
+        let tx = conn.begin().await?;
+        record_migration(&tx, &table, name, &migration_checksum(&content)).await?;
+        tx.commit().await?;
+        println!("{:<8} {}", "marked", name);
+    }
+    
+    Ok(())
+}
+
+/// Objects in `created` that `current` lacks, such as `table orders` or
+/// `column orders.total`
+fn missing_objects(created: &Schema, current: &Schema) -> Vec<String> {
+    let mut missing = Vec::new();
+    let mut tables: Vec<_> = created.tables.iter().collect();
+    tables.sort_by_key(|(key, _)| *key);
+    for (key, table) in tables {
+        match current.tables.get(key) {
+            Some(existing) => missing.extend(
+                table
+                    .columns
+                    .iter()
+                    .filter(|c| !existing.columns.iter().any(|e| e.name == c.name))
+                    .map(|c| format!("column {}.{}", key, c.name)),
+            ),
+            None => missing.push(format!("table {}", key)),
+        }
+    }
+    let mut check = |kind: &str, created: Vec<&String>, current: Vec<&String>| {
+        let mut absent: Vec<_> = created.into_iter().filter(|key| !current.contains(key)).collect();
+        absent.sort();
+        missing.extend(absent.into_iter().map(|key| format!("{} {}", kind, key)));
+    };
+    check("view", created.views.keys().collect(), current.views.keys().collect());
+    check(
+        "materialized view",
+        created.materialized_views.keys().collect(),
+        current.materialized_views.keys().collect(),
+    );
+    check("type", created.enums.keys().collect(), current.enums.keys().collect());
+    check("domain", created.domains.keys().collect(), current.domains.keys().collect());
+    check("sequence", created.sequences.keys().collect(), current.sequences.keys().collect());
+    check("trigger", created.triggers.keys().collect(), current.triggers.keys().collect());
+    check("policy", created.policies.keys().collect(), current.policies.keys().collect());
+    // Declared argument types are spelled differently from introspected ones, so
+    // routines are matched by name
+    let routines = |schema: &Schema| -> Vec<String> {
+        let functions = schema.functions.values().map(|f| QualifiedName::key(&f.schema, &f.name));
+        let procedures = schema.procedures.values().map(|p| QualifiedName::key(&p.schema, &p.name));
+        functions.chain(procedures).collect()
+    };
+    let (created_routines, current_routines) = (routines(created), routines(current));
+    check("routine", created_routines.iter().collect(), current_routines.iter().collect());
+    missing
+}
+
+/// Name and recorded checksum of every tracked migration, oldest first; none
+/// before the table is created
+async fn get_tracked_checksums(
+    conn: &dyn DatabaseConnection,
+    table: &QualifiedName,
+) -> Result<Vec<(String, Option<String>)>> {
+    if !migrations_table_exists(conn, table).await? {
+        return Ok(Vec::new());
+    }
+    let rows = conn
+        .query(&format!("SELECT name, checksum FROM {} ORDER BY id", table.to_sql(true)))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let name = row.get("name")?.as_str()?.to_string();
+            let checksum = row.get("checksum").and_then(|v| v.as_str()).map(String::from);
+            Some((name, checksum))
+        })
+        .collect())
+}
+
+/// Ask a yes/no question on the terminal; without one there is nobody to answer,
+/// so the caller must pass `--yes`
+fn confirm(question: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("{} Re-run with --yes to confirm without a terminal", question);
+    }
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
 }
 
-async fn record_migration(tx: &Box<dyn Transaction>, name: &str, _migration: &Migration) -> Result<()> {
-    let sql = format!("INSERT INTO schema_migrations (name) VALUES ('{}')", name.replace('\'', "''"));
+async fn forget_migration(tx: &dyn Transaction, table: &QualifiedName, name: &str) -> Result<()> {
+    let sql = format!(
+        "DELETE FROM {} WHERE name = '{}'",
+        table.to_sql(true),
+        name.replace('\'', "''")
+    );
     tx.execute(&sql).await?;
     Ok(())
 }
 
-fn get_driver() -> Result<Box<dyn DatabaseDriver>> {
-    Ok(Box::new(PostgresDriver::new()))
+fn get_driver(config: &Config) -> Result<Box<dyn DatabaseDriver>> {
+    Ok(Box::new(
+        PostgresDriver::new()
+            .with_statement_timeout(config.database.statement_timeout_ms)
+            .with_ssl_root_cert(config.database.ssl_root_cert.clone())
+            .with_application_name(config.database.application_name.clone())
+            .with_options(config.database.options.clone()),
+    ))
 } 
\ No newline at end of file
@@ -1,5 +1,6 @@
 // Export modules
 pub mod diff;
+pub mod fingerprint;
 pub mod init;
 pub mod inspect;
 pub mod introspect;
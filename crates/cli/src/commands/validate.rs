@@ -2,59 +2,92 @@ use crate::config::Config;
 use anyhow::Result;
 use parser::ast::Statement;
 use parser::parse_file;
-use std::path::Path;
-use tracing::{error, info};
+use std::path::{Path, PathBuf};
+use tracing::info;
 
-pub async fn execute(path: &str, _config: &Config) -> Result<()> {
+/// Validate one schema file or every `.sql` file under a directory. Errors from
+/// all files are reported together, grouped by file, unless `fail_fast` stops
+/// at the first file with errors.
+pub async fn execute(path: &str, fail_fast: bool, _config: &Config) -> Result<()> {
     let path = Path::new(path);
 
     if !path.exists() {
         anyhow::bail!("Schema path does not exist: {}", path.display());
     }
 
-    let mut has_errors = false;
-
-    if path.is_file() {
-        validate_file(path, &mut has_errors)?;
-    } else if path.is_dir() {
-        // Validate all .sql files in directory
-        for entry in walkdir::WalkDir::new(path)
+    let files: Vec<PathBuf> = if path.is_file() {
+        vec![path.to_path_buf()]
+    } else {
+        // Validate all .sql files in directory, in a stable order
+        walkdir::WalkDir::new(path)
+            .sort_by_file_name()
             .into_iter()
             .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "sql"))
-        {
-            validate_file(entry.path(), &mut has_errors)?;
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "sql"))
+            .map(|e| e.into_path())
+            .collect()
+    };
+
+    let mut failed_files = Vec::new();
+    for file in &files {
+        let errors = validate_file(file);
+        if errors.is_empty() {
+            continue;
+        }
+        failed_files.push((file, errors));
+        if fail_fast {
+            break;
         }
     }
 
-    if has_errors {
-        anyhow::bail!("Schema validation failed");
+    if !failed_files.is_empty() {
+        for (file, errors) in &failed_files {
+            println!("{}:", file.display());
+            for error in errors {
+                for line in error.lines() {
+                    println!("  {}", line);
+                }
+            }
+        }
+        let error_count: usize = failed_files.iter().map(|(_, errors)| errors.len()).sum();
+        anyhow::bail!(
+            "Schema validation failed: {} error(s) in {} file(s)",
+            error_count,
+            failed_files.len()
+        );
     }
 
     info!("Schema validation successful");
     Ok(())
 }
 
-fn validate_file(path: &Path, has_errors: &mut bool) -> Result<()> {
+/// Every problem found in one file, empty when it is valid
+fn validate_file(path: &Path) -> Vec<String> {
     info!("Validating {}", path.display());
 
     match parse_file(path) {
-        Ok(statements) => {
-            // Validate each statement
-            for (i, stmt) in statements.iter().enumerate() {
-                if let Err(e) = validate_statement(stmt) {
-                    error!("Error in {} at statement {}: {}", path.display(), i + 1, e);
-                    *has_errors = true;
-                }
-            }
-        }
-        Err(e) => {
-            error!("Failed to parse {}: {}", path.display(), e);
-            *has_errors = true;
-        }
+        Ok(statements) => statements
+            .iter()
+            .enumerate()
+            .filter_map(|(i, stmt)| {
+                validate_statement(stmt)
+                    .err()
+                    .map(|e| format!("statement {}: {}", i + 1, e))
+            })
+            .collect(),
+        Err(e) => match parse_error_excerpt(&e) {
+            Some(excerpt) => vec![format!("failed to parse: {}\n{}", e, excerpt)],
+            None => vec![format!("failed to parse: {}", e)],
+        },
     }
+}
 
-    Ok(())
+/// The offending line with a caret under the column, when `error` is a parse
+/// error that knows its position
+pub(crate) fn parse_error_excerpt(error: &anyhow::Error) -> Option<String> {
+    error
+        .downcast_ref::<shem_core::Error>()
+        .and_then(shem_core::Error::excerpt)
 }
 
 fn validate_statement(stmt: &Statement) -> Result<()> {
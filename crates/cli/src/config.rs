@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use glob::glob;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use shem_core::{GenerateOptions, IntrospectOptions, QualifiedName};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,8 +10,61 @@ pub struct Config {
     pub database_url: Option<String>,
     pub schema_dir: PathBuf,
     pub migrations_dir: PathBuf,
+    /// Table recording applied migrations, `schema.table` outside public
+    #[serde(default = "default_migrations_table")]
+    pub migrations_table: String,
     pub postgres: PostgresConfig,
     pub declarative: DeclarativeConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub diff: DiffConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// Format `introspect` writes the schema in
+    #[serde(default)]
+    pub format: OutputFormat,
+    /// Write one SQL file per object category instead of a single `schema.sql`
+    #[serde(default)]
+    pub split: bool,
+    /// Object categories (`schemas`, `tables`, ...) to create in this order.
+    /// They take the places listed categories hold by default, unlisted
+    /// categories keep theirs.
+    #[serde(default)]
+    pub object_order: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiffConfig {
+    /// USING expressions for column type changes that a plain cast can't
+    /// convert, keyed by `table.column` (`schema.table.column` outside public)
+    #[serde(default)]
+    pub column_using: HashMap<String, String>,
+}
+
+/// Serialization format for introspected schemas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Sql,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    /// `statement_timeout` applied to every introspection and migration session
+    pub statement_timeout_ms: Option<u64>,
+    /// PEM root certificates trusted for `sslmode=verify-full` connections
+    pub ssl_root_cert: Option<PathBuf>,
+    /// `application_name` sessions report, `shem` unless the URL sets one
+    pub application_name: Option<String>,
+    /// libpq `options` passed to every session, such as `-c search_path=app`
+    pub options: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +73,9 @@ pub struct PostgresConfig {
     pub extensions: Vec<String>,
     pub exclude_tables: Vec<String>,
     pub exclude_schemas: Vec<String>,
+    /// Introspect objects owned by system roles or installed by extensions
+    #[serde(default)]
+    pub include_system_objects: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,17 +94,26 @@ pub struct SafetyConfig {
     pub backup_before_apply: bool,
 }
 
+/// Output format for log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             database_url: None,
             schema_dir: PathBuf::from("schema"),
             migrations_dir: PathBuf::from("migrations"),
+            migrations_table: default_migrations_table(),
             postgres: PostgresConfig {
                 search_path: vec!["public".to_string()],
                 extensions: vec![],
                 exclude_tables: vec![],
                 exclude_schemas: vec!["information_schema".to_string(), "pg_catalog".to_string()],
+                include_system_objects: false,
             },
             declarative: DeclarativeConfig {
                 enabled: true,
@@ -60,10 +126,17 @@ impl Default for Config {
                     backup_before_apply: false,
                 },
             },
+            database: DatabaseConfig::default(),
+            output: OutputConfig::default(),
+            diff: DiffConfig::default(),
         }
     }
 }
 
+fn default_migrations_table() -> String {
+    "schema_migrations".to_string()
+}
+
 impl Config {
     pub fn from_path(path: &Path) -> Result<Self> {
         // 🔐 Reject unsupported formats
@@ -77,6 +150,28 @@ impl Config {
         toml::from_str(&content).with_context(|| "Failed to parse TOML config")
     }
 
+    /// The configured migrations table as a possibly schema-qualified name
+    pub fn migrations_table_name(&self) -> QualifiedName {
+        QualifiedName::parse(&self.migrations_table)
+    }
+
+    /// Introspection options derived from the `[postgres]` settings
+    pub fn introspect_options(&self) -> IntrospectOptions {
+        IntrospectOptions {
+            exclude_schemas: self.postgres.exclude_schemas.clone(),
+            include_system_objects: self.postgres.include_system_objects,
+            ..IntrospectOptions::default()
+        }
+    }
+
+    /// Migration generation options derived from the `[diff]` settings
+    pub fn generate_options(&self, idempotent: bool) -> GenerateOptions {
+        GenerateOptions {
+            idempotent,
+            column_using: self.diff.column_using.clone(),
+        }
+    }
+
     pub fn load_schema_files(&self) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
         let mut seen = HashSet::new();
@@ -104,4 +199,4 @@ impl Config {
 
         Ok(files)
     }
-}
\ No newline at end of file
+}
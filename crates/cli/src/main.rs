@@ -1,11 +1,15 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
 use std::path::PathBuf;
 use tracing::{error, info};
 
 mod commands;
 mod config;
-use crate::{commands::*, config::Config};
+use crate::{
+    commands::*,
+    config::{Config, LogFormat},
+};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -19,10 +23,14 @@ pub struct Cli {
     #[arg(short, long)]
     pub config: Option<PathBuf>,
 
-    /// Verbose output
-    #[arg(short, long, default_value = "false")]
+    /// Verbose output, accepted before or after the subcommand
+    #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Log output format
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    pub log_format: LogFormat,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -40,7 +48,7 @@ pub enum Command {
         /// Schema file or directory
         #[arg(short, long, default_value = "schema")]
         schema: PathBuf,
-        /// Output migration file
+        /// Output migration file (printed to stdout when neither this nor --name is given)
         #[arg(short, long)]
         output: Option<PathBuf>,
         /// Database connection string
@@ -49,24 +57,38 @@ pub enum Command {
         /// Migration name (will be used in filename)
         #[arg(short, long)]
         name: Option<String>,
+        /// Database whose schema is the desired state (diff two live databases)
+        #[arg(long, requires = "target", conflicts_with = "database_url")]
+        source: Option<String>,
+        /// Database to migrate towards the source schema
+        #[arg(long, requires = "source", conflicts_with = "database_url")]
+        target: Option<String>,
+        /// Emit ADD COLUMN IF NOT EXISTS / DROP COLUMN IF EXISTS so the migration can be re-run
+        #[arg(long)]
+        idempotent: bool,
     },
     /// Apply migrations to database
     Migrate {
-        /// Migration directory
-        #[arg(short, long, default_value = "migrations")]
-        migrations: PathBuf,
+        /// Migration directories, comma-separated or repeated; merged in timestamp order
+        #[arg(short, long, global = true, default_value = "migrations", value_delimiter = ',')]
+        migrations: Vec<PathBuf>,
         /// Database connection string
-        #[arg(short, long)]
+        #[arg(short, long, global = true)]
         database_url: Option<String>,
         /// Dry run - show what would be executed
-        #[arg(long)]
+        #[arg(long, global = true)]
         dry_run: bool,
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
     },
     /// Validate schema files
     Validate {
         /// Schema file or directory
         #[arg(short, long, default_value = "schema")]
         schema: PathBuf,
+        /// Stop at the first file with errors instead of reporting every file
+        #[arg(long)]
+        fail_fast: bool,
     },
     /// Introspect database and generate schema
     Introspect {
@@ -76,15 +98,93 @@ pub enum Command {
         /// Output directory
         #[arg(short, long, default_value = "schema")]
         output: PathBuf,
-        /// Verbose output
+        /// Wrap owned objects in SET ROLE <owner> / RESET ROLE to reproduce ownership
+        #[arg(long)]
+        set_role: bool,
+        /// Write one file per object category plus a schema.sql including them
+        #[arg(long)]
+        split: bool,
+        /// Omit AUTHORIZATION from CREATE SCHEMA so the output applies on databases without the owner roles
+        #[arg(long)]
+        no_owner: bool,
+        /// Omit COMMENT ON statements
+        #[arg(long)]
+        no_comments: bool,
+        /// Omit GRANT statements
+        #[arg(long)]
+        no_grants: bool,
+        /// Omit SECURITY LABEL statements
+        #[arg(long)]
+        no_security_labels: bool,
+        /// Emit only object definitions: implies --no-owner, --no-comments, --no-grants and
+        /// --no-security-labels and ignores --set-role
+        #[arg(long)]
+        objects_only: bool,
+        /// Maximum number of concurrent introspection queries (default based on max_connections)
+        #[arg(long)]
+        concurrency: Option<usize>,
+        /// Replace passwords in connection strings (e.g. subscriptions) with *****
+        #[arg(long)]
+        redact_secrets: bool,
+        /// Wrap the output in a single BEGIN/COMMIT transaction
+        #[arg(long)]
+        atomic: bool,
+        /// Declare columns backed by their own default sequence as serial/bigserial
+        #[arg(long)]
+        prefer_serial: bool,
+        /// Start the output with DROP ... IF EXISTS CASCADE for every object (destructive)
+        #[arg(long)]
+        with_drops: bool,
+    },
+    /// Print a SHA-256 fingerprint of the normalized schema, to detect drift at a glance
+    Fingerprint {
+        /// Fingerprint these schema files, applied to a scratch database on the
+        /// database_url server, instead of the database itself
         #[arg(short, long)]
-        verbose: bool,
+        schema: Option<PathBuf>,
+        /// Database connection string (defaults to the config's database_url)
+        #[arg(long)]
+        database_url: Option<String>,
+    },
+    /// Re-sync the migrations tracking table with the migration files
+    Repair {
+        /// Migration directories, comma-separated or repeated
+        #[arg(short, long, default_value = "migrations", value_delimiter = ',')]
+        migrations: Vec<PathBuf>,
+        /// Database connection string
+        #[arg(short, long)]
+        database_url: Option<String>,
+        /// Record these pending migrations as applied without running them
+        #[arg(long, value_delimiter = ',')]
+        mark_applied: Vec<String>,
+        /// Remove tracking rows of missing migrations without asking
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Show schema information
     Inspect {
         /// Schema file or directory
         #[arg(short, long, default_value = "schema")]
         schema: PathBuf,
+        /// Database to check for maintenance issues such as unused sequences
+        #[arg(long)]
+        database_url: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MigrateAction {
+    /// Roll back applied migrations using their down sections
+    Down {
+        /// Number of migrations to roll back, newest first
+        #[arg(long, default_value = "1")]
+        steps: usize,
+    },
+    /// List migrations and whether each has been applied
+    Status {
+        /// Only show migrations at or after this version (timestamp prefix)
+        #[arg(long)]
+        since: Option<u64>,
     },
 }
 
@@ -108,9 +208,13 @@ async fn main() -> Result<()> {
 
     // Initialize tracing
     let level = if cli.verbose { "debug" } else { "info" };
-    tracing_subscriber::fmt()
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(format!("shem={}", level))
-        .init();
+        .with_ansi(std::io::stdout().is_terminal());
+    match cli.log_format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber.json().init(),
+    }
 
     info!("Starting shem CLI");
 
@@ -133,42 +237,121 @@ async fn main() -> Result<()> {
             output,
             database_url,
             name,
-        } => {
-            diff::execute(
-                schema,
-                output,
-                database_url.or_else(|| config.database_url.clone()),
-                name,
-                &config,
-            )
-            .await
-        }
+            source,
+            target,
+            idempotent,
+        } => match (source, target) {
+            (Some(source), Some(target)) => {
+                diff::execute_live(source, target, output, name, idempotent, &config).await
+            }
+            _ => {
+                diff::execute(
+                    schema,
+                    output,
+                    database_url.or_else(|| config.database_url.clone()),
+                    name,
+                    idempotent,
+                    &config,
+                )
+                .await
+            }
+        },
         Command::Migrate {
             migrations,
             database_url,
             dry_run,
+            action,
+        } => match action {
+            Some(MigrateAction::Down { steps }) => {
+                migrate::rollback(
+                    migrations,
+                    database_url.or_else(|| config.database_url.clone()),
+                    steps,
+                    dry_run,
+                    &config,
+                )
+                .await
+            }
+            Some(MigrateAction::Status { since }) => {
+                migrate::status(
+                    migrations,
+                    database_url.or_else(|| config.database_url.clone()),
+                    since,
+                    &config,
+                )
+                .await
+            }
+            None => {
+                migrate::execute(
+                    migrations,
+                    database_url.or_else(|| config.database_url.clone()),
+                    dry_run,
+                    cli.log_format,
+                    &config,
+                )
+                .await
+            }
+        },
+        Command::Validate { schema, fail_fast } => {
+            validate::execute(schema.to_str().unwrap(), fail_fast, &config).await
+        }
+        Command::Introspect {
+            database_url,
+            output,
+            set_role,
+            split,
+            no_owner,
+            no_comments,
+            no_grants,
+            no_security_labels,
+            objects_only,
+            concurrency,
+            redact_secrets,
+            atomic,
+            prefer_serial,
+            with_drops,
         } => {
-            migrate::execute(
-                migrations,
+            // The flag overrides `[output] split` from the config file
+            let mut config = config.clone();
+            config.output.split |= split;
+            let toggles = if objects_only {
+                introspect::OutputToggles::objects_only()
+            } else {
+                introspect::OutputToggles {
+                    set_role,
+                    no_owner,
+                    no_comments,
+                    no_grants,
+                    no_security_labels,
+                }
+            };
+            introspect::execute(
                 database_url.or_else(|| config.database_url.clone()),
-                dry_run,
+                output,
                 &config,
+                toggles,
+                concurrency,
+                redact_secrets,
+                atomic,
+                prefer_serial,
+                with_drops,
             )
             .await
         }
-        Command::Validate { schema } => validate::execute(schema.to_str().unwrap(), &config).await,
-        Command::Introspect {
+        Command::Fingerprint {
+            schema,
             database_url,
-            output,
-            verbose,
-        } => introspect::execute(
-            database_url.or_else(|| config.database_url.clone()),
-            output,
-            &config,
-            verbose,
-        )
-        .await,
-        Command::Inspect { schema } => inspect::execute(schema.to_str().unwrap(), &config).await,
+        } => fingerprint::execute(schema, database_url, &config).await,
+        Command::Repair {
+            migrations,
+            database_url,
+            mark_applied,
+            yes,
+        } => migrate::repair(migrations, database_url, mark_applied, yes, &config).await,
+        Command::Inspect {
+            schema,
+            database_url,
+        } => inspect::execute(schema.to_str().unwrap(), database_url, &config).await,
     };
 
     match result {
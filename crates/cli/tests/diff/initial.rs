@@ -0,0 +1,183 @@
+//! Initial migration tests
+//!
+//! Tests for diffing a complete schema against an empty database, which should
+//! produce one create migration in dependency order.
+
+use crate::fixtures::sql;
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_diff_complete_schema_against_empty_database() -> Result<()> {
+    env_logger::try_init().ok();
+    let source_env = TestEnv::new()?;
+    let target_env = TestEnv::new()?;
+    let source_pool = db::setup_test_db(&source_env.db_name).await?;
+    let target_pool = db::setup_test_db(&target_env.db_name).await?;
+
+    // Function bodies hold semicolons, so the fixture is sent in one go
+    sqlx::raw_sql(sql::COMPLETE_SCHEMA).execute(&source_pool).await?;
+
+    let source_url = db::get_database_url(&source_env.db_name);
+    let target_url = db::get_database_url(&target_env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "diff",
+            "--source",
+            &source_url,
+            "--target",
+            &target_url,
+            "--name",
+            "initial",
+        ],
+        &source_env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // A single migration holds the whole schema
+    let files: Vec<_> =
+        std::fs::read_dir(source_env.temp_path().join("migrations"))?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1, "Expected one migration file, got {:?}", files);
+    let migration = std::fs::read_to_string(files[0].path())?;
+    debug!("migration: \n{}", migration);
+    let up = migration.split("-- Down Migration").next().unwrap();
+    assert!(!up.contains("DROP "), "An empty database has nothing to drop");
+
+    // Everything a statement uses is created before it
+    let position = |needle: &str| {
+        up.find(needle)
+            .unwrap_or_else(|| panic!("{} missing from migration", needle))
+    };
+    assert!(position("CREATE EXTENSION") < position(r#"CREATE TYPE "user_status""#));
+    assert!(position(r#"CREATE TYPE "user_status""#) < position(r#"CREATE TABLE "users""#));
+    assert!(position(r#"CREATE DOMAIN "email_address""#) < position(r#"CREATE TABLE "users""#));
+    assert!(position(r#"CREATE TABLE "users""#) < position(r#"CREATE TABLE "posts""#));
+    assert!(position(r#"CREATE TABLE "posts""#) < position(r#"CREATE VIEW "published_posts""#));
+    assert!(position(r#"FUNCTION "update_updated_at""#) < position(r#"CREATE TRIGGER "update_users_updated_at""#));
+
+    // The migration applies to the empty database and reproduces the schema
+    let migrate_output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &target_url],
+        &source_env.temp_path(),
+    )?;
+    assert_command_success(&migrate_output);
+    let (tables,): (i64,) = sqlx::query_as(
+        "SELECT count(*) FROM information_schema.tables \
+         WHERE table_schema = 'public' AND table_name IN ('users', 'posts', 'active_users', 'published_posts')",
+    )
+    .fetch_one(&target_pool)
+    .await?;
+    assert_eq!(tables, 4);
+
+    // Clean up
+    source_pool.close().await;
+    target_pool.close().await;
+    db::drop_test_db(&source_env.db_name).await?;
+    db::drop_test_db(&target_env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_diff_schema_files_against_empty_database() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // The tables reference each other, and the SQL functions read and return them
+    std::fs::create_dir_all(env.temp_path().join("schema"))?;
+    std::fs::write(
+        env.temp_path().join("schema/schema.sql"),
+        "CREATE FUNCTION books_by(author INTEGER) RETURNS SETOF books LANGUAGE sql AS $$
+             SELECT * FROM books WHERE author_id = author
+         $$;
+         CREATE FUNCTION book_count() RETURNS BIGINT LANGUAGE sql AS $$
+             SELECT count(*) FROM books
+         $$;
+         CREATE TABLE authors (id INTEGER, favorite_book_id INTEGER, PRIMARY KEY (id));
+         CREATE TABLE books (
+             id INTEGER,
+             author_id INTEGER,
+             PRIMARY KEY (id),
+             CONSTRAINT books_author_fkey FOREIGN KEY (author_id) REFERENCES authors (id)
+         );
+         ALTER TABLE authors ADD CONSTRAINT authors_favorite_book_fkey
+             FOREIGN KEY (favorite_book_id) REFERENCES books (id);",
+    )?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["diff", "--database-url", &db_url, "--name", "initial"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let files: Vec<_> =
+        std::fs::read_dir(env.temp_path().join("migrations"))?.collect::<Result<_, _>>()?;
+    assert_eq!(files.len(), 1, "Expected one migration file, got {:?}", files);
+    let migration = std::fs::read_to_string(files[0].path())?;
+    debug!("migration: \n{}", migration);
+    let up = migration.split("-- Down Migration").next().unwrap();
+
+    // Foreign keys follow both tables, and the functions follow the foreign keys
+    let position = |needle: &str| {
+        up.find(needle)
+            .unwrap_or_else(|| panic!("{} missing from migration", needle))
+    };
+    assert!(position(r#"CREATE TABLE "authors""#) < position("ADD CONSTRAINT \"books_author_fkey\""));
+    assert!(position(r#"CREATE TABLE "books""#) < position("ADD CONSTRAINT \"authors_favorite_book_fkey\""));
+    assert!(position("ADD CONSTRAINT \"authors_favorite_book_fkey\"") < position(r#"FUNCTION "books_by""#));
+    assert!(position("ADD CONSTRAINT \"books_author_fkey\"") < position(r#"FUNCTION "book_count""#));
+
+    // The migration applies to the empty database
+    let migrate_output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&migrate_output);
+    let (foreign_keys,): (i64,) = sqlx::query_as(
+        "SELECT count(*) FROM pg_constraint WHERE contype = 'f' \
+         AND conname IN ('books_author_fkey', 'authors_favorite_book_fkey')",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(foreign_keys, 2);
+    let (books,): (i64,) = sqlx::query_as("SELECT book_count()").fetch_one(&pool).await?;
+    assert_eq!(books, 0);
+    sqlx::raw_sql("SELECT * FROM books_by(1)").execute(&pool).await?;
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_diff_schema_files_with_alter_table_additions() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+
+    // Columns and constraints added by ALTER TABLE belong to the table, however
+    // the table is qualified
+    std::fs::create_dir_all(env.temp_path().join("schema"))?;
+    std::fs::write(
+        env.temp_path().join("schema/schema.sql"),
+        "CREATE TABLE users (id INTEGER);
+         ALTER TABLE public.users ADD COLUMN email TEXT NOT NULL;
+         ALTER TABLE users ADD CONSTRAINT users_id_check CHECK (id > 0);
+         ALTER TABLE users ADD CONSTRAINT users_email_excl EXCLUDE USING btree (email WITH =);",
+    )?;
+
+    let output = run_shem_command_in_dir(&["diff", "--name", "initial"], &env.temp_path())?;
+    assert_command_success(&output);
+
+    let files: Vec<_> =
+        std::fs::read_dir(env.temp_path().join("migrations"))?.collect::<Result<_, _>>()?;
+    let migration = std::fs::read_to_string(files[0].path())?;
+    debug!("migration: \n{}", migration);
+    let up = migration.split("-- Down Migration").next().unwrap();
+    assert!(up.contains(r#""email" TEXT NOT NULL"#));
+    assert!(up.contains(r#"CONSTRAINT "users_id_check" CHECK"#));
+    assert!(up.contains(r#"CONSTRAINT "users_email_excl" EXCLUDE USING btree (email WITH =)"#));
+    Ok(())
+}
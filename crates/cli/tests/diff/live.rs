@@ -0,0 +1,108 @@
+//! Live database diff tests
+//!
+//! Tests for diffing two introspected databases with --source/--target.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_diff_two_databases() -> Result<()> {
+    env_logger::try_init().ok();
+    let source_env = TestEnv::new()?;
+    let target_env = TestEnv::new()?;
+    let source_pool = db::setup_test_db(&source_env.db_name).await?;
+    let target_pool = db::setup_test_db(&target_env.db_name).await?;
+
+    // Both databases share one table, the source has an extra one
+    for pool in [&source_pool, &target_pool] {
+        db::execute_sql(pool, "CREATE TABLE users (id integer PRIMARY KEY, email text);").await?;
+    }
+    db::execute_sql(
+        &source_pool,
+        "CREATE TABLE orders (id integer PRIMARY KEY, total numeric);",
+    )
+    .await?;
+
+    let source_url = db::get_database_url(&source_env.db_name);
+    let target_url = db::get_database_url(&target_env.db_name);
+
+    let output = run_shem_command_in_dir(
+        &[
+            "diff",
+            "--source",
+            &source_url,
+            "--target",
+            &target_url,
+            "--output",
+            "live_diff.sql",
+        ],
+        &source_env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let migration = std::fs::read_to_string(source_env.temp_path().join("live_diff.sql"))?;
+    debug!("migration: \n{}", migration);
+    let up = migration.split("-- Down Migration").next().unwrap();
+    assert!(up.contains("CREATE TABLE") && up.contains("orders"));
+    assert!(!up.contains("users"), "Shared table should not be part of the diff");
+
+    // Clean up
+    source_pool.close().await;
+    target_pool.close().await;
+    db::drop_test_db(&source_env.db_name).await?;
+    db::drop_test_db(&target_env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_diff_alters_changed_tables_to_stdout() -> Result<()> {
+    env_logger::try_init().ok();
+    let source_env = TestEnv::new()?;
+    let target_env = TestEnv::new()?;
+    let source_pool = db::setup_test_db(&source_env.db_name).await?;
+    let target_pool = db::setup_test_db(&target_env.db_name).await?;
+
+    // The source adds a column and drops a table the target still has
+    db::execute_sql(
+        &source_pool,
+        "CREATE TABLE users (id integer PRIMARY KEY, email text NOT NULL);",
+    )
+    .await?;
+    db::execute_sql(&target_pool, "CREATE TABLE users (id integer PRIMARY KEY);").await?;
+    db::execute_sql(&target_pool, "CREATE TABLE legacy (id integer);").await?;
+
+    // A same-named table in another schema is altered under its own name
+    db::execute_sql(
+        &source_pool,
+        "CREATE SCHEMA app; CREATE TABLE app.users (id integer, active boolean);",
+    )
+    .await?;
+    db::execute_sql(&target_pool, "CREATE SCHEMA app; CREATE TABLE app.users (id integer);").await?;
+
+    let source_url = db::get_database_url(&source_env.db_name);
+    let target_url = db::get_database_url(&target_env.db_name);
+
+    let output = run_shem_command_in_dir(
+        &["diff", "--source", &source_url, "--target", &target_url],
+        &source_env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let migration = String::from_utf8_lossy(&output.stdout);
+    debug!("migration: \n{}", migration);
+    let (up, down) = migration.split_once("-- Down Migration").unwrap();
+    assert!(up.contains("ALTER TABLE \"users\" ADD COLUMN \"email\" text NOT NULL;"));
+    assert!(up.contains("ALTER TABLE \"app\".\"users\" ADD COLUMN \"active\" boolean;"));
+    assert!(up.contains("DROP TABLE IF EXISTS \"legacy\" CASCADE;"));
+    assert!(down.contains("ALTER TABLE \"users\" DROP COLUMN \"email\";"));
+    assert!(down.contains("ALTER TABLE \"app\".\"users\" DROP COLUMN \"active\";"));
+    assert!(!up.contains("CREATE TABLE"), "Existing tables should be altered");
+
+    // Clean up
+    source_pool.close().await;
+    target_pool.close().await;
+    db::drop_test_db(&source_env.db_name).await?;
+    db::drop_test_db(&target_env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,6 @@
+//! Diff command tests
+//! 
+//! Tests for the diff command functionality.
+
+pub mod live;
+pub mod initial;
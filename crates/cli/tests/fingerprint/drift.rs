@@ -0,0 +1,183 @@
+//! Fingerprint drift tests
+//!
+//! Fingerprints two databases built from the same schema in a different order,
+//! one with an extra column, one migrated from schema files, and the schema
+//! files themselves.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use std::path::PathBuf;
+
+/// Test helper creating a database from `sql` and returning its fingerprint
+async fn fingerprint_of(sql: &str, dir: &PathBuf) -> Result<String> {
+    let db_name = db::generate_unique_db_name();
+    let pool = db::setup_test_db(&db_name).await?;
+    sqlx::raw_sql(sql).execute(&pool).await?;
+
+    let fingerprint = run_fingerprint(&["--database-url", &db::get_database_url(&db_name)], dir)?;
+
+    pool.close().await;
+    db::drop_test_db(&db_name).await?;
+    Ok(fingerprint)
+}
+
+/// Test helper running `shem fingerprint` with `args`
+fn run_fingerprint(args: &[&str], dir: &PathBuf) -> Result<String> {
+    let output = run_shem_command_in_dir(&[&["fingerprint"], args].concat(), dir)?;
+    assert_command_success(&output);
+
+    // Logs share stdout, the fingerprint is the line of 64 hex digits
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fingerprint = stdout
+        .lines()
+        .find(|line| line.len() == 64 && line.chars().all(|c| c.is_ascii_hexdigit()))
+        .expect("fingerprint line");
+    Ok(fingerprint.to_string())
+}
+
+#[tokio::test]
+async fn test_fingerprint_matches_equal_schemas_and_detects_changes() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+
+    let original = fingerprint_of(
+        "CREATE TABLE customers (id integer PRIMARY KEY, name text);
+         CREATE TABLE orders (id integer PRIMARY KEY, customer_id integer REFERENCES customers);
+         CREATE INDEX orders_customer_idx ON orders (customer_id);",
+        &env.temp_path(),
+    )
+    .await?;
+    let reordered = fingerprint_of(
+        "CREATE TABLE orders (id int4 PRIMARY KEY, customer_id int4);
+         CREATE INDEX orders_customer_idx ON orders (customer_id);
+         CREATE TABLE customers (id int4 PRIMARY KEY, name text);
+         ALTER TABLE orders ADD FOREIGN KEY (customer_id) REFERENCES customers;",
+        &env.temp_path(),
+    )
+    .await?;
+    let changed = fingerprint_of(
+        "CREATE TABLE customers (id integer PRIMARY KEY, name text, email text);
+         CREATE TABLE orders (id integer PRIMARY KEY, customer_id integer REFERENCES customers);
+         CREATE INDEX orders_customer_idx ON orders (customer_id);",
+        &env.temp_path(),
+    )
+    .await?;
+
+    assert_eq!(original, reordered);
+    assert_ne!(original, changed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fingerprint_of_database_migrated_from_schema_files() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+    let schema = "CREATE TABLE customers (id INTEGER, name TEXT, PRIMARY KEY (id));
+         CREATE TABLE orders (
+             id INTEGER,
+             customer_id INTEGER,
+             PRIMARY KEY (id),
+             CONSTRAINT orders_customer_fkey FOREIGN KEY (customer_id) REFERENCES customers (id)
+         );";
+
+    // Schema files are fingerprinted through a database migrated from them,
+    // tracked in a table of its own name
+    std::fs::write(
+        env.temp_path().join("shem.toml"),
+        r#"
+schema_dir = "schema"
+migrations_dir = "migrations"
+migrations_table = "applied_migrations"
+
+[postgres]
+search_path = ["public"]
+extensions = []
+exclude_tables = []
+exclude_schemas = ["information_schema", "pg_catalog"]
+
+[declarative]
+enabled = true
+schema_paths = ["./schema/*.sql"]
+shadow_port = 5433
+auto_cleanup = true
+
+[declarative.safety_checks]
+warn_on_drop = true
+require_confirmation = true
+backup_before_apply = false
+"#,
+    )?;
+    std::fs::create_dir_all(env.temp_path().join("schema"))?;
+    std::fs::write(env.temp_path().join("schema/schema.sql"), schema)?;
+    let output = run_shem_command_in_dir(&["diff", "--name", "initial"], &env.temp_path())?;
+    assert_command_success(&output);
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(&["migrate", "--database-url", &db_url], &env.temp_path())?;
+    assert_command_success(&output);
+    let tracked: i64 = sqlx::query_scalar("SELECT count(*) FROM applied_migrations")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(tracked, 1);
+    let migrated = run_fingerprint(&["--database-url", &db_url], &env.temp_path())?;
+
+    // The tracking table the migration left behind doesn't count
+    let direct = fingerprint_of(schema, &env.temp_path()).await?;
+    assert_eq!(migrated, direct);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fingerprint_of_schema_files_matches_database() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+    let db_url = db::get_database_url(&env.db_name);
+    let schema = |check: &str| {
+        format!(
+            "CREATE TABLE customers (id integer PRIMARY KEY, name text);
+             CREATE TABLE orders (
+                 id integer PRIMARY KEY,
+                 customer_id integer REFERENCES customers,
+                 total numeric CHECK ({check})
+             );
+             COMMENT ON TABLE orders IS 'Placed orders';"
+        )
+    };
+
+    // The files are applied to a scratch database, so nothing the parser
+    // leaves out tells them apart from a database built from them
+    std::fs::create_dir_all(env.temp_path().join("schema"))?;
+    std::fs::write(env.temp_path().join("schema/schema.sql"), schema("total >= 0"))?;
+    let files = run_fingerprint(
+        &["--schema", "schema", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    let database = fingerprint_of(&schema("total >= 0"), &env.temp_path()).await?;
+    assert_eq!(files, database);
+
+    // Editing the CHECK changes the fingerprint
+    std::fs::write(env.temp_path().join("schema/schema.sql"), schema("total > 0"))?;
+    let edited = run_fingerprint(
+        &["--schema", "schema", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_ne!(files, edited);
+
+    // The scratch databases are dropped again
+    let leftover: i64 = sqlx::query_scalar(
+        "SELECT count(*) FROM pg_database WHERE datname LIKE 'shem_fingerprint_%'",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(leftover, 0);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
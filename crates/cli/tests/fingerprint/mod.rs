@@ -0,0 +1,5 @@
+//! Fingerprint command tests
+//!
+//! Tests that the schema fingerprint only changes when the schema does.
+
+pub mod drift;
@@ -224,8 +224,6 @@ pub mod sql {
 
 /// Expected schema output fixtures
 pub mod expected {
-    use super::*;
-
     /// Expected output for simple table introspection
     pub const SIMPLE_TABLE_SCHEMA: &str = r#"
         CREATE TABLE users (
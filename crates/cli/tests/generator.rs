@@ -1,5 +1,11 @@
 // Import all organized test modules
+mod diff;
+mod fingerprint;
+pub mod fixtures;
 mod introspect;
+mod migrate;
+mod round_trip;
+mod validate;
 
 // Re-export all tests for backward compatibility
 pub use introspect::*;
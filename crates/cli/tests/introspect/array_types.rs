@@ -0,0 +1,42 @@
+//! Array type introspection tests
+//!
+//! Tests that the implicit array types PostgreSQL creates for every type are
+//! not emitted as objects of their own.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_does_not_emit_implicit_array_types() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    sqlx::raw_sql(
+        "CREATE TYPE mood AS ENUM ('happy', 'sad'); \
+         CREATE TABLE diary (id integer PRIMARY KEY, moods mood[]);",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    // `_mood` and `_diary` come with `mood` and `diary`, creating them again fails
+    assert_eq!(schema_content.matches("CREATE TYPE").count(), 1);
+    assert!(schema_content.contains("CREATE TYPE public.mood AS ENUM"));
+    assert!(!schema_content.contains("_mood"));
+    assert!(!schema_content.contains("_diary"));
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
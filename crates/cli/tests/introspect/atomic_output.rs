@@ -0,0 +1,68 @@
+//! Atomic output introspection tests
+//!
+//! Tests for wrapping the introspected schema in a single transaction.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_atomic_output() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(&pool, "CREATE TABLE accounts (id integer PRIMARY KEY);").await?;
+    db::execute_sql(
+        &pool,
+        "CREATE SUBSCRIPTION atomic_sub CONNECTION 'host=localhost dbname=remote_db' PUBLICATION atomic_pub WITH (connect = false);",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "schema",
+            "--atomic",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.starts_with("BEGIN;\n"));
+    let begin = schema_content.find("BEGIN;").unwrap();
+    let commit = schema_content.find("COMMIT;").unwrap();
+    let table = schema_content.find("CREATE TABLE public.accounts").unwrap();
+    let subscription = schema_content.find("CREATE SUBSCRIPTION atomic_sub").unwrap();
+    assert!(begin < table && table < commit);
+    // CREATE SUBSCRIPTION can't run in a transaction block, so it follows the COMMIT
+    assert!(commit < subscription);
+
+    // Split output has no single file to wrap
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "split",
+            "--split",
+            "--atomic",
+        ],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+
+    // Clean up
+    db::execute_sql(&pool, "ALTER SUBSCRIPTION atomic_sub SET (slot_name = NONE);").await?;
+    db::execute_sql(&pool, "DROP SUBSCRIPTION atomic_sub;").await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Column collation introspection tests
+//!
+//! Tests that a column's non-default collation is kept in the introspect output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_column_collation_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE labels (code text COLLATE \"C\" NOT NULL, name text)",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("code text COLLATE \"C\" NOT NULL"));
+    // The default collation is left out
+    assert!(schema_content.contains("name text\n"));
+
+    // Replaying the table restores the collation
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE TABLE") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let (collation,): (String,) = sqlx::query_as(
+        "SELECT c.collname::text FROM pg_attribute a JOIN pg_collation c ON c.oid = a.attcollation \
+         WHERE a.attrelid = 'labels'::regclass AND a.attname = 'code'",
+    )
+    .fetch_one(&replay_pool)
+    .await?;
+    assert_eq!(collation, "C");
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
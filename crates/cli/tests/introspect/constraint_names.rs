@@ -0,0 +1,41 @@
+//! Constraint name introspection tests
+//!
+//! Tests that table constraints keep their names instead of getting ones
+//! generated by PostgreSQL on replay.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_keeps_table_constraint_names() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    sqlx::raw_sql(
+        "CREATE TABLE items (\
+         qty integer CONSTRAINT positive_qty CHECK (qty > 0), \
+         code text CONSTRAINT \"Code_Unique\" UNIQUE);",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("CONSTRAINT positive_qty CHECK ((qty > 0))"));
+    assert!(schema_content.contains("CONSTRAINT \"Code_Unique\" UNIQUE (code)"));
+    assert_eq!(schema_content.matches("CONSTRAINT").count(), 2);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Deferrable constraint introspection tests
+//!
+//! Tests for keeping the deferrability of primary key and unique constraints
+//! through the introspected SQL and back.
+
+use anyhow::Result;
+use cli::commands::introspect::SqlSerializer;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use shem_core::ConstraintKind;
+use shem_core::traits::SchemaSerializer;
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_deferrable_primary_key_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE ledger (
+            id integer PRIMARY KEY DEFERRABLE INITIALLY DEFERRED,
+            code text UNIQUE DEFERRABLE
+        );",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("PRIMARY KEY (id) DEFERRABLE INITIALLY DEFERRED"));
+    assert!(schema_content.contains("UNIQUE (code) DEFERRABLE"));
+
+    // Reading the output back keeps the same deferrability
+    let schema = SqlSerializer::default().deserialize(&schema_content).await?;
+    let ledger = schema.tables.get("ledger").expect("ledger should be parsed");
+    let pkey = ledger
+        .constraints
+        .iter()
+        .find(|c| c.kind == ConstraintKind::PrimaryKey)
+        .expect("primary key should be parsed");
+    assert!(pkey.deferrable && pkey.initially_deferred);
+    assert_eq!(pkey.definition, "PRIMARY KEY (id) DEFERRABLE INITIALLY DEFERRED");
+    let unique = ledger
+        .constraints
+        .iter()
+        .find(|c| c.kind == ConstraintKind::Unique)
+        .expect("unique constraint should be parsed");
+    assert!(unique.deferrable && !unique.initially_deferred);
+    assert_eq!(unique.definition, "UNIQUE (code) DEFERRABLE");
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Deterministic output introspection tests
+//!
+//! Tests that introspecting an unchanged database writes identical output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_introspect_twice_writes_identical_output() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // Enough objects per category that hash ordering would show
+    for name in ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"] {
+        db::execute_sql(
+            &pool,
+            &format!("CREATE TYPE {}_state AS ENUM ('on', 'off');", name),
+        )
+        .await?;
+        db::execute_sql(
+            &pool,
+            &format!(
+                "CREATE TABLE {}_items (id integer PRIMARY KEY, state {}_state);",
+                name, name
+            ),
+        )
+        .await?;
+        db::execute_sql(
+            &pool,
+            &format!(
+                "CREATE VIEW {}_ids AS SELECT id FROM {}_items;",
+                name, name
+            ),
+        )
+        .await?;
+        db::execute_sql(
+            &pool,
+            &format!("COMMENT ON TABLE {}_items IS '{} items';", name, name),
+        )
+        .await?;
+    }
+
+    let db_url = db::get_database_url(&env.db_name);
+    for output_dir in ["first", "second"] {
+        let output = run_shem_command_in_dir(
+            &["introspect", "--database-url", &db_url, "--output", output_dir],
+            &env.temp_path(),
+        )?;
+        assert_command_success(&output);
+    }
+
+    // Roles are cluster-wide and tests running alongside create their own
+    let read = |dir: &str| -> Result<String> {
+        let content = std::fs::read_to_string(env.temp_path().join(dir).join("schema.sql"))?;
+        Ok(content
+            .split("\n\n")
+            .filter(|statement| !statement.contains(" ROLE "))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    };
+    let first = read("first")?;
+    assert!(first.contains("CREATE TABLE public.foxtrot_items"));
+    assert_eq!(first, read("second")?);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
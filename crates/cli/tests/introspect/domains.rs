@@ -1,10 +1,56 @@
 //! Domain introspection tests
-//! 
-//! Tests for introspecting various types of domains.
-
-// TODO: Implement domain introspection tests
-// This module will contain tests for:
-// - Simple domains
-// - Domains with constraints
-// - Domains with default values
-// - Domains with collations 
\ No newline at end of file
+//!
+//! Tests that a domain's default, NOT NULL and checks survive the introspect output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_domain_default_not_null_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE DOMAIN positive_int AS integer NOT NULL DEFAULT 1 CHECK (VALUE > 0)",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains(
+        "CREATE DOMAIN public.positive_int AS integer DEFAULT 1 NOT NULL CONSTRAINT positive_int_check CHECK ((VALUE > 0))"
+    ));
+
+    // Replaying the domain restores the default and NOT NULL
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE DOMAIN") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let (default, not_null): (Option<String>, bool) = sqlx::query_as(
+        "SELECT typdefault, typnotnull FROM pg_type WHERE typname = 'positive_int'",
+    )
+    .fetch_one(&replay_pool)
+    .await?;
+    assert_eq!(default.as_deref(), Some("1"));
+    assert!(not_null);
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Foreign key introspection tests
+//!
+//! Tests that foreign keys are added after every table exists, so tables
+//! referencing each other can be created.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_foreign_keys_only_added_after_tables() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    sqlx::raw_sql(
+        "CREATE TABLE authors (id integer PRIMARY KEY, favorite_book integer); \
+         CREATE TABLE books (id integer PRIMARY KEY, author integer REFERENCES authors); \
+         ALTER TABLE authors ADD CONSTRAINT authors_favorite_fkey \
+         FOREIGN KEY (favorite_book) REFERENCES books;",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert_eq!(schema_content.matches("FOREIGN KEY").count(), 2);
+    for constraint in ["authors_favorite_fkey", "books_author_fkey"] {
+        assert_eq!(
+            schema_content
+                .matches(&format!("ADD CONSTRAINT {constraint} FOREIGN KEY"))
+                .count(),
+            1,
+            "{constraint} should only be added by ALTER TABLE"
+        );
+    }
+    // The tables reference each other, neither can be created with its foreign key
+    let last_table_pos = schema_content.rfind("CREATE TABLE").unwrap();
+    assert!(
+        schema_content
+            .match_indices("FOREIGN KEY")
+            .all(|(pos, _)| pos > last_table_pos)
+    );
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Function attribute introspection tests
+//!
+//! Tests that volatility, strictness, security, parallel safety and cost hints
+//! survive a round trip through the introspect output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_stable_strict_function_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE FUNCTION add_tax(amount numeric) RETURNS numeric LANGUAGE sql \
+         STABLE STRICT SECURITY DEFINER PARALLEL SAFE COST 5 AS 'SELECT amount * 1.2'",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE FUNCTION net_amount(amount numeric) RETURNS numeric LANGUAGE sql AS 'SELECT amount'",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content
+        .contains("LANGUAGE sql STABLE STRICT SECURITY DEFINER PARALLEL SAFE COST 5 AS"));
+    // Defaults are left out
+    assert!(schema_content.contains("RETURNS numeric LANGUAGE sql AS $$SELECT amount$$"));
+
+    // Replaying the functions restores the same attributes
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE FUNCTION") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let attributes: Vec<(String, String, bool, bool, String, f32)> = sqlx::query_as(
+        "SELECT proname::text, provolatile::text, proisstrict, prosecdef, proparallel::text, procost \
+         FROM pg_proc WHERE proname IN ('add_tax', 'net_amount') ORDER BY proname",
+    )
+    .fetch_all(&replay_pool)
+    .await?;
+    assert_eq!(
+        attributes,
+        vec![
+            ("add_tax".to_string(), "s".to_string(), true, true, "s".to_string(), 5.0),
+            ("net_amount".to_string(), "v".to_string(), false, false, "u".to_string(), 100.0),
+        ]
+    );
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
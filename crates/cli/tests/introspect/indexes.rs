@@ -0,0 +1,72 @@
+//! Index introspection tests
+//!
+//! Tests that table and materialized view indexes are written after their owner.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+const INDEX_DEFINITIONS: &str = "SELECT indexname::text, indexdef FROM pg_indexes \
+                                 WHERE schemaname = 'public' ORDER BY indexname";
+
+#[tokio::test]
+async fn test_introspect_index_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE accounts (id integer PRIMARY KEY, email text, active boolean, plan text);
+         CREATE UNIQUE INDEX accounts_email_idx ON accounts USING btree (email DESC NULLS LAST) WHERE active;
+         CREATE INDEX accounts_lower_email_idx ON accounts (lower(email)) INCLUDE (plan) WITH (fillfactor = 80);
+         CREATE MATERIALIZED VIEW plan_counts AS SELECT plan, count(*) AS total FROM accounts GROUP BY plan;
+         CREATE UNIQUE INDEX plan_counts_plan_idx ON plan_counts (plan NULLS FIRST);",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains(
+        "CREATE UNIQUE INDEX accounts_email_idx ON public.accounts USING btree (email DESC NULLS LAST) WHERE active;"
+    ));
+    assert!(schema_content.contains(
+        "CREATE INDEX accounts_lower_email_idx ON public.accounts USING btree ((lower(email))) INCLUDE (plan) WITH (fillfactor = 80);"
+    ));
+    assert!(schema_content.contains(
+        "CREATE UNIQUE INDEX plan_counts_plan_idx ON public.plan_counts USING btree (plan NULLS FIRST);"
+    ));
+    // The primary key index comes with the constraint
+    assert!(!schema_content.contains("INDEX accounts_pkey"));
+
+    // Replaying the tables and views recreates identical indexes
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE TABLE") || statement.starts_with("CREATE MATERIALIZED VIEW")
+        {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let original: Vec<(String, String)> = sqlx::query_as(INDEX_DEFINITIONS)
+        .fetch_all(&pool)
+        .await?;
+    let replayed: Vec<(String, String)> = sqlx::query_as(INDEX_DEFINITIONS)
+        .fetch_all(&replay_pool)
+        .await?;
+    assert_eq!(replayed, original);
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
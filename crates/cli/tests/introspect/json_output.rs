@@ -0,0 +1,111 @@
+//! JSON output introspection tests
+//!
+//! Tests for writing the introspected schema as JSON.
+
+use anyhow::Result;
+use cli::commands::introspect::JsonSerializer;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use shem_core::traits::SchemaSerializer;
+
+const JSON_CONFIG: &str = r#"
+schema_dir = "schema"
+migrations_dir = "migrations"
+
+[postgres]
+search_path = ["public"]
+extensions = []
+exclude_tables = []
+exclude_schemas = ["information_schema", "pg_catalog"]
+
+[declarative]
+enabled = true
+schema_paths = ["./schema/*.sql"]
+shadow_port = 5433
+auto_cleanup = true
+
+[declarative.safety_checks]
+warn_on_drop = true
+require_confirmation = true
+backup_before_apply = false
+
+[output]
+format = "json"
+"#;
+
+#[tokio::test]
+async fn test_introspect_writes_json_snapshot() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE accounts (id integer PRIMARY KEY, email text);",
+    )
+    .await?;
+    std::fs::write(env.temp_path().join("shem.toml"), JSON_CONFIG)?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "snapshot",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    assert!(!env.temp_path().join("snapshot/schema.sql").exists());
+
+    // The snapshot reads back into the same schema
+    let content = std::fs::read_to_string(env.temp_path().join("snapshot/schema.json"))?;
+    let serializer = JsonSerializer;
+    let schema = serializer.deserialize(&content).await?;
+    let accounts = schema
+        .tables
+        .get("accounts")
+        .expect("accounts should be in the snapshot");
+    assert_eq!(accounts.columns.len(), 2);
+    let reserialized = serializer.serialize(&schema).await?;
+    assert_eq!(serializer.deserialize(&reserialized).await?, schema);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_json_rejects_sql_output_toggles() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+    std::fs::write(env.temp_path().join("shem.toml"), JSON_CONFIG)?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    for toggle in ["--objects-only", "--no-comments", "--no-grants", "--no-security-labels"] {
+        let output = run_shem_command_in_dir(
+            &[
+                "introspect",
+                "--database-url",
+                &db_url,
+                "--output",
+                "snapshot",
+                toggle,
+            ],
+            &env.temp_path(),
+        )?;
+        // The snapshot would silently keep what the flag asks to leave out
+        assert!(!output.status.success(), "{toggle} should be rejected");
+        // Errors are logged to stdout
+        assert!(String::from_utf8_lossy(&output.stdout).contains("require SQL output"));
+        assert!(!env.temp_path().join("snapshot/schema.json").exists());
+    }
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -2,7 +2,34 @@
 //! 
 //! Tests for the introspect command functionality, organized by object type.
 
+pub mod array_types;
+pub mod atomic_output;
+pub mod check_constraints;
+pub mod column_collation;
+pub mod comments;
+pub mod constraint_names;
+pub mod deferrable_constraints;
+pub mod deterministic_output;
+pub mod domains;
 pub mod extensions;
+pub mod foreign_keys;
+pub mod foreign_tables;
+pub mod function_attributes;
+pub mod indexes;
+pub mod json_output;
+pub mod object_order;
+pub mod objects_only;
+pub mod output_format;
+pub mod ownership;
+pub mod quoted_identifiers;
+pub mod sequence_ownership;
+pub mod servers;
+pub mod split_output;
+pub mod subscriptions;
+pub mod trigger_clauses;
+pub mod table_options;
+pub mod verbose;
+pub mod with_drops;
 //pub mod tables;
 //pub mod views;
 //pub mod functions;
@@ -10,7 +37,6 @@ pub mod extensions;
 //pub mod types;
 //pub mod sequences;
 
-//pub mod domains;
 //pub mod policies;
 //pub mod rules;
 //pub mod event_triggers;
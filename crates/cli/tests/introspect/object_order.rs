@@ -0,0 +1,176 @@
+//! Object order introspection tests
+//!
+//! Tests for reordering object categories with `[output] object_order`.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+fn config_with_order(object_order: &str) -> String {
+    format!(
+        r#"
+schema_dir = "schema"
+migrations_dir = "migrations"
+
+[postgres]
+search_path = ["public"]
+extensions = []
+exclude_tables = []
+exclude_schemas = ["information_schema", "pg_catalog"]
+
+[declarative]
+enabled = true
+schema_paths = ["./schema/*.sql"]
+shadow_port = 5433
+auto_cleanup = true
+
+[declarative.safety_checks]
+warn_on_drop = true
+require_confirmation = true
+backup_before_apply = false
+
+[output]
+object_order = {}
+"#,
+        object_order
+    )
+}
+
+#[tokio::test]
+async fn test_introspect_custom_object_order() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE accounts (id integer PRIMARY KEY, email text);",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE logins (id integer PRIMARY KEY, account_id integer REFERENCES accounts (id));",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE VIEW account_emails AS SELECT email FROM accounts;",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE FUNCTION add_one(x integer) RETURNS integer LANGUAGE sql AS 'SELECT x + 1';",
+    )
+    .await?;
+
+    // Functions normally follow views; list them before tables instead
+    std::fs::write(
+        env.temp_path().join("shem.toml"),
+        config_with_order(r#"["schemas", "functions", "tables", "foreign_keys"]"#),
+    )?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema.sql: \n{}", schema_content);
+    let function = schema_content.find("FUNCTION public.add_one").unwrap();
+    let accounts = schema_content.find("CREATE TABLE public.accounts").unwrap();
+    let logins = schema_content.find("CREATE TABLE public.logins").unwrap();
+    let foreign_key = schema_content.find("FOREIGN KEY").unwrap();
+    let view = schema_content.find("CREATE VIEW").unwrap();
+    assert!(function < accounts);
+    // Tables keep their dependency order within the group
+    assert!(accounts < logins);
+    assert!(logins < foreign_key);
+    // Unlisted categories keep their place, after the tables they select from
+    assert!(logins < view && view < foreign_key);
+
+    // Foreign keys can't be created before the tables they belong to
+    std::fs::write(
+        env.temp_path().join("shem.toml"),
+        config_with_order(r#"["foreign_keys", "tables"]"#),
+    )?;
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "rejected"],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("object_order must keep tables before foreign_keys"));
+
+    // Moving tables after views would also push them past the unlisted foreign keys
+    std::fs::write(
+        env.temp_path().join("shem.toml"),
+        config_with_order(r#"["views", "tables"]"#),
+    )?;
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "rejected"],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("object_order must keep tables before foreign_keys"));
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_object_order_keeps_unlisted_categories_in_place() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    sqlx::raw_sql(
+        "CREATE SCHEMA app;
+         CREATE TYPE app.status AS ENUM ('active', 'closed');
+         CREATE FUNCTION app.is_code(value text) RETURNS boolean
+             LANGUAGE sql IMMUTABLE AS $$ SELECT value ~ '^[A-Z]+$' $$;
+         CREATE FUNCTION app.describe(value integer) RETURNS text
+             LANGUAGE sql AS $$ SELECT value::text $$;
+         CREATE TABLE app.accounts (
+             code text CHECK (app.is_code(code)),
+             status app.status
+         );",
+    )
+    .execute(&pool)
+    .await?;
+
+    // Only tables are listed, so nothing else moves around them
+    std::fs::write(
+        env.temp_path().join("shem.toml"),
+        config_with_order(r#"["tables"]"#),
+    )?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema.sql: \n{}", schema_content);
+    let schema = schema_content.find("CREATE SCHEMA IF NOT EXISTS app").unwrap();
+    let status = schema_content.find("CREATE TYPE app.status").unwrap();
+    let is_code = schema_content.find("FUNCTION app.is_code").unwrap();
+    let accounts = schema_content.find("CREATE TABLE app.accounts").unwrap();
+    let describe = schema_content.find("FUNCTION app.describe").unwrap();
+    assert!(schema < status && status < accounts);
+    // The function the check constraint calls stays ahead of the table, the
+    // other function stays after it
+    assert!(is_code < accounts);
+    assert!(accounts < describe);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
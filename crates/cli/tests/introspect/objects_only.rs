@@ -0,0 +1,90 @@
+//! Objects-only introspection tests
+//!
+//! Tests for leaving comments, grants, security labels and ownership out of the
+//! introspected schema.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_objects_only() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // Keep the role under the 63 byte identifier limit so it is not truncated
+    let role = format!("objects_{}", &env.db_name[env.db_name.len() - 16..]);
+    db::execute_sql(&pool, &format!("CREATE ROLE {};", role)).await?;
+    db::execute_sql(&pool, &format!("CREATE SCHEMA app AUTHORIZATION {};", role)).await?;
+    db::execute_sql(&pool, "CREATE TABLE app.items (id integer PRIMARY KEY, name text);").await?;
+    db::execute_sql(&pool, &format!("ALTER TABLE app.items OWNER TO {};", role)).await?;
+    db::execute_sql(&pool, "COMMENT ON TABLE app.items IS 'Catalog items';").await?;
+    db::execute_sql(&pool, "COMMENT ON COLUMN app.items.name IS 'Display name';").await?;
+    db::execute_sql(&pool, "GRANT SELECT ON app.items TO PUBLIC;").await?;
+    // No label provider is loaded, so write the label into the catalog directly
+    db::execute_sql(
+        &pool,
+        "INSERT INTO pg_seclabel (objoid, classoid, objsubid, provider, label) \
+         VALUES ('app.items'::regclass, 'pg_class'::regclass, 0, 'dummy', 'classified');",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+
+    // By default comments, grants, security labels and schema ownership are all reproduced
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "full"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let schema_content = std::fs::read_to_string(env.temp_path().join("full/schema.sql"))?;
+    assert!(schema_content.contains("COMMENT ON TABLE"));
+    assert!(schema_content.contains("GRANT SELECT"));
+    assert!(schema_content.contains("AUTHORIZATION"));
+    assert!(
+        schema_content.contains("SECURITY LABEL FOR dummy ON TABLE app.items IS 'classified';")
+    );
+
+    // --objects-only wins over --set-role and leaves only the definitions
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "schema",
+            "--objects-only",
+            "--set-role",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("CREATE SCHEMA IF NOT EXISTS app;"));
+    assert!(schema_content.contains("CREATE TABLE"));
+    for excluded in [
+        "COMMENT ON",
+        "GRANT",
+        "SECURITY LABEL",
+        "AUTHORIZATION",
+        "OWNER",
+        "SET ROLE",
+    ] {
+        assert!(
+            !schema_content.contains(excluded),
+            "unexpected {} in:\n{}",
+            excluded,
+            schema_content
+        );
+    }
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    let admin = db::get_test_pool("postgres").await?;
+    db::execute_sql(&admin, &format!("DROP ROLE IF EXISTS {};", role)).await?;
+    Ok(())
+}
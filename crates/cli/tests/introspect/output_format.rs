@@ -0,0 +1,73 @@
+//! Output format introspection tests
+//!
+//! Tests for statement separators and line endings in generated SQL files.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+/// Every statement is followed by one blank line except the last, which ends
+/// the file with a single newline
+fn assert_consistent_layout(file: &str, content: &str) {
+    assert!(content.ends_with(";\n"), "{} should end with one newline", file);
+    assert!(!content.ends_with("\n\n"), "{} should end with one newline", file);
+    assert!(!content.contains("\n\n\n"), "{} has extra blank lines", file);
+}
+
+#[tokio::test]
+async fn test_introspect_output_separators() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(&pool, "CREATE TABLE accounts (id integer PRIMARY KEY, name text);").await?;
+    db::execute_sql(&pool, "COMMENT ON TABLE accounts IS 'Customer accounts';").await?;
+    db::execute_sql(&pool, "COMMENT ON COLUMN accounts.name IS 'Display name';").await?;
+    db::execute_sql(&pool, "GRANT SELECT ON accounts TO PUBLIC;").await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert_consistent_layout("schema.sql", &schema_content);
+    // Comments and grants are separated like every other statement
+    assert!(schema_content.contains(
+        "COMMENT ON TABLE public.accounts IS 'Customer accounts';\n\nCOMMENT ON COLUMN public.accounts.name IS 'Display name';\n\n"
+    ));
+    assert!(schema_content.contains(";\n\nGRANT SELECT ON"));
+
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "split",
+            "--split",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    for entry in std::fs::read_dir(env.temp_path().join("split"))? {
+        let path = entry?.path();
+        let content = std::fs::read_to_string(&path)?;
+        let file = path.file_name().unwrap().to_string_lossy().to_string();
+        if file == "schema.sql" {
+            // The entry point holds \ir lines rather than statements
+            assert!(content.ends_with(".sql\n") && !content.ends_with("\n\n"));
+        } else {
+            assert_consistent_layout(&file, &content);
+        }
+    }
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
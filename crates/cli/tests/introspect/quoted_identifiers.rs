@@ -0,0 +1,115 @@
+//! Quoted identifier introspection tests
+//!
+//! Tests that reserved words and mixed-case names are quoted in the output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_quotes_reserved_and_mixed_case_names() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        r#"CREATE TABLE "Orders" (id integer PRIMARY KEY, "order" integer, "select" text);"#,
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        r#"CREATE TABLE shipments (id integer, order_id integer CONSTRAINT "Shipments_Order" REFERENCES "Orders" (id));"#,
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains(r#"CREATE TABLE public."Orders""#));
+    assert!(schema_content.contains(r#""order" integer"#));
+    assert!(schema_content.contains(r#""select" text"#));
+    assert!(schema_content.contains(r#"ADD CONSTRAINT "Shipments_Order""#));
+
+    // The table statements apply to an empty database as written
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE TABLE") || statement.starts_with("ALTER TABLE") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let columns: Vec<(String,)> = sqlx::query_as(
+        "SELECT column_name::text FROM information_schema.columns \
+         WHERE table_name = 'Orders' ORDER BY ordinal_position",
+    )
+    .fetch_all(&replay_pool)
+    .await?;
+    let columns: Vec<&str> = columns.iter().map(|(name,)| name.as_str()).collect();
+    assert_eq!(columns, ["id", "order", "select"]);
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_quotes_mixed_case_role_settings() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // Roles are cluster-wide, so use one unique to this test database
+    let role = format!("Reporter_{}", &env.db_name[env.db_name.len() - 16..]);
+    sqlx::raw_sql(&format!(
+        "CREATE ROLE \"{role}\"; ALTER ROLE \"{role}\" SET statement_timeout TO '5s';"
+    ))
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    let create = format!("CREATE ROLE \"{}\"", role);
+    let setting = format!("ALTER ROLE \"{}\" SET statement_timeout TO '5s'", role);
+    assert!(schema_content.contains(&create));
+    assert!(schema_content.contains(&setting));
+
+    // The role statements recreate the role with its setting once it is gone
+    db::execute_sql(&pool, &format!("DROP ROLE \"{}\";", role)).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with(&create) || statement.starts_with(&setting) {
+            sqlx::raw_sql(statement).execute(&pool).await?;
+        }
+    }
+    let (settings,): (Vec<String>,) = sqlx::query_as(
+        "SELECT s.setconfig FROM pg_db_role_setting s \
+         JOIN pg_roles r ON r.oid = s.setrole WHERE r.rolname = $1 AND s.setdatabase = 0",
+    )
+    .bind(&role)
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(settings, ["statement_timeout=5s"]);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    let admin = db::get_test_pool("postgres").await?;
+    db::execute_sql(&admin, &format!("DROP ROLE IF EXISTS \"{}\";", role)).await?;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Sequence ownership introspection tests
+//!
+//! Tests that a sequence keeps its data type and owning column when the
+//! introspect output is replayed.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_owned_integer_sequence_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(&pool, "CREATE TABLE orders (id integer NOT NULL)").await?;
+    db::execute_sql(
+        &pool,
+        "CREATE SEQUENCE order_seq AS integer START 10 OWNED BY orders.id",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("CREATE SEQUENCE public.order_seq AS integer START WITH 10"));
+    assert!(schema_content.contains("ALTER SEQUENCE public.order_seq OWNED BY public.orders.id"));
+
+    // Replaying the sequence and table restores the type and the ownership
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE SEQUENCE") || statement.starts_with("CREATE TABLE") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let (data_type, owner): (String, Option<String>) = sqlx::query_as(
+        "SELECT format_type(seqtypid, NULL), pg_get_serial_sequence('orders', 'id') \
+         FROM pg_sequence WHERE seqrelid = 'order_seq'::regclass",
+    )
+    .fetch_one(&replay_pool)
+    .await?;
+    assert_eq!(data_type, "integer");
+    assert_eq!(owner.as_deref(), Some("public.order_seq"));
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
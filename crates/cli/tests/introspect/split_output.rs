@@ -0,0 +1,130 @@
+//! Split output introspection tests
+//!
+//! Tests for writing one file per object category with --split.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_split_writes_category_files() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE accounts (id integer PRIMARY KEY, email text);",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE VIEW account_emails AS SELECT email FROM accounts;",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "schema",
+            "--split",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let read = |name: &str| std::fs::read_to_string(env.temp_path().join("schema").join(name));
+    let tables = read("tables.sql")?;
+    let views = read("views.sql")?;
+    assert!(tables.contains("CREATE TABLE") && tables.contains("accounts"));
+    assert!(!tables.contains("CREATE VIEW"));
+    assert!(views.contains("CREATE VIEW") && views.contains("account_emails"));
+
+    // The entry point includes the category files, tables before the views using them
+    let entry_point = read("schema.sql")?;
+    debug!("schema.sql: \n{}", entry_point);
+    assert!(!entry_point.contains("CREATE"));
+    let tables_at = entry_point
+        .find("\\ir tables.sql")
+        .expect("tables.sql included");
+    let views_at = entry_point
+        .find("\\ir views.sql")
+        .expect("views.sql included");
+    assert!(tables_at < views_at);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_split_replays_in_dependency_order() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // One function is called from a table check, the other returns the table's rows
+    sqlx::raw_sql(
+        "CREATE FUNCTION is_positive(value integer) RETURNS boolean
+             LANGUAGE sql IMMUTABLE AS $$ SELECT value > 0 $$;
+         CREATE TABLE accounts (id integer PRIMARY KEY CHECK (is_positive(id)), email text);
+         CREATE FUNCTION active_accounts() RETURNS SETOF accounts
+             LANGUAGE sql AS $$ SELECT * FROM accounts $$;",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "schema",
+            "--split",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // The check function comes before the tables, the set-returning one after
+    let schema_dir = env.temp_path().join("schema");
+    let entry_point = std::fs::read_to_string(schema_dir.join("schema.sql"))?;
+    debug!("schema.sql: \n{}", entry_point);
+    let includes: Vec<&str> = entry_point
+        .lines()
+        .filter_map(|line| line.strip_prefix("\\ir "))
+        .collect();
+    let position = |file: &str| includes.iter().position(|include| *include == file);
+    assert!(position("functions.sql") < position("tables.sql"));
+    assert!(position("tables.sql") < position("functions_2.sql"));
+    let functions = std::fs::read_to_string(schema_dir.join("functions.sql"))?;
+    assert!(functions.contains("is_positive") && !functions.contains("active_accounts"));
+
+    // Replaying the includes in order rebuilds the schema in an empty database;
+    // the roles belong to the cluster the replay shares, so they already exist
+    let mut replay = String::new();
+    for include in includes.iter().filter(|include| !include.starts_with("roles")) {
+        replay.push_str(&std::fs::read_to_string(schema_dir.join(include))?);
+    }
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    sqlx::raw_sql(&replay).execute(&replay_pool).await?;
+    let (accounts,): (i64,) = sqlx::query_as("SELECT count(*) FROM active_accounts()")
+        .fetch_one(&replay_pool)
+        .await?;
+    assert_eq!(accounts, 0);
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
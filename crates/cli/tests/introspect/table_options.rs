@@ -0,0 +1,128 @@
+//! Table option introspection tests
+//!
+//! Tests that partitioning, inheritance and storage parameters are kept in the
+//! CREATE TABLE output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+/// Apply the CREATE TABLE statements of `schema_content` to a fresh database
+async fn replay_tables(schema_content: &str) -> Result<(String, sqlx::PgPool)> {
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if statement.starts_with("CREATE TABLE") {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    Ok((replay_db, replay_pool))
+}
+
+#[tokio::test]
+async fn test_introspect_partitioned_table_options() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // A partitioned table with no partitions is still partitioned
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE events (id integer, created_at date) PARTITION BY RANGE (created_at);",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE measurements (id integer, region text) PARTITION BY LIST (region);",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE measurements_eu PARTITION OF measurements FOR VALUES IN ('eu') WITH (fillfactor = 70);",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains("PARTITION BY RANGE (created_at)"));
+    assert!(schema_content.contains("PARTITION BY LIST (region)"));
+    assert!(schema_content.contains("FOR VALUES IN ('eu') WITH (fillfactor = 70)"));
+
+    let (replay_db, replay_pool) = replay_tables(&schema_content).await?;
+    let tables: Vec<(String, String, Option<Vec<String>>)> = sqlx::query_as(
+        "SELECT relname::text, relkind::text, reloptions::text[] FROM pg_class \
+         WHERE relname IN ('events', 'measurements', 'measurements_eu') ORDER BY relname",
+    )
+    .fetch_all(&replay_pool)
+    .await?;
+    assert_eq!(
+        tables,
+        vec![
+            ("events".to_string(), "p".to_string(), None),
+            ("measurements".to_string(), "p".to_string(), None),
+            (
+                "measurements_eu".to_string(),
+                "r".to_string(),
+                Some(vec!["fillfactor=70".to_string()])
+            ),
+        ]
+    );
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_inheriting_table_options() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(&pool, "CREATE TABLE vehicles (id integer, make text);").await?;
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE cars (num_doors integer) INHERITS (vehicles) WITH (fillfactor = 80, autovacuum_enabled = false);",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content
+        .contains("INHERITS (vehicles) WITH (autovacuum_enabled = false, fillfactor = 80)"));
+
+    let (replay_db, replay_pool) = replay_tables(&schema_content).await?;
+    let (parent, options): (String, Vec<String>) = sqlx::query_as(
+        "SELECT p.relname::text, c.reloptions::text[] FROM pg_inherits i \
+         JOIN pg_class c ON c.oid = i.inhrelid JOIN pg_class p ON p.oid = i.inhparent \
+         WHERE c.relname = 'cars'",
+    )
+    .fetch_one(&replay_pool)
+    .await?;
+    assert_eq!(parent, "vehicles");
+    assert_eq!(options, ["autovacuum_enabled=false", "fillfactor=80"]);
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
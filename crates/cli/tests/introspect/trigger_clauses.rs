@@ -0,0 +1,137 @@
+//! Trigger clause introspection tests
+//!
+//! Tests that the trigger level, WHEN condition and schema-qualified table
+//! survive a round trip through the introspect output.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_statement_and_conditional_triggers_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    sqlx::raw_sql(
+        "CREATE TABLE accounts (id integer NOT NULL, balance numeric);
+         CREATE FUNCTION log_change() RETURNS trigger LANGUAGE plpgsql AS $$
+         BEGIN
+             RETURN NULL;
+         END;
+         $$;
+         CREATE TRIGGER accounts_audit AFTER INSERT ON accounts
+             FOR EACH STATEMENT EXECUTE FUNCTION log_change();
+         CREATE TRIGGER accounts_balance AFTER UPDATE ON accounts
+             FOR EACH ROW WHEN (OLD.balance IS DISTINCT FROM NEW.balance)
+             EXECUTE FUNCTION log_change();",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert!(schema_content.contains(
+        "CREATE TRIGGER accounts_audit AFTER INSERT ON public.accounts FOR EACH STATEMENT \
+         EXECUTE FUNCTION log_change()"
+    ));
+    assert!(schema_content.contains(
+        "CREATE TRIGGER accounts_balance AFTER UPDATE ON public.accounts FOR EACH ROW WHEN ("
+    ));
+    assert!(schema_content.contains("IS DISTINCT FROM"));
+
+    // Replaying the table, function and triggers restores both clauses
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+    for statement in schema_content.split(";\n\n") {
+        if ["CREATE TABLE", "CREATE FUNCTION", "CREATE TRIGGER"]
+            .iter()
+            .any(|prefix| statement.starts_with(prefix))
+        {
+            sqlx::raw_sql(statement).execute(&replay_pool).await?;
+        }
+    }
+    let triggers: Vec<(String, String)> = sqlx::query_as(
+        "SELECT tgname::text, pg_get_triggerdef(oid) FROM pg_trigger \
+         WHERE tgrelid = 'accounts'::regclass ORDER BY tgname",
+    )
+    .fetch_all(&replay_pool)
+    .await?;
+    assert_eq!(triggers.len(), 2);
+    assert!(triggers[0].1.contains("FOR EACH STATEMENT"));
+    assert!(triggers[1].1.contains(
+        "FOR EACH ROW WHEN ((old.balance IS DISTINCT FROM new.balance))"
+    ));
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_same_trigger_policy_and_rule_names_on_two_tables() -> Result<()> {
+    use cli::commands::introspect::SqlSerializer;
+    use shem_core::traits::SchemaSerializer;
+
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // Triggers, policies and rules are named per table, so both copies must survive
+    sqlx::raw_sql(
+        "CREATE TABLE projects (id integer NOT NULL, tenant_id integer);
+         CREATE TABLE invoices (id integer NOT NULL, tenant_id integer);
+         CREATE FUNCTION audit_change() RETURNS trigger LANGUAGE plpgsql AS $$
+         BEGIN
+             RETURN NEW;
+         END;
+         $$;
+         CREATE TRIGGER audit AFTER INSERT ON projects
+             FOR EACH ROW EXECUTE FUNCTION audit_change();
+         CREATE TRIGGER audit AFTER INSERT ON invoices
+             FOR EACH ROW EXECUTE FUNCTION audit_change();
+         CREATE POLICY tenant_isolation ON projects USING (tenant_id = 1);
+         CREATE POLICY tenant_isolation ON invoices USING (tenant_id = 2);
+         CREATE RULE protect AS ON DELETE TO projects DO INSTEAD NOTHING;
+         CREATE RULE protect AS ON DELETE TO invoices DO INSTEAD NOTHING;",
+    )
+    .execute(&pool)
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    assert_eq!(schema_content.matches("CREATE TRIGGER audit ").count(), 2);
+    assert!(schema_content.contains("ON public.projects FOR EACH ROW"));
+    assert!(schema_content.contains("ON public.invoices FOR EACH ROW"));
+    assert_eq!(schema_content.matches("CREATE POLICY tenant_isolation ").count(), 2);
+    assert_eq!(schema_content.matches("CREATE RULE protect ").count(), 2);
+
+    // Reading the output back keeps one trigger and one policy per table
+    let schema = SqlSerializer::default().deserialize(&schema_content).await?;
+    assert!(schema.triggers.contains_key("projects.audit"));
+    assert!(schema.triggers.contains_key("invoices.audit"));
+    assert!(schema.policies.contains_key("projects.tenant_isolation"));
+    assert!(schema.policies.contains_key("invoices.tenant_isolation"));
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
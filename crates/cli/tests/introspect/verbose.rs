@@ -0,0 +1,36 @@
+//! Verbose flag tests
+//!
+//! Tests that `--verbose` is accepted before or after the subcommand with the
+//! same effect.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_verbose_flag_position_does_not_matter() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+    db::execute_sql(&pool, "CREATE TABLE users (id integer PRIMARY KEY);").await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let run = |args: &[&str]| -> Result<String> {
+        let output = run_shem_command_in_dir(args, &env.temp_path())?;
+        assert_command_success(&output);
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let before = run(&["-v", "introspect", "--database-url", &db_url, "--output", "before"])?;
+    let after = run(&["introspect", "-v", "--database-url", &db_url, "--output", "after"])?;
+    let quiet = run(&["introspect", "--database-url", &db_url, "--output", "quiet"])?;
+
+    for logs in [&before, &after] {
+        assert!(logs.contains("DEBUG"), "Expected debug logs in:\n{}", logs);
+        assert!(logs.contains("Introspected 1 tables"));
+    }
+    assert!(!quiet.contains("DEBUG"), "Unexpected debug logs in:\n{}", quiet);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
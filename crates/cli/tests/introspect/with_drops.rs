@@ -0,0 +1,78 @@
+//! Drop prelude introspection tests
+//!
+//! Tests for prefixing the introspected schema with DROP ... IF EXISTS statements.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use tracing::debug;
+
+#[tokio::test]
+async fn test_introspect_with_drops() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    db::execute_sql(&pool, "CREATE TABLE authors (id integer PRIMARY KEY, name text);").await?;
+    db::execute_sql(
+        &pool,
+        "CREATE TABLE books (id integer PRIMARY KEY, author_id integer CONSTRAINT books_author_fk REFERENCES authors (id));",
+    )
+    .await?;
+    db::execute_sql(
+        &pool,
+        "CREATE VIEW book_authors AS SELECT b.id, a.name FROM books b JOIN authors a ON a.id = b.author_id;",
+    )
+    .await?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "introspect",
+            "--database-url",
+            &db_url,
+            "--output",
+            "schema",
+            "--with-drops",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+
+    let drop_view = schema_content
+        .find("DROP VIEW IF EXISTS \"book_authors\" CASCADE;")
+        .unwrap();
+    let drop_fk = schema_content
+        .find("DROP CONSTRAINT IF EXISTS \"books_author_fk\";")
+        .unwrap();
+    let drop_books = schema_content
+        .find("DROP TABLE IF EXISTS \"books\" CASCADE;")
+        .unwrap();
+    let drop_authors = schema_content
+        .find("DROP TABLE IF EXISTS \"authors\" CASCADE;")
+        .unwrap();
+    let first_create = schema_content.find("CREATE ").unwrap();
+
+    // Dependents are dropped before what they depend on, all before anything is created
+    assert!(drop_view < drop_books && drop_view < drop_authors);
+    assert!(drop_fk < drop_authors);
+    assert!(drop_books.max(drop_authors) < first_create);
+    // The public schema ships with every database and is kept
+    assert!(!schema_content.contains("DROP SCHEMA IF EXISTS \"public\""));
+
+    // Without the flag nothing is dropped
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &db_url, "--output", "plain"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let plain_content = std::fs::read_to_string(env.temp_path().join("plain/schema.sql"))?;
+    assert!(!plain_content.contains("DROP "));
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! Enum value migration tests
+//!
+//! Tests for applying migrations that add enum values and use them.
+
+use super::write_migration;
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_migrate_uses_enum_value_added_in_same_migration() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(
+        &migrations,
+        "20240101000000_status",
+        "CREATE TYPE status AS ENUM ('draft', 'published');",
+    )?;
+    write_migration(
+        &migrations,
+        "20240102000000_archived",
+        "ALTER TYPE status ADD VALUE IF NOT EXISTS 'archived';\n\
+         CREATE TABLE posts (state status NOT NULL DEFAULT 'archived');\n\
+         INSERT INTO posts DEFAULT VALUES;",
+    )?;
+
+    // PostgreSQL rejects the new value until the statement adding it is committed
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let (state,): (String,) = sqlx::query_as("SELECT state::text FROM posts")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(state, "archived");
+    let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM schema_migrations")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 2);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Layered migration tests
+//!
+//! Tests for applying migrations spread over several directories.
+
+use super::write_migration;
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_migrate_merges_directories_in_timestamp_order() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    // Each migration depends on the previous one, whichever directory it lives in
+    let framework = env.temp_path().join("framework");
+    let app = env.temp_path().join("app");
+    write_migration(
+        &framework,
+        "20240101000000_users",
+        "CREATE TABLE users (id integer PRIMARY KEY);",
+    )?;
+    write_migration(
+        &app,
+        "20240102000000_posts",
+        "CREATE TABLE posts (id integer PRIMARY KEY, user_id integer REFERENCES users (id));",
+    )?;
+    write_migration(
+        &framework,
+        "20240103000000_audit",
+        "CREATE TABLE audit (post_id integer REFERENCES posts (id));",
+    )?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "migrate",
+            "--migrations",
+            "framework,app",
+            "--database-url",
+            &db_url,
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let applied: Vec<(String,)> = sqlx::query_as("SELECT name FROM schema_migrations ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+    assert_eq!(
+        applied,
+        vec![
+            ("20240101000000_users".to_string(),),
+            ("20240102000000_posts".to_string(),),
+            ("20240103000000_audit".to_string(),),
+        ]
+    );
+
+    // The same version in two directories is ambiguous
+    write_migration(
+        &app,
+        "20240103000000_comments",
+        "CREATE TABLE comments (id integer);",
+    )?;
+    let output = run_shem_command_in_dir(
+        &[
+            "migrate",
+            "--migrations",
+            "framework",
+            "--migrations",
+            "app",
+            "--database-url",
+            &db_url,
+        ],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    // Errors are logged, and the log goes to stdout
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .contains("Migration version 20240103000000 exists in both")
+    );
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
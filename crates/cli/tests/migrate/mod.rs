@@ -0,0 +1,23 @@
+//! Migrate command tests
+//! 
+//! Tests for the migrate command functionality.
+
+pub mod enum_values;
+pub mod layered;
+pub mod progress;
+pub mod repair;
+pub mod rollback;
+pub mod status;
+
+use anyhow::Result;
+use std::path::Path;
+
+/// Write `sql` as the up section of migration `name` in `dir`
+pub fn write_migration(dir: &Path, name: &str, sql: &str) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(
+        dir.join(format!("{}.sql", name)),
+        format!("-- +up\n{}\n", sql),
+    )?;
+    Ok(())
+}
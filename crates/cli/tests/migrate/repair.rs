@@ -0,0 +1,192 @@
+//! Migration repair tests
+//!
+//! Tests for re-syncing the migrations table with the migration files.
+
+use super::write_migration;
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use sha2::{Digest, Sha256};
+
+#[tokio::test]
+async fn test_repair_removes_stale_tracking_row() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(&migrations, "20240101000000_users", "CREATE TABLE users (id integer);")?;
+    write_migration(&migrations, "20240102000000_posts", "CREATE TABLE posts (id integer);")?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    std::fs::remove_file(migrations.join("20240102000000_posts.sql"))?;
+
+    // Without a terminal to confirm on, nothing is removed
+    let output = run_shem_command_in_dir(
+        &["repair", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM schema_migrations")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 2);
+
+    let output = run_shem_command_in_dir(
+        &["repair", "--database-url", &db_url, "--yes"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("removed  20240102000000_posts"));
+
+    let names: Vec<(String,)> = sqlx::query_as("SELECT name FROM schema_migrations ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+    assert_eq!(names, vec![("20240101000000_users".to_string(),)]);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_updates_checksum_and_marks_applied() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(&migrations, "20240101000000_users", "CREATE TABLE users (id integer);")?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // Edit the applied migration and add one whose change was made by hand
+    write_migration(
+        &migrations,
+        "20240101000000_users",
+        "CREATE TABLE users (id integer PRIMARY KEY);",
+    )?;
+    write_migration(&migrations, "20240102000000_posts", "CREATE TABLE posts (id integer);")?;
+    db::execute_sql(&pool, "CREATE TABLE posts (id integer)").await?;
+
+    let output = run_shem_command_in_dir(
+        &[
+            "repair",
+            "--database-url",
+            &db_url,
+            "--mark-applied",
+            "20240102000000_posts",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("updated  20240101000000_users"));
+    assert!(stdout.contains("marked   20240102000000_posts"));
+
+    let rows: Vec<(String, Option<String>)> =
+        sqlx::query_as("SELECT name, checksum FROM schema_migrations ORDER BY id")
+            .fetch_all(&pool)
+            .await?;
+    let checksum = |name: &str| -> Result<String> {
+        let content = std::fs::read_to_string(migrations.join(format!("{}.sql", name)))?;
+        Ok(format!("{:x}", Sha256::digest(content.as_bytes())))
+    };
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "20240101000000_users".to_string(),
+                Some(checksum("20240101000000_users")?)
+            ),
+            (
+                "20240102000000_posts".to_string(),
+                Some(checksum("20240102000000_posts")?)
+            ),
+        ]
+    );
+
+    // A second run finds nothing to do, and migrate skips the marked migration
+    let output = run_shem_command_in_dir(
+        &["repair", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("updated"));
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_refuses_to_mark_migration_missing_from_database() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(
+        &migrations,
+        "20240101000000_posts",
+        "CREATE TABLE posts (id integer, title text);",
+    )?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let mark_applied = || {
+        run_shem_command_in_dir(
+            &[
+                "repair",
+                "--database-url",
+                &db_url,
+                "--mark-applied",
+                "20240101000000_posts",
+            ],
+            &env.temp_path(),
+        )
+    };
+
+    // Neither a missing table nor a missing column is taken as applied; errors
+    // are logged to stdout
+    let output = mark_applied()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("table posts"));
+
+    db::execute_sql(&pool, "CREATE TABLE posts (id integer)").await?;
+    let output = mark_applied()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("column posts.title"));
+
+    let (count,): (i64,) = sqlx::query_as("SELECT count(*) FROM schema_migrations")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 0);
+
+    db::execute_sql(&pool, "ALTER TABLE posts ADD COLUMN title text").await?;
+    let output = mark_applied()?;
+    assert_command_success(&output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("marked   20240101000000_posts"));
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
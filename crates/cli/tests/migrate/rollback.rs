@@ -0,0 +1,74 @@
+//! Migrate rollback tests
+//!
+//! Tests for rolling back applied migrations with `migrate down`.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_migrate_down_reverts_last_migrations() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations_dir = env.temp_path().join("migrations");
+    std::fs::create_dir_all(&migrations_dir)?;
+    for (name, table) in [
+        ("001_users", "users"),
+        ("002_posts", "posts"),
+        ("003_tags", "tags"),
+    ] {
+        std::fs::write(
+            migrations_dir.join(format!("{}.sql", name)),
+            format!(
+                "-- +up\nCREATE TABLE {table} (id integer PRIMARY KEY);\n-- +down\nDROP TABLE {table};\n"
+            ),
+        )?;
+    }
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &[
+            "migrate",
+            "--migrations",
+            "migrations",
+            "--database-url",
+            &db_url,
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let output = run_shem_command_in_dir(
+        &[
+            "migrate",
+            "down",
+            "--steps",
+            "2",
+            "--migrations",
+            "migrations",
+            "--database-url",
+            &db_url,
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let tables: Vec<(String,)> = sqlx::query_as(
+        "SELECT table_name::text FROM information_schema.tables \
+         WHERE table_schema = 'public' AND table_name IN ('users', 'posts', 'tags')",
+    )
+    .fetch_all(&pool)
+    .await?;
+    assert_eq!(tables, vec![("users".to_string(),)]);
+
+    let applied: Vec<(String,)> = sqlx::query_as("SELECT name FROM schema_migrations ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+    assert_eq!(applied, vec![("001_users".to_string(),)]);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Migration status tests
+//!
+//! Tests for listing applied and pending migrations.
+
+use super::write_migration;
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+
+#[tokio::test]
+async fn test_status_since_version() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let _pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(&migrations, "20240101000000_users", "CREATE TABLE users (id integer);")?;
+    write_migration(&migrations, "20240102000000_posts", "CREATE TABLE posts (id integer);")?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    write_migration(&migrations, "20240103000000_tags", "CREATE TABLE tags (id integer);")?;
+
+    let output = run_shem_command_in_dir(
+        &[
+            "migrate",
+            "--database-url",
+            &db_url,
+            "status",
+            "--since",
+            "20240102000000",
+        ],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let listed: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("applied") || line.starts_with("pending"))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            "applied  20240102000000_posts",
+            "pending  20240103000000_tags",
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_since_compares_versions_as_numbers() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let _pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(&migrations, "9_users", "CREATE TABLE users (id integer);")?;
+    write_migration(&migrations, "10_posts", "CREATE TABLE posts (id integer);")?;
+    write_migration(&migrations, "100_tags", "CREATE TABLE tags (id integer);")?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url, "status", "--since", "10"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // As text "9_users" would sort after "10"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pending  10_posts"));
+    assert!(stdout.contains("pending  100_tags"));
+    assert!(!stdout.contains("9_users"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_and_migrate_report_edited_migrations() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    let pool = db::setup_test_db(&env.db_name).await?;
+
+    let migrations = env.temp_path().join("migrations");
+    write_migration(&migrations, "20240101000000_users", "CREATE TABLE users (id integer);")?;
+
+    let db_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // The applied migration is edited and a new one is added
+    write_migration(
+        &migrations,
+        "20240101000000_users",
+        "CREATE TABLE users (id integer, name text);",
+    )?;
+    write_migration(&migrations, "20240102000000_posts", "CREATE TABLE posts (id integer);")?;
+
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url, "status"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("changed  20240101000000_users"));
+    assert!(stdout.contains("pending  20240102000000_posts"));
+
+    // Nothing is applied on top of an edited migration until repair accepts it;
+    // errors are logged to stdout
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("20240101000000_users"));
+    let (posts,): (i64,) = sqlx::query_as(
+        "SELECT count(*) FROM information_schema.tables WHERE table_name = 'posts'",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(posts, 0);
+
+    let output = run_shem_command_in_dir(
+        &["repair", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+    let output = run_shem_command_in_dir(
+        &["migrate", "--database-url", &db_url],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    // Clean up
+    pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Round trip of comments on objects other than tables and columns
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_comments_on_other_objects_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE TABLE orders (id integer PRIMARY KEY, total numeric);
+        CREATE FUNCTION touch_order() RETURNS trigger LANGUAGE plpgsql AS $$
+        BEGIN
+            RETURN NEW;
+        END;
+        $$;
+        CREATE TRIGGER orders_touch BEFORE UPDATE ON orders
+            FOR EACH ROW EXECUTE FUNCTION touch_order();
+        CREATE PROCEDURE archive_orders(cutoff integer) LANGUAGE sql AS $$ DELETE FROM orders WHERE id < cutoff $$;
+        CREATE MATERIALIZED VIEW order_summary AS SELECT count(*) AS order_count FROM orders;
+        CREATE COLLATION byte_order FROM "C";
+        COMMENT ON TRIGGER orders_touch ON orders IS 'Keeps orders fresh';
+        COMMENT ON PROCEDURE archive_orders(integer) IS 'Moves old orders away';
+        COMMENT ON MATERIALIZED VIEW order_summary IS 'Order statistics';
+        COMMENT ON COLLATION byte_order IS 'Byte-wise ordering';
+        "#,
+    )
+    .await
+}
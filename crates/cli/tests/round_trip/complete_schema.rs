@@ -0,0 +1,11 @@
+//! Round trip of the complete schema fixture
+
+use super::assert_round_trip;
+use crate::fixtures::sql;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_complete_schema_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(sql::COMPLETE_SCHEMA).await
+}
@@ -0,0 +1,23 @@
+//! Round trip of exclusion constraints using non-default operator classes
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_exclusion_constraint_with_opclass_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE TABLE subnets (
+            id integer PRIMARY KEY,
+            network inet NOT NULL,
+            active_during tstzrange NOT NULL,
+            CONSTRAINT subnets_no_overlap EXCLUDE USING gist (
+                network inet_ops WITH &&,
+                active_during WITH &&
+            ) WHERE (id > 0)
+        );
+        "#,
+    )
+    .await
+}
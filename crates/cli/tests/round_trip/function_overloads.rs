@@ -0,0 +1,20 @@
+//! Round trip of overloaded functions that carry their own comments
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_commented_function_overloads_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE FUNCTION describe(value integer) RETURNS text
+            LANGUAGE sql IMMUTABLE AS $$ SELECT 'number ' || value $$;
+        CREATE FUNCTION describe(value text, prefix text) RETURNS text
+            LANGUAGE sql IMMUTABLE AS $$ SELECT prefix || value $$;
+        COMMENT ON FUNCTION describe(integer) IS 'Describes a number';
+        COMMENT ON FUNCTION describe(text, text) IS 'Describes prefixed text';
+        "#,
+    )
+    .await
+}
@@ -0,0 +1,118 @@
+//! Round-trip tests
+//!
+//! Introspect a database, apply the generated SQL to a fresh database,
+//! introspect that one too and require both schemas to be equal.
+
+pub mod comments;
+pub mod complete_schema;
+pub mod exclusion_constraints;
+pub mod function_overloads;
+pub mod quoted_names;
+pub mod typed_tables;
+pub mod zero_columns;
+
+use anyhow::{Context, Result};
+use cli::{TestEnv, assert_command_success, db, run_shem_command_in_dir};
+use postgres::PostgresDriver;
+use serde_json::Value;
+use shem_core::{DatabaseDriver, Schema};
+use tracing::debug;
+
+/// Create `setup_sql` in one database, replay `shem introspect`'s output in
+/// another and fail with every field that differs between the two schemas
+pub async fn assert_round_trip(setup_sql: &str) -> Result<()> {
+    let env = TestEnv::new()?;
+    let source_pool = db::setup_test_db(&env.db_name).await?;
+    let replay_db = db::generate_unique_db_name();
+    let replay_pool = db::setup_test_db(&replay_db).await?;
+
+    // Function bodies hold semicolons, so scripts are sent in one go
+    sqlx::raw_sql(setup_sql).execute(&source_pool).await?;
+
+    let source_url = db::get_database_url(&env.db_name);
+    let output = run_shem_command_in_dir(
+        &["introspect", "--database-url", &source_url, "--output", "schema"],
+        &env.temp_path(),
+    )?;
+    assert_command_success(&output);
+
+    let schema_content = std::fs::read_to_string(env.temp_path().join("schema/schema.sql"))?;
+    debug!("schema_content: \n{}", schema_content);
+    // Roles are shared by the whole cluster and already exist
+    let replay_sql = schema_content
+        .split(";\n\n")
+        .filter(|statement| !statement.trim_start().starts_with("CREATE ROLE"))
+        .collect::<Vec<_>>()
+        .join(";\n\n");
+    sqlx::raw_sql(&replay_sql)
+        .execute(&replay_pool)
+        .await
+        .context("Replaying the introspected schema failed")?;
+
+    let source = introspect(&source_url).await?;
+    let replayed = introspect(&db::get_database_url(&replay_db)).await?;
+    let mut differences = Vec::new();
+    collect_differences("schema", &source, &replayed, &mut differences);
+    assert!(
+        differences.is_empty(),
+        "Schema changed after a round trip:\n{}",
+        differences.join("\n")
+    );
+
+    // Clean up
+    replay_pool.close().await;
+    db::drop_test_db(&replay_db).await?;
+    source_pool.close().await;
+    db::drop_test_db(&env.db_name).await?;
+    Ok(())
+}
+
+/// Normalized schema of the database as JSON; roles and tablespaces belong to
+/// the cluster, which other tests change concurrently, so they are left out
+async fn introspect(url: &str) -> Result<Value> {
+    let schema: Schema = PostgresDriver::new().connect(url).await?.introspect().await?;
+    let mut schema = schema.normalize();
+    schema.roles.clear();
+    schema.tablespaces.clear();
+    Ok(serde_json::to_value(&schema)?)
+}
+
+/// Walk both values side by side, recording `path: expected != actual` for
+/// every leaf that differs and every key or element only one side has
+fn collect_differences(path: &str, expected: &Value, actual: &Value, out: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = format!("{}.{}", path, key);
+                match (expected.get(key), actual.get(key)) {
+                    (Some(expected), Some(actual)) => {
+                        collect_differences(&path, expected, actual, out)
+                    }
+                    (Some(expected), None) => out.push(format!("{}: {} != <missing>", path, expected)),
+                    (None, Some(actual)) => out.push(format!("{}: <missing> != {}", path, actual)),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for index in 0..expected.len().max(actual.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (expected.get(index), actual.get(index)) {
+                    (Some(expected), Some(actual)) => {
+                        collect_differences(&path, expected, actual, out)
+                    }
+                    (Some(expected), None) => out.push(format!("{}: {} != <missing>", path, expected)),
+                    (None, Some(actual)) => out.push(format!("{}: <missing> != {}", path, actual)),
+                    (None, None) => {}
+                }
+            }
+        }
+        (expected, actual) if expected != actual => {
+            out.push(format!("{}: {} != {}", path, expected, actual))
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,26 @@
+//! Round trip of comments and grants on objects whose names need quoting
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_comments_and_grants_on_quoted_names_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE SCHEMA "Billing";
+        CREATE TYPE "Billing"."Status" AS ENUM ('open', 'paid');
+        CREATE TABLE "Billing"."Invoices" ("Total" numeric, "order" integer);
+        CREATE VIEW "Open Invoices" AS SELECT "Total" FROM "Billing"."Invoices";
+        COMMENT ON TABLE "Billing"."Invoices" IS 'Issued invoices';
+        COMMENT ON COLUMN "Billing"."Invoices"."Total" IS 'Amount due';
+        COMMENT ON COLUMN "Billing"."Invoices"."order" IS 'Order number';
+        COMMENT ON TYPE "Billing"."Status" IS 'Payment state';
+        COMMENT ON VIEW "Open Invoices" IS 'Unpaid invoices';
+        COMMENT ON COLUMN "Open Invoices"."Total" IS 'Amount still due';
+        GRANT SELECT ON "Billing"."Invoices" TO PUBLIC;
+        GRANT UPDATE ("Total") ON "Billing"."Invoices" TO PUBLIC;
+        "#,
+    )
+    .await
+}
@@ -0,0 +1,20 @@
+//! Round trip of a table created from a composite type
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_typed_table_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE TYPE person_type AS (id integer, name text, nickname text);
+        CREATE TABLE people OF person_type (
+            id WITH OPTIONS PRIMARY KEY,
+            name WITH OPTIONS NOT NULL DEFAULT 'anonymous'
+        );
+        CREATE TABLE visitors OF person_type;
+        "#,
+    )
+    .await
+}
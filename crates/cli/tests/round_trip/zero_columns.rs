@@ -0,0 +1,17 @@
+//! Round trip of a table whose columns were all dropped
+
+use super::assert_round_trip;
+use anyhow::Result;
+
+#[tokio::test]
+async fn test_table_without_columns_round_trip() -> Result<()> {
+    env_logger::try_init().ok();
+    assert_round_trip(
+        r#"
+        CREATE TABLE hollow (a integer, b text);
+        ALTER TABLE hollow DROP COLUMN a, DROP COLUMN b;
+        COMMENT ON TABLE hollow IS 'Only system columns are left';
+        "#,
+    )
+    .await
+}
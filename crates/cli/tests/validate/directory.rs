@@ -0,0 +1,48 @@
+//! Directory validation tests
+//!
+//! Tests for reporting errors from every file in a schema directory.
+
+use anyhow::Result;
+use cli::{TestEnv, assert_command_success, run_shem_command_in_dir};
+
+#[test]
+fn test_validate_directory_reports_every_file() -> Result<()> {
+    env_logger::try_init().ok();
+    let env = TestEnv::new()?;
+    std::fs::create_dir_all(env.temp_path().join("schema"))?;
+    env.create_test_file("schema/00_valid.sql", "CREATE TABLE accounts (id integer);")?;
+    env.create_test_file(
+        "schema/01_duplicate.sql",
+        "CREATE TABLE orders (id integer, id text);",
+    )?;
+    env.create_test_file("schema/02_syntax.sql", "CREATE TABLE items (id integer;")?;
+
+    let output = run_shem_command_in_dir(
+        &["validate", "--schema", "schema"],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("01_duplicate.sql:"));
+    assert!(stdout.contains("Duplicate column name: id"));
+    assert!(stdout.contains("02_syntax.sql:"));
+    assert!(!stdout.contains("00_valid.sql:"));
+
+    // --fail-fast stops after the first file with errors
+    let output = run_shem_command_in_dir(
+        &["validate", "--schema", "schema", "--fail-fast"],
+        &env.temp_path(),
+    )?;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("01_duplicate.sql:"));
+    assert!(!stdout.contains("02_syntax.sql:"));
+
+    // A valid directory passes
+    std::fs::remove_file(env.temp_path().join("schema/01_duplicate.sql"))?;
+    std::fs::remove_file(env.temp_path().join("schema/02_syntax.sql"))?;
+    let output = run_shem_command_in_dir(&["validate", "--schema", "schema"], &env.temp_path())?;
+    assert_command_success(&output);
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! Validate command tests
+//!
+//! Tests for validating schema files without a database.
+
+pub mod directory;
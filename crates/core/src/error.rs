@@ -25,6 +25,54 @@ pub enum Error {
 
     #[error("PostgreSQL error: {0}")]
     Postgres(#[from] tokio_postgres::Error),
+
+    #[error("Parse error at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+        snippet: String,
+    },
+}
+
+impl Error {
+    /// Parse error for `message` at byte `offset` of `sql`, located by 1-based
+    /// line and column with the text of that line as the snippet
+    pub fn parse_at(sql: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(sql.len());
+        while !sql.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &sql[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Error::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+            snippet: sql[line_start..].lines().next().unwrap_or("").to_string(),
+        }
+    }
+
+    /// The offending line of a parse error with a caret under its column
+    pub fn excerpt(&self) -> Option<String> {
+        let Error::Parse {
+            line,
+            column,
+            snippet,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let width = line.to_string().len();
+        Some(format!(
+            "{:>width$} | {}\n{:>width$} | {}^",
+            line,
+            snippet,
+            "",
+            " ".repeat(column - 1),
+        ))
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>; 
\ No newline at end of file
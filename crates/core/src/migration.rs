@@ -1,11 +1,15 @@
+use crate::normalize::{defaults_equivalent, types_equivalent};
 use crate::schema::{
-    CheckOption, Domain, EnumType, Extension, Function, IndexMethod, MaterializedView,
-    ParameterMode, Policy, Procedure, ReturnKind, Sequence, Server, SortOrder, Table, Trigger,
-    TriggerEvent, TriggerTiming, View,
+    CheckOption, Constraint, ConstraintKind, Domain, DomainConstraint, EnumType, Extension,
+    Function, IndexMethod, MaterializedView, Parameter, ParameterMode, Policy, Procedure,
+    QualifiedName, ReturnKind, Sequence, Server, SortOrder, Table, Trigger, TriggerEvent,
+    TriggerLevel, TriggerTiming, View,
 };
-use crate::{Result, Schema};
+use crate::traits::{GenerateOptions, SqlGenerator};
+use crate::{Error, Result, Schema};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -19,128 +23,404 @@ pub struct Migration {
 
 /// Generate migration from schema diff
 pub fn generate_migration(from: &Schema, to: &Schema) -> Result<Migration> {
-    let mut statements = Vec::new();
-    let mut rollback_statements = Vec::new();
+    diff_schemas(from, to, None, &GenerateOptions::default())
+}
 
-    // Handle tables
-    for (name, table) in &to.tables {
-        if !from.tables.contains_key(name) {
-            statements.push(generate_create_table(table)?);
-            rollback_statements.push(generate_drop_table(table)?);
-        } else {
-            let old_table = &from.tables[name];
-            let (up, down) = generate_alter_table(old_table, table)?;
-            statements.extend(up);
-            rollback_statements.extend(down);
+/// Generate migration from schema diff, rendering table changes with the
+/// dialect's SQL generator
+pub fn generate_migration_with(
+    from: &Schema,
+    to: &Schema,
+    generator: &dyn SqlGenerator,
+    options: &GenerateOptions,
+) -> Result<Migration> {
+    diff_schemas(from, to, Some(generator), options)
+}
+
+fn diff_schemas(
+    from: &Schema,
+    to: &Schema,
+    generator: Option<&dyn SqlGenerator>,
+    options: &GenerateOptions,
+) -> Result<Migration> {
+    let mut statements = Vec::new();
+    // Undo steps per object, replayed in reverse so dependents are undone first
+    let mut rollback: Vec<Vec<String>> = Vec::new();
+
+    // Handle removed objects first, dependents before what they depend on
+    for (name, policy) in sorted(&from.policies) {
+        if !to.policies.contains_key(name) {
+            statements.push(format!(
+                "DROP POLICY IF EXISTS {} ON {};",
+                quote_ident(&policy.name),
+                quoted_name(&policy.schema, &policy.table)
+            ));
+            rollback.push(vec![generate_create_policy(policy)?]);
         }
     }
-
-    // Handle views
-    for (name, view) in &to.views {
-        if !from.views.contains_key(name) {
-            statements.push(generate_create_view(view)?);
-            rollback_statements.push(format!("DROP VIEW IF EXISTS {}", name));
+    for (name, trigger) in sorted(&from.triggers) {
+        if !to.triggers.contains_key(name) {
+            statements.push(format!(
+                "DROP TRIGGER IF EXISTS {} ON {};",
+                quote_ident(&trigger.name),
+                quoted_name(&trigger.schema, &trigger.table)
+            ));
+            rollback.push(vec![generate_create_trigger(trigger)?]);
         }
     }
-
-    // Handle materialized views
-    for (name, view) in &to.materialized_views {
-        if !from.materialized_views.contains_key(name) {
-            statements.push(generate_create_materialized_view(view)?);
-            rollback_statements.push(format!("DROP MATERIALIZED VIEW IF EXISTS {}", name));
+    for (name, view) in sorted(&from.views) {
+        if !to.views.contains_key(name) {
+            statements.push(format!(
+                "DROP VIEW IF EXISTS {};",
+                quoted_name(&view.schema, &view.name)
+            ));
+            rollback.push(vec![generate_create_view(view)?]);
+        }
+    }
+    for (name, view) in sorted(&from.materialized_views) {
+        if !to.materialized_views.contains_key(name) {
+            statements.push(format!(
+                "DROP MATERIALIZED VIEW IF EXISTS {};",
+                quoted_name(&view.schema, &view.name)
+            ));
+            rollback.push(vec![generate_create_materialized_view(view)?]);
+        }
+    }
+    for (name, table) in sorted(&from.tables) {
+        if !to.tables.contains_key(name) {
+            let (up, down) = match generator {
+                Some(generator) => (
+                    generator.generate_drop_table(table)?,
+                    generator.generate_create_table(table)?,
+                ),
+                None => (generate_drop_table(table)?, generate_create_table(table)?),
+            };
+            statements.push(up);
+            rollback.push(vec![down]);
+        }
+    }
+    for (name, func) in sorted(&from.functions) {
+        if !to.functions.contains_key(name) {
+            statements.push(drop_function(func));
+            rollback.push(vec![generate_create_function(func)?]);
+        }
+    }
+    for (name, proc) in sorted(&from.procedures) {
+        if !to.procedures.contains_key(name) {
+            statements.push(drop_procedure(proc));
+            rollback.push(vec![generate_create_procedure(proc)?]);
+        }
+    }
+    // Sequences owned by a column go away with their table
+    for (name, seq) in sorted(&from.sequences) {
+        if !to.sequences.contains_key(name) && seq.owned_by.is_none() {
+            statements.push(format!(
+                "DROP SEQUENCE IF EXISTS {};",
+                quoted_name(&seq.schema, &seq.name)
+            ));
+            rollback.push(vec![generate_create_sequence(seq)?]);
+        }
+    }
+    for (name, domain) in sorted(&from.domains) {
+        if !to.domains.contains_key(name) {
+            statements.push(format!(
+                "DROP DOMAIN IF EXISTS {};",
+                quoted_name(&domain.schema, &domain.name)
+            ));
+            rollback.push(vec![generate_create_domain(domain)?]);
+        }
+    }
+    for (name, server) in sorted(&from.servers) {
+        if !to.servers.contains_key(name) {
+            statements.push(format!(
+                "DROP SERVER IF EXISTS {};",
+                quote_ident(&server.name)
+            ));
+            rollback.push(vec![generate_create_server(server)?]);
         }
     }
 
-    // Handle functions
-    for (name, func) in &to.functions {
-        if !from.functions.contains_key(name) {
-            statements.push(generate_create_function(func)?);
-            rollback_statements.push(format!("DROP FUNCTION IF EXISTS {}", name));
+    // Create objects after whatever they depend on: extensions and servers,
+    // then types, sequences and the functions that columns and constraints use,
+    // then tables and their foreign keys, the remaining routines, the views over
+    // them, and finally triggers and policies
+    // Handle extensions
+    for (name, ext) in sorted(&to.extensions) {
+        if !from.extensions.contains_key(name) {
+            statements.push(generate_create_extension(ext)?);
+            rollback.push(vec![format!("DROP EXTENSION IF EXISTS \"{}\"", ext.name)]);
+        } else {
+            let old_ext = &from.extensions[name];
+            // An empty version means whatever is installed is acceptable
+            if !ext.version.is_empty() && ext.version != old_ext.version {
+                statements.push(format!(
+                    "ALTER EXTENSION \"{}\" UPDATE TO '{}';",
+                    ext.name, ext.version
+                ));
+                if !old_ext.version.is_empty() {
+                    rollback.push(vec![format!(
+                        "ALTER EXTENSION \"{}\" UPDATE TO '{}';",
+                        old_ext.name, old_ext.version
+                    )]);
+                }
+            }
         }
     }
 
-    // Handle procedures
-    for (name, proc) in &to.procedures {
-        if !from.procedures.contains_key(name) {
-            statements.push(generate_create_procedure(proc)?);
-            rollback_statements.push(format!("DROP PROCEDURE IF EXISTS {}", name));
+    // Handle servers
+    for (name, server) in sorted(&to.servers) {
+        if !from.servers.contains_key(name) {
+            statements.push(generate_create_server(server)?);
+            rollback.push(vec![format!(
+                "DROP SERVER IF EXISTS {}",
+                quote_ident(&server.name)
+            )]);
         }
     }
 
-    // Note: Types are now handled as enums separately
+    // Handle enums
+    for (name, enum_type) in sorted(&to.enums) {
+        if !from.enums.contains_key(name) {
+            statements.push(generate_create_enum(enum_type)?);
+            rollback.push(vec![format!(
+                "DROP TYPE IF EXISTS {} CASCADE;",
+                quoted_name(&enum_type.schema, &enum_type.name)
+            )]);
+        } else {
+            let old_enum = &from.enums[name];
+            let (up, down) = generate_alter_enum(old_enum, enum_type)?;
+            statements.extend(up);
+            rollback.push(down);
+        }
+    }
 
     // Handle domains
-    for (name, domain) in &to.domains {
+    for (name, domain) in sorted(&to.domains) {
         if !from.domains.contains_key(name) {
             statements.push(generate_create_domain(domain)?);
-            rollback_statements.push(format!("DROP DOMAIN IF EXISTS {}", name));
+            rollback.push(vec![format!(
+                "DROP DOMAIN IF EXISTS {}",
+                quoted_name(&domain.schema, &domain.name)
+            )]);
+        } else {
+            let old_domain = &from.domains[name];
+            let (up, down) = generate_alter_domain(old_domain, domain)?;
+            statements.extend(up);
+            rollback.push(down);
         }
     }
 
     // Handle sequences
-    for (name, seq) in &to.sequences {
+    for (name, seq) in sorted(&to.sequences) {
         if !from.sequences.contains_key(name) {
             statements.push(generate_create_sequence(seq)?);
-            rollback_statements.push(format!("DROP SEQUENCE IF EXISTS {}", name));
+            rollback.push(vec![format!(
+                "DROP SEQUENCE IF EXISTS {}",
+                quoted_name(&seq.schema, &seq.name)
+            )]);
         } else {
             let old_seq = &from.sequences[name];
             let (up, down) = generate_alter_sequence(old_seq, seq)?;
             statements.extend(up);
-            rollback_statements.extend(down);
+            rollback.push(down);
         }
     }
 
-    // Handle extensions
-    for (name, ext) in &to.extensions {
-        if !from.extensions.contains_key(name) {
-            statements.push(generate_create_extension(ext)?);
-            rollback_statements.push(format!("DROP EXTENSION IF EXISTS {}", name));
+    // Handle functions that column defaults and constraints call; the rest may
+    // select from or return tables, so they are created once the tables exist
+    let (table_functions, other_functions): (Vec<_>, Vec<_>) = sorted(&to.functions)
+        .into_iter()
+        .filter(|(name, _)| !from.functions.contains_key(*name))
+        .partition(|(_, func)| used_by_tables(&func.name, &to.tables));
+    for (_, func) in table_functions {
+        statements.push(generate_create_function(func)?);
+        rollback.push(vec![drop_function(func)]);
+    }
+
+    // Handle tables. Foreign keys of new tables are added once every table
+    // exists, so tables that reference each other can be created
+    let mut foreign_keys = Vec::new();
+    for (name, table) in tables_in_dependency_order(&to.tables) {
+        if !from.tables.contains_key(name) {
+            let is_foreign_key =
+                |c: &Constraint| matches!(c.kind, ConstraintKind::ForeignKey { .. });
+            let mut created = table.clone();
+            created.constraints.retain(|c| !is_foreign_key(c));
+            let (up, down) = match generator {
+                Some(generator) => (
+                    generator.generate_create_table(&created)?,
+                    generator.generate_drop_table(&created)?,
+                ),
+                None => (
+                    generate_create_table(&created)?,
+                    generate_drop_table(&created)?,
+                ),
+            };
+            statements.push(up);
+            rollback.push(vec![down]);
+            if created.constraints.len() != table.constraints.len() {
+                // Diffed against a copy holding only the foreign keys, so other
+                // constraints can't be mistaken for changed ones
+                let mut referencing = created.clone();
+                referencing.constraints = table
+                    .constraints
+                    .iter()
+                    .filter(|c| is_foreign_key(c))
+                    .cloned()
+                    .collect();
+                created.constraints.clear();
+                foreign_keys.push((created, referencing));
+            }
+        } else {
+            let old_table = &from.tables[name];
+            let (up, down) = match generator {
+                Some(generator) => {
+                    generator.generate_alter_table_with_options(old_table, table, options)?
+                }
+                None => generate_alter_table(old_table, table)?,
+            };
+            // Dialect generators leave ALTER statements unterminated
+            statements.extend(up.into_iter().map(terminate_statement));
+            rollback.push(down.into_iter().map(terminate_statement).collect());
+        }
+    }
+    for (created, referencing) in foreign_keys {
+        let (up, down) = match generator {
+            Some(generator) => {
+                generator.generate_alter_table_with_options(&created, &referencing, options)?
+            }
+            None => generate_alter_table(&created, &referencing)?,
+        };
+        statements.extend(up.into_iter().map(terminate_statement));
+        rollback.push(down.into_iter().map(terminate_statement).collect());
+    }
+
+    // Sequences are created before the tables, so ownership is handed over here
+    for (name, seq) in sorted(&to.sequences) {
+        if let Some(owned_by) = &seq.owned_by
+            && !from.sequences.contains_key(name)
+        {
+            statements.push(format!(
+                "ALTER SEQUENCE {} OWNED BY {};",
+                quoted_name(&seq.schema, &seq.name),
+                owned_by
+            ));
+        }
+    }
+
+    // Handle the remaining functions
+    for (_, func) in other_functions {
+        statements.push(generate_create_function(func)?);
+        rollback.push(vec![drop_function(func)]);
+    }
+
+    // Handle procedures
+    for (name, proc) in sorted(&to.procedures) {
+        if !from.procedures.contains_key(name) {
+            statements.push(generate_create_procedure(proc)?);
+            rollback.push(vec![drop_procedure(proc)]);
+        }
+    }
+
+    // Handle views
+    for (name, view) in views_in_dependency_order(&to.views) {
+        if !from.views.contains_key(name) {
+            statements.push(generate_create_view(view)?);
+            rollback.push(vec![format!(
+                "DROP VIEW IF EXISTS {}",
+                quoted_name(&view.schema, &view.name)
+            )]);
+        }
+    }
+
+    // Handle materialized views
+    for (name, view) in sorted(&to.materialized_views) {
+        if !from.materialized_views.contains_key(name) {
+            statements.push(generate_create_materialized_view(view)?);
+            rollback.push(vec![format!(
+                "DROP MATERIALIZED VIEW IF EXISTS {}",
+                quoted_name(&view.schema, &view.name)
+            )]);
         }
     }
 
     // Handle triggers
-    for (name, trigger) in &to.triggers {
+    for (name, trigger) in sorted(&to.triggers) {
         if !from.triggers.contains_key(name) {
             statements.push(generate_create_trigger(trigger)?);
-            rollback_statements.push(format!(
+            rollback.push(vec![format!(
                 "DROP TRIGGER IF EXISTS {} ON {}",
-                name, trigger.table
-            ));
+                quote_ident(&trigger.name),
+                quoted_name(&trigger.schema, &trigger.table)
+            )]);
         }
     }
 
     // Handle policies
-    for (name, policy) in &to.policies {
+    for (name, policy) in sorted(&to.policies) {
         if !from.policies.contains_key(name) {
             statements.push(generate_create_policy(policy)?);
-            rollback_statements.push(format!(
+            rollback.push(vec![format!(
                 "DROP POLICY IF EXISTS {} ON {}",
-                name, policy.table
-            ));
+                quote_ident(&policy.name),
+                quoted_name(&policy.schema, &policy.table)
+            )]);
         }
     }
 
-    // Handle servers
-    for (name, server) in &to.servers {
-        if !from.servers.contains_key(name) {
-            statements.push(generate_create_server(server)?);
-            rollback_statements.push(format!("DROP SERVER IF EXISTS {}", name));
+    // Handle comments, including those on objects created above
+    let mut comments: Vec<(&str, String, Option<&str>, Option<&str>)> = Vec::new();
+    for (name, table) in sorted(&to.tables) {
+        let old_table = from.tables.get(name);
+        let table_name = quoted_name(&table.schema, &table.name);
+        comments.push((
+            "TABLE",
+            table_name.clone(),
+            old_table.and_then(|t| t.comment.as_deref()),
+            table.comment.as_deref(),
+        ));
+        for column in &table.columns {
+            let old_column =
+                old_table.and_then(|t| t.columns.iter().find(|c| c.name == column.name));
+            comments.push((
+                "COLUMN",
+                format!("{}.{}", table_name, quote_ident(&column.name)),
+                old_column.and_then(|c| c.comment.as_deref()),
+                column.comment.as_deref(),
+            ));
         }
     }
-
-    // Handle enums
-    for (name, enum_type) in &to.enums {
-        if !from.enums.contains_key(name) {
-            statements.push(generate_create_enum(enum_type)?);
-            rollback_statements.push(format!("DROP TYPE IF EXISTS {} CASCADE;", enum_type.name));
+    for (name, view) in sorted(&to.views) {
+        comments.push((
+            "VIEW",
+            quoted_name(&view.schema, &view.name),
+            from.views.get(name).and_then(|v| v.comment.as_deref()),
+            view.comment.as_deref(),
+        ));
+    }
+    for (name, func) in sorted(&to.functions) {
+        comments.push((
+            "FUNCTION",
+            function_signature(func),
+            from.functions.get(name).and_then(|f| f.comment.as_deref()),
+            func.comment.as_deref(),
+        ));
+    }
+    for (object_type, name, old, new) in comments {
+        if old != new {
+            statements.push(comment_statement(object_type, &name, new));
+            rollback.push(vec![comment_statement(object_type, &name, old)]);
         }
     }
 
     // Handle removed enums
-    for (name, enum_type) in &from.enums {
+    for (name, enum_type) in sorted(&from.enums) {
         if !to.enums.contains_key(name) {
-            statements.push(format!("DROP TYPE IF EXISTS {} CASCADE;", enum_type.name));
-            rollback_statements.push(generate_create_enum(enum_type)?);
+            statements.push(format!(
+                "DROP TYPE IF EXISTS {} CASCADE;",
+                quoted_name(&enum_type.schema, &enum_type.name)
+            ));
+            rollback.push(vec![generate_create_enum(enum_type)?]);
         }
     }
 
@@ -148,20 +428,181 @@ pub fn generate_migration(from: &Schema, to: &Schema) -> Result<Migration> {
         version: chrono::Utc::now().format("%Y%m%d%H%M%S").to_string(),
         description: "Generated migration".to_string(),
         statements,
-        rollback_statements,
+        rollback_statements: rollback
+            .into_iter()
+            .rev()
+            .flatten()
+            .map(terminate_statement)
+            .collect(),
         created_at: Utc::now(),
     })
 }
 
+/// Double-quoted name of a schema-scoped object, qualified outside `public`, so
+/// mixed-case and reserved names survive
+fn quoted_name(schema: &Option<String>, name: &str) -> String {
+    QualifiedName::new(schema.clone(), name).to_sql(true)
+}
+
+/// Double-quoted identifier, such as a column or a trigger name
+fn quote_ident(ident: &str) -> String {
+    quoted_name(&None, ident)
+}
+
+/// Quoted name and input argument types identifying a function among its overloads
+fn function_signature(func: &Function) -> String {
+    format!(
+        "{}({})",
+        quoted_name(&func.schema, &func.name),
+        func.argument_types()
+    )
+}
+
+fn drop_function(func: &Function) -> String {
+    format!("DROP FUNCTION IF EXISTS {};", function_signature(func))
+}
+
+fn drop_procedure(proc: &Procedure) -> String {
+    format!(
+        "DROP PROCEDURE IF EXISTS {}({});",
+        quoted_name(&proc.schema, &proc.name),
+        proc.argument_types()
+    )
+}
+
+/// `COMMENT ON` setting an object's comment, or clearing it with `IS NULL`;
+/// `name` is already quoted
+fn comment_statement(object_type: &str, name: &str, comment: Option<&str>) -> String {
+    match comment {
+        Some(comment) => format!(
+            "COMMENT ON {} {} IS '{}';",
+            object_type,
+            name,
+            comment.replace('\'', "''")
+        ),
+        None => format!("COMMENT ON {} {} IS NULL;", object_type, name),
+    }
+}
+
+/// Entries of `map` ordered by key, so generated migrations are stable across runs
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+}
+
+/// Entries of `map` ordered so each comes after the entries it depends on;
+/// dependencies outside `map`, self references and cycles are ignored
+fn dependency_order<V>(
+    map: &HashMap<String, V>,
+    dependencies: impl Fn(&V) -> Vec<String>,
+) -> Vec<(&String, &V)> {
+    fn visit<'a, V>(
+        key: &str,
+        map: &'a HashMap<String, V>,
+        dependencies: &dyn Fn(&V) -> Vec<String>,
+        visited: &mut HashSet<&'a String>,
+        ordered: &mut Vec<(&'a String, &'a V)>,
+    ) {
+        let Some((key, value)) = map.get_key_value(key) else {
+            return;
+        };
+        if !visited.insert(key) {
+            return;
+        }
+        let mut deps = dependencies(value);
+        deps.sort();
+        for dep in deps {
+            visit(&dep, map, dependencies, visited, ordered);
+        }
+        ordered.push((key, value));
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::new();
+    for (key, _) in sorted(map) {
+        visit(key, map, &dependencies, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+/// Tables after the tables they reference through foreign keys or inherit from.
+/// Foreign keys of new tables are added after all of them, so a reference cycle
+/// only leaves this order partial
+fn tables_in_dependency_order(tables: &HashMap<String, Table>) -> Vec<(&String, &Table)> {
+    dependency_order(tables, |table| {
+        let references = table.constraints.iter().filter_map(|c| match &c.kind {
+            ConstraintKind::ForeignKey { references, .. } => Some(references.replace('"', "")),
+            _ => None,
+        });
+        references
+            .chain(table.inherits.iter().cloned())
+            .map(|name| QualifiedName::parse(&name).to_sql(false))
+            .collect()
+    })
+}
+
+/// Views after the views their definitions select from
+fn views_in_dependency_order(views: &HashMap<String, View>) -> Vec<(&String, &View)> {
+    dependency_order(views, |view| {
+        views
+            .iter()
+            .filter(|(_, other)| contains_identifier(&view.definition, &other.name))
+            .map(|(key, _)| key.clone())
+            .collect()
+    })
+}
+
+/// Whether a column default, generated column or constraint of any table calls
+/// the function `name`
+fn used_by_tables(name: &str, tables: &HashMap<String, Table>) -> bool {
+    tables.values().any(|table| {
+        let columns = table.columns.iter().flat_map(|column| {
+            column.default.iter().chain(
+                column
+                    .generated
+                    .iter()
+                    .map(|generated| &generated.expression),
+            )
+        });
+        let constraints = table.constraints.iter().map(|c| &c.definition);
+        columns
+            .chain(constraints)
+            .any(|sql| contains_identifier(sql, name))
+    })
+}
+
+/// Whether `name` appears in `sql` as a whole identifier rather than part of one
+fn contains_identifier(sql: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    sql.match_indices(name).any(|(start, _)| {
+        let before = sql[..start].chars().next_back();
+        let after = sql[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
 // Helper functions for generating SQL statements
 
+/// End a statement with a semicolon; comment lines are left as they are
+fn terminate_statement(statement: String) -> String {
+    if statement.trim_end().ends_with(';') || statement.starts_with("--") {
+        statement
+    } else {
+        format!("{};", statement)
+    }
+}
+
 fn generate_create_table(table: &Table) -> Result<String> {
     let mut sql = format!("CREATE TABLE {} (", table.name);
     let mut columns = Vec::new();
 
-    // Add columns
-    for (_i, col) in table.columns.iter().enumerate() {
+    // Add columns, leaving inherited ones to the INHERITS clause
+    for col in table.columns.iter().filter(|c| !c.inherited) {
         let mut col_def = format!("{} {}", col.name, col.type_name);
+        if let Some(collation) = col.collation.as_deref().filter(|c| *c != "default") {
+            col_def.push_str(&format!(" COLLATE \"{}\"", collation));
+        }
         if !col.nullable {
             col_def.push_str(" NOT NULL");
         }
@@ -221,11 +662,30 @@ fn generate_create_table(table: &Table) -> Result<String> {
     }
 
     sql.push_str(&columns.join(",\n    "));
-    sql.push_str("\n);");
+    sql.push_str("\n)");
+    if !table.inherits.is_empty() {
+        sql.push_str(&format!(" INHERITS ({})", table.inherits.join(", ")));
+    }
+    sql.push_str(&storage_clause(&table.storage_parameters));
+    sql.push(';');
 
     Ok(sql)
 }
 
+/// ` WITH (...)` for non-default storage parameters such as fillfactor, sorted by name
+/// so the output is stable across runs; empty when there are none
+pub fn storage_clause(parameters: &HashMap<String, String>) -> String {
+    if parameters.is_empty() {
+        return String::new();
+    }
+    let mut parameters: Vec<String> = parameters
+        .iter()
+        .map(|(k, v)| format!("{} = {}", k, v))
+        .collect();
+    parameters.sort();
+    format!(" WITH ({})", parameters.join(", "))
+}
+
 fn generate_drop_table(table: &Table) -> Result<String> {
     Ok(format!("DROP TABLE IF EXISTS {} CASCADE;", table.name))
 }
@@ -304,7 +764,7 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
     for (name, new_col) in &new_columns {
         if let Some(old_col) = old_columns.get(name) {
             // Check for type changes
-            if old_col.type_name != new_col.type_name {
+            if !types_equivalent(&old_col.type_name, &new_col.type_name) {
                 up_statements.push(format!(
                     "ALTER TABLE {} ALTER COLUMN {} TYPE {};",
                     new.name, name, new_col.type_name
@@ -339,7 +799,7 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
             }
 
             // Check for default changes
-            if old_col.default != new_col.default {
+            if !defaults_equivalent(old_col.default.as_deref(), new_col.default.as_deref()) {
                 if let Some(default) = &new_col.default {
                     up_statements.push(format!(
                         "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
@@ -417,10 +877,13 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
                 .iter()
                 .map(|c| {
                     let mut col = c.name.clone();
-                    col.push_str(match c.order {
-                        SortOrder::Ascending => " ASC",
-                        SortOrder::Descending => " DESC",
-                    });
+                    if let Some(opclass) = &c.opclass {
+                        col.push_str(&format!(" {}", opclass));
+                    }
+                    // ASC is the default and is rejected by methods without ordering such as gin
+                    if c.order == SortOrder::Descending {
+                        col.push_str(" DESC");
+                    }
                     if c.nulls_first {
                         col.push_str(" NULLS FIRST");
                     }
@@ -430,7 +893,7 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
 
             let unique = if new_index.unique { "UNIQUE " } else { "" };
             up_statements.push(format!(
-                "CREATE {}INDEX {} ON {} USING {} ({});",
+                "CREATE {}INDEX {} ON {} USING {} ({}){};",
                 unique,
                 name,
                 new.name,
@@ -442,7 +905,8 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
                     IndexMethod::Gin => "gin",
                     IndexMethod::Brin => "brin",
                 },
-                columns.join(", ")
+                columns.join(", "),
+                storage_clause(&new_index.storage_parameters)
             ));
             down_statements.push(format!("DROP INDEX {};", name));
         }
@@ -458,10 +922,13 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
                     .iter()
                     .map(|c| {
                         let mut col = c.name.clone();
-                        col.push_str(match c.order {
-                            SortOrder::Ascending => " ASC",
-                            SortOrder::Descending => " DESC",
-                        });
+                        if let Some(opclass) = &c.opclass {
+                            col.push_str(&format!(" {}", opclass));
+                        }
+                        // ASC is the default and is rejected by methods without ordering such as gin
+                        if c.order == SortOrder::Descending {
+                            col.push_str(" DESC");
+                        }
                         if c.nulls_first {
                             col.push_str(" NULLS FIRST");
                         }
@@ -471,7 +938,7 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
 
                 let unique = if old_index.unique { "UNIQUE " } else { "" };
                 down_statements.push(format!(
-                    "CREATE {}INDEX {} ON {} USING {} ({});",
+                    "CREATE {}INDEX {} ON {} USING {} ({}){};",
                     unique,
                     name,
                     old.name,
@@ -483,7 +950,8 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
                         IndexMethod::Gin => "gin",
                         IndexMethod::Brin => "brin",
                     },
-                    columns.join(", ")
+                    columns.join(", "),
+                    storage_clause(&old_index.storage_parameters)
                 ));
             }
         }
@@ -493,7 +961,12 @@ fn generate_alter_table(old: &Table, new: &Table) -> Result<(Vec<String>, Vec<St
 }
 
 fn generate_create_view(view: &View) -> Result<String> {
-    let mut sql = format!("CREATE VIEW {} AS {}", view.name, view.definition);
+    // Introspected definitions end with their own semicolon
+    let mut sql = format!(
+        "CREATE VIEW {} AS {}",
+        quoted_name(&view.schema, &view.name),
+        view.definition.trim_end().trim_end_matches(';')
+    );
 
     match view.check_option {
         CheckOption::Local => sql.push_str(" WITH LOCAL CHECK OPTION"),
@@ -508,7 +981,8 @@ fn generate_create_view(view: &View) -> Result<String> {
 fn generate_create_materialized_view(view: &MaterializedView) -> Result<String> {
     let mut sql = format!(
         "CREATE MATERIALIZED VIEW {} AS {}",
-        view.name, view.definition
+        quoted_name(&view.schema, &view.name),
+        view.definition.trim_end().trim_end_matches(';')
     );
 
     // Add WITH DATA or WITH NO DATA based on the populate_with_data field
@@ -523,31 +997,13 @@ fn generate_create_materialized_view(view: &MaterializedView) -> Result<String>
 }
 
 fn generate_create_function(func: &Function) -> Result<String> {
-    let mut sql = format!("CREATE OR REPLACE FUNCTION {} (", func.name);
+    let mut sql = format!(
+        "CREATE OR REPLACE FUNCTION {} (",
+        quoted_name(&func.schema, &func.name)
+    );
 
     // Add parameters
-    let params: Vec<String> = func
-        .parameters
-        .iter()
-        .map(|param| {
-            let mut param_def = String::new();
-            if !param.name.is_empty() {
-                param_def.push_str(&param.name);
-                param_def.push(' ');
-            }
-            param_def.push_str(&param.type_name);
-            if let Some(default) = &param.default {
-                param_def.push_str(&format!(" DEFAULT {}", default));
-            }
-            param_def.push_str(match param.mode {
-                ParameterMode::In => " IN",
-                ParameterMode::Out => " OUT",
-                ParameterMode::InOut => " INOUT",
-                ParameterMode::Variadic => " VARIADIC",
-            });
-            param_def
-        })
-        .collect();
+    let params: Vec<String> = func.parameters.iter().map(generate_parameter).collect();
 
     sql.push_str(&params.join(", "));
     sql.push_str(") ");
@@ -560,6 +1016,10 @@ fn generate_create_function(func: &Function) -> Result<String> {
         ReturnKind::Void => {
             sql.push_str("RETURNS void");
         }
+        // Introspected result types already read `TABLE(...)`
+        ReturnKind::Table if func.returns.type_name.to_uppercase().starts_with("TABLE") => {
+            sql.push_str(&format!("RETURNS {}", func.returns.type_name));
+        }
         ReturnKind::Table => {
             sql.push_str(&format!("RETURNS TABLE ({})", func.returns.type_name));
         }
@@ -568,40 +1028,45 @@ fn generate_create_function(func: &Function) -> Result<String> {
         }
     }
 
-    // Add language and definition
-    sql.push_str(&format!(" LANGUAGE {} AS $$", func.language));
+    // Add language, non-default attributes and definition
+    sql.push_str(&format!(
+        " LANGUAGE {}{} AS $$",
+        func.language,
+        func.attributes()
+    ));
     sql.push_str(&func.definition);
     sql.push_str("$$;");
 
     Ok(sql)
 }
 
+/// Render a routine parameter as `[mode] [name] type [DEFAULT expr]`; IN is the default mode
+fn generate_parameter(param: &Parameter) -> String {
+    let mut param_def = String::from(match param.mode {
+        ParameterMode::In => "",
+        ParameterMode::Out => "OUT ",
+        ParameterMode::InOut => "INOUT ",
+        ParameterMode::Variadic => "VARIADIC ",
+    });
+    if !param.name.is_empty() {
+        param_def.push_str(&param.name);
+        param_def.push(' ');
+    }
+    param_def.push_str(&param.type_name);
+    if let Some(default) = &param.default {
+        param_def.push_str(&format!(" DEFAULT {}", default));
+    }
+    param_def
+}
+
 fn generate_create_procedure(proc: &Procedure) -> Result<String> {
-    let mut sql = format!("CREATE OR REPLACE PROCEDURE {} (", proc.name);
+    let mut sql = format!(
+        "CREATE OR REPLACE PROCEDURE {} (",
+        quoted_name(&proc.schema, &proc.name)
+    );
 
     // Add parameters
-    let params: Vec<String> = proc
-        .parameters
-        .iter()
-        .map(|param| {
-            let mut param_def = String::new();
-            if !param.name.is_empty() {
-                param_def.push_str(&param.name);
-                param_def.push(' ');
-            }
-            param_def.push_str(&param.type_name);
-            if let Some(default) = &param.default {
-                param_def.push_str(&format!(" DEFAULT {}", default));
-            }
-            param_def.push_str(match param.mode {
-                ParameterMode::In => " IN",
-                ParameterMode::Out => " OUT",
-                ParameterMode::InOut => " INOUT",
-                ParameterMode::Variadic => " VARIADIC",
-            });
-            param_def
-        })
-        .collect();
+    let params: Vec<String> = proc.parameters.iter().map(generate_parameter).collect();
 
     sql.push_str(&params.join(", "));
     sql.push_str(") ");
@@ -615,23 +1080,144 @@ fn generate_create_procedure(proc: &Procedure) -> Result<String> {
 }
 
 fn generate_create_domain(domain: &Domain) -> Result<String> {
-    let mut sql = format!("CREATE DOMAIN {} AS {}", domain.name, domain.base_type);
+    let mut sql = format!(
+        "CREATE DOMAIN {} AS {}",
+        quoted_name(&domain.schema, &domain.name),
+        domain.base_type
+    );
+
+    if let Some(default) = &domain.default {
+        sql.push_str(&format!(" DEFAULT {}", default));
+    }
+    if domain.not_null {
+        sql.push_str(" NOT NULL");
+    }
 
     // Add constraints
     for constraint in &domain.constraints {
-        sql.push_str(&format!(" CHECK ({})", constraint.check));
+        if let Some(name) = &constraint.name {
+            sql.push_str(&format!(" CONSTRAINT {}", quote_ident(name)));
+        }
+        sql.push_str(&format!(" {}", domain_check_clause(constraint)));
     }
 
     sql.push(';');
     Ok(sql)
 }
 
+/// CHECK clause for a domain constraint; introspected checks already carry the
+/// `CHECK` keyword while declared ones hold the bare expression
+fn domain_check_clause(constraint: &DomainConstraint) -> String {
+    if constraint.check.starts_with("CHECK ") {
+        constraint.check.clone()
+    } else {
+        format!("CHECK ({})", constraint.check)
+    }
+}
+
+/// Alter a domain in place; dropping and recreating it fails while columns use it
+fn generate_alter_domain(old: &Domain, new: &Domain) -> Result<(Vec<String>, Vec<String>)> {
+    let name = quoted_name(&new.schema, &new.name);
+    let mut up_statements = Vec::new();
+    let mut down_statements = Vec::new();
+
+    // Handle default changes
+    if !defaults_equivalent(old.default.as_deref(), new.default.as_deref()) {
+        match &new.default {
+            Some(default) => {
+                up_statements.push(format!("ALTER DOMAIN {} SET DEFAULT {};", name, default))
+            }
+            None => up_statements.push(format!("ALTER DOMAIN {} DROP DEFAULT;", name)),
+        }
+        match &old.default {
+            Some(default) => {
+                down_statements.push(format!("ALTER DOMAIN {} SET DEFAULT {};", name, default))
+            }
+            None => down_statements.push(format!("ALTER DOMAIN {} DROP DEFAULT;", name)),
+        }
+    }
+
+    // Handle NOT NULL changes
+    if old.not_null != new.not_null {
+        if new.not_null {
+            up_statements.push(format!("ALTER DOMAIN {} SET NOT NULL;", name));
+            down_statements.push(format!("ALTER DOMAIN {} DROP NOT NULL;", name));
+        } else {
+            up_statements.push(format!("ALTER DOMAIN {} DROP NOT NULL;", name));
+            down_statements.push(format!("ALTER DOMAIN {} SET NOT NULL;", name));
+        }
+    }
+
+    // Handle check constraint changes; unnamed checks get PostgreSQL's default name
+    let constraint_name = |domain: &Domain, constraint: &DomainConstraint| {
+        constraint
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("{}_check", domain.name))
+    };
+    let add_constraint = |domain: &Domain, constraint: &DomainConstraint| {
+        format!(
+            "ALTER DOMAIN {} ADD CONSTRAINT {} {}{};",
+            quoted_name(&domain.schema, &domain.name),
+            quote_ident(&constraint_name(domain, constraint)),
+            domain_check_clause(constraint),
+            if constraint.not_valid {
+                " NOT VALID"
+            } else {
+                ""
+            }
+        )
+    };
+    let drop_constraint = |domain: &Domain, constraint: &DomainConstraint| {
+        format!(
+            "ALTER DOMAIN {} DROP CONSTRAINT {};",
+            quoted_name(&domain.schema, &domain.name),
+            quote_ident(&constraint_name(domain, constraint))
+        )
+    };
+
+    let old_constraints: std::collections::HashMap<_, _> = old
+        .constraints
+        .iter()
+        .map(|c| (constraint_name(old, c), c))
+        .collect();
+    let new_constraints: std::collections::HashMap<_, _> = new
+        .constraints
+        .iter()
+        .map(|c| (constraint_name(new, c), c))
+        .collect();
+
+    // Drop removed or changed constraints before adding their replacements
+    for old_constraint in &old.constraints {
+        let name = constraint_name(old, old_constraint);
+        let unchanged = new_constraints
+            .get(&name)
+            .is_some_and(|c| domain_check_clause(c) == domain_check_clause(old_constraint));
+        if !unchanged {
+            up_statements.push(drop_constraint(old, old_constraint));
+            down_statements.push(add_constraint(old, old_constraint));
+        }
+    }
+
+    for new_constraint in &new.constraints {
+        let name = constraint_name(new, new_constraint);
+        let unchanged = old_constraints
+            .get(&name)
+            .is_some_and(|c| domain_check_clause(c) == domain_check_clause(new_constraint));
+        if !unchanged {
+            up_statements.push(add_constraint(new, new_constraint));
+            down_statements.insert(0, drop_constraint(new, new_constraint));
+        }
+    }
+
+    Ok((up_statements, down_statements))
+}
+
 fn generate_create_sequence(seq: &Sequence) -> Result<String> {
-    let mut sql = format!("CREATE SEQUENCE {}", seq.name);
+    let mut sql = format!("CREATE SEQUENCE {}", quoted_name(&seq.schema, &seq.name));
 
-    // Add schema if specified
-    if let Some(schema) = &seq.schema {
-        sql = format!("CREATE SEQUENCE {}.{}", schema, seq.name);
+    if !seq.data_type.is_empty() {
+        sql.push_str(&format!(" AS {}", seq.data_type));
     }
 
     // Add sequence options
@@ -660,6 +1246,7 @@ fn generate_create_sequence(seq: &Sequence) -> Result<String> {
 }
 
 fn generate_alter_sequence(old: &Sequence, new: &Sequence) -> Result<(Vec<String>, Vec<String>)> {
+    let name = quoted_name(&new.schema, &new.name);
     let mut up_statements = Vec::new();
     let mut down_statements = Vec::new();
 
@@ -667,11 +1254,11 @@ fn generate_alter_sequence(old: &Sequence, new: &Sequence) -> Result<(Vec<String
     if old.start != new.start {
         up_statements.push(format!(
             "ALTER SEQUENCE {} RESTART WITH {};",
-            new.name, new.start
+            name, new.start
         ));
         down_statements.push(format!(
             "ALTER SEQUENCE {} RESTART WITH {};",
-            old.name, old.start
+            name, old.start
         ));
     }
 
@@ -679,56 +1266,56 @@ fn generate_alter_sequence(old: &Sequence, new: &Sequence) -> Result<(Vec<String
     if old.increment != new.increment {
         up_statements.push(format!(
             "ALTER SEQUENCE {} INCREMENT BY {};",
-            new.name, new.increment
+            name, new.increment
         ));
         down_statements.push(format!(
             "ALTER SEQUENCE {} INCREMENT BY {};",
-            old.name, old.increment
+            name, old.increment
         ));
     }
 
     // Handle min value changes
     if old.min_value != new.min_value {
         if let Some(min) = new.min_value {
-            up_statements.push(format!("ALTER SEQUENCE {} MINVALUE {};", new.name, min));
+            up_statements.push(format!("ALTER SEQUENCE {} MINVALUE {};", name, min));
         } else {
-            up_statements.push(format!("ALTER SEQUENCE {} NO MINVALUE;", new.name));
+            up_statements.push(format!("ALTER SEQUENCE {} NO MINVALUE;", name));
         }
         if let Some(min) = old.min_value {
-            down_statements.push(format!("ALTER SEQUENCE {} MINVALUE {};", old.name, min));
+            down_statements.push(format!("ALTER SEQUENCE {} MINVALUE {};", name, min));
         } else {
-            down_statements.push(format!("ALTER SEQUENCE {} NO MINVALUE;", old.name));
+            down_statements.push(format!("ALTER SEQUENCE {} NO MINVALUE;", name));
         }
     }
 
     // Handle max value changes
     if old.max_value != new.max_value {
         if let Some(max) = new.max_value {
-            up_statements.push(format!("ALTER SEQUENCE {} MAXVALUE {};", new.name, max));
+            up_statements.push(format!("ALTER SEQUENCE {} MAXVALUE {};", name, max));
         } else {
-            up_statements.push(format!("ALTER SEQUENCE {} NO MAXVALUE;", new.name));
+            up_statements.push(format!("ALTER SEQUENCE {} NO MAXVALUE;", name));
         }
         if let Some(max) = old.max_value {
-            down_statements.push(format!("ALTER SEQUENCE {} MAXVALUE {};", old.name, max));
+            down_statements.push(format!("ALTER SEQUENCE {} MAXVALUE {};", name, max));
         } else {
-            down_statements.push(format!("ALTER SEQUENCE {} NO MAXVALUE;", old.name));
+            down_statements.push(format!("ALTER SEQUENCE {} NO MAXVALUE;", name));
         }
     }
 
     // Handle cache changes
     if old.cache != new.cache {
-        up_statements.push(format!("ALTER SEQUENCE {} CACHE {};", new.name, new.cache));
-        down_statements.push(format!("ALTER SEQUENCE {} CACHE {};", old.name, old.cache));
+        up_statements.push(format!("ALTER SEQUENCE {} CACHE {};", name, new.cache));
+        down_statements.push(format!("ALTER SEQUENCE {} CACHE {};", name, old.cache));
     }
 
     // Handle cycle changes
     if old.cycle != new.cycle {
         if new.cycle {
-            up_statements.push(format!("ALTER SEQUENCE {} CYCLE;", new.name));
-            down_statements.push(format!("ALTER SEQUENCE {} NO CYCLE;", old.name));
+            up_statements.push(format!("ALTER SEQUENCE {} CYCLE;", name));
+            down_statements.push(format!("ALTER SEQUENCE {} NO CYCLE;", name));
         } else {
-            up_statements.push(format!("ALTER SEQUENCE {} NO CYCLE;", new.name));
-            down_statements.push(format!("ALTER SEQUENCE {} CYCLE;", old.name));
+            up_statements.push(format!("ALTER SEQUENCE {} NO CYCLE;", name));
+            down_statements.push(format!("ALTER SEQUENCE {} CYCLE;", name));
         }
     }
 
@@ -746,19 +1333,26 @@ fn generate_create_extension(ext: &Extension) -> Result<String> {
         sql.push_str(&format!(" VERSION '{}'", ext.version));
     }
 
+    if ext.cascade {
+        sql.push_str(" CASCADE");
+    }
+
     sql.push(';');
     Ok(sql)
 }
 
 fn generate_create_trigger(trigger: &Trigger) -> Result<String> {
-    let events: Vec<&str> = trigger
+    let events: Vec<String> = trigger
         .events
         .iter()
         .map(|e| match e {
-            TriggerEvent::Insert => "INSERT",
-            TriggerEvent::Update => "UPDATE",
-            TriggerEvent::Delete => "DELETE",
-            TriggerEvent::Truncate => "TRUNCATE",
+            TriggerEvent::Insert => "INSERT".to_string(),
+            TriggerEvent::Update if !trigger.update_columns.is_empty() => {
+                format!("UPDATE OF {}", trigger.update_columns.join(", "))
+            }
+            TriggerEvent::Update => "UPDATE".to_string(),
+            TriggerEvent::Delete => "DELETE".to_string(),
+            TriggerEvent::Truncate => "TRUNCATE".to_string(),
         })
         .collect();
 
@@ -768,26 +1362,39 @@ fn generate_create_trigger(trigger: &Trigger) -> Result<String> {
         TriggerTiming::InsteadOf => "INSTEAD OF",
     };
 
+    let level = match trigger.for_each {
+        TriggerLevel::Row => "ROW",
+        TriggerLevel::Statement => "STATEMENT",
+    };
+
     let events_str = events.join(" OR ");
     let function = &trigger.function;
 
-    let args = if !trigger.arguments.is_empty() {
-        format!("({})", trigger.arguments.join(", "))
-    } else {
-        String::new()
+    let args = format!("({})", trigger.arguments.join(", "));
+
+    let when = match trigger.when.as_ref().or(trigger.condition.as_ref()) {
+        Some(condition) => format!(" WHEN ({})", condition),
+        None => String::new(),
     };
 
     Ok(format!(
-        "CREATE TRIGGER {} {} {} ON {} FOR EACH ROW EXECUTE FUNCTION {}{};",
-        trigger.name, timing, events_str, trigger.table, function, args
+        "CREATE TRIGGER {} {} {} ON {} FOR EACH {}{} EXECUTE FUNCTION {}{};",
+        quote_ident(&trigger.name),
+        timing,
+        events_str,
+        quoted_name(&trigger.schema, &trigger.table),
+        level,
+        when,
+        function,
+        args
     ))
 }
 
 fn generate_create_policy(policy: &Policy) -> Result<String> {
     let mut sql = format!(
         "CREATE POLICY {} ON {} AS {}",
-        policy.name,
-        policy.table,
+        quote_ident(&policy.name),
+        quoted_name(&policy.schema, &policy.table),
         if policy.permissive {
             "PERMISSIVE"
         } else {
@@ -814,7 +1421,8 @@ fn generate_create_policy(policy: &Policy) -> Result<String> {
 fn generate_create_server(server: &Server) -> Result<String> {
     let mut sql = format!(
         "CREATE SERVER {} FOREIGN DATA WRAPPER {}",
-        server.name, server.foreign_data_wrapper
+        quote_ident(&server.name),
+        server.foreign_data_wrapper
     );
 
     if !server.options.is_empty() {
@@ -831,18 +1439,15 @@ fn generate_create_server(server: &Server) -> Result<String> {
 }
 
 fn generate_create_enum(enum_type: &EnumType) -> Result<String> {
-    let mut sql = format!("CREATE TYPE {}", enum_type.name);
-
-    if let Some(schema) = &enum_type.schema {
-        sql = format!("CREATE TYPE {}.{}", schema, enum_type.name);
-    }
-
-    sql.push_str(" AS ENUM (");
+    let mut sql = format!(
+        "CREATE TYPE {} AS ENUM (",
+        quoted_name(&enum_type.schema, &enum_type.name)
+    );
 
     let values = enum_type
         .values
         .iter()
-        .map(|v| format!("'{}'", v))
+        .map(|v| format!("'{}'", v.replace('\'', "''")))
         .collect::<Vec<_>>()
         .join(", ");
 
@@ -852,9 +1457,197 @@ fn generate_create_enum(enum_type: &EnumType) -> Result<String> {
     Ok(sql)
 }
 
+/// Add the values `new` gained, each placed before the existing value it
+/// precedes; PostgreSQL can't drop or reorder enum values, so those changes
+/// are rejected rather than attempted. A value can't be used before the
+/// transaction adding it commits, so `shem migrate` commits after each
+/// `ADD VALUE`, and `IF NOT EXISTS` lets a migration that failed after one
+/// of them be re-run
+fn generate_alter_enum(old: &EnumType, new: &EnumType) -> Result<(Vec<String>, Vec<String>)> {
+    let enum_name = QualifiedName::new(new.schema.clone(), &new.name);
+    let type_name = enum_name.to_sql(true);
+    let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+
+    let removed: Vec<&str> = old
+        .values
+        .iter()
+        .filter(|value| !new.values.contains(value))
+        .map(String::as_str)
+        .collect();
+    if !removed.is_empty() {
+        return Err(Error::Migration(format!(
+            "Enum {} drops value(s) {}, which PostgreSQL does not support; \
+             create a new type, move columns over to it and drop the old one by hand",
+            enum_name,
+            removed.join(", ")
+        )));
+    }
+
+    let kept: Vec<&String> = new
+        .values
+        .iter()
+        .filter(|value| old.values.contains(value))
+        .collect();
+    if !kept.iter().copied().eq(old.values.iter()) {
+        return Err(Error::Migration(format!(
+            "Enum {} reorders its existing values, which PostgreSQL does not support; \
+             create a new type, move columns over to it and drop the old one by hand",
+            enum_name
+        )));
+    }
+
+    let mut up_statements = Vec::new();
+    for (position, value) in new.values.iter().enumerate() {
+        if old.values.contains(value) {
+            continue;
+        }
+        // Values added in order before the same existing value keep their order
+        let next_existing = new.values[position + 1..]
+            .iter()
+            .find(|next| old.values.contains(next));
+        up_statements.push(match next_existing {
+            Some(next) => format!(
+                "ALTER TYPE {} ADD VALUE IF NOT EXISTS {} BEFORE {};",
+                type_name,
+                literal(value),
+                literal(next)
+            ),
+            None => format!(
+                "ALTER TYPE {} ADD VALUE IF NOT EXISTS {};",
+                type_name,
+                literal(value)
+            ),
+        });
+    }
+
+    // Added values can't be dropped again; the down section says so rather than
+    // leaving them out silently
+    let added: Vec<String> = new
+        .values
+        .iter()
+        .filter(|value| !old.values.contains(value))
+        .map(|value| literal(value))
+        .collect();
+    let down_statements = if added.is_empty() {
+        Vec::new()
+    } else {
+        vec![format!(
+            "-- Enum {} keeps the added value(s) {}, PostgreSQL can't drop enum values",
+            enum_name,
+            added.join(", ")
+        )]
+    };
+    Ok((up_statements, down_statements))
+}
+
 /// Write migration to file
 pub fn write_migration(path: &Path, migration: &Migration) -> Result<()> {
-    let content = format!(
+    std::fs::write(path, format_migration(migration))?;
+
+    Ok(())
+}
+
+/// Lines separating the up section of a migration file from its down section
+const DOWN_MARKERS: &[&str] = &["-- Down Migration", "-- +down"];
+
+/// Parse a migration file into its up and down statements.
+///
+/// Everything after a `-- Down Migration` or `-- +down` line is the down
+/// section; comment lines are ignored.
+pub fn parse_migration(content: &str) -> Migration {
+    let mut up = Vec::new();
+    let mut down = Vec::new();
+    let mut in_down = false;
+
+    for line in content.lines().map(str::trim) {
+        if DOWN_MARKERS
+            .iter()
+            .any(|marker| line.eq_ignore_ascii_case(marker))
+        {
+            in_down = true;
+        } else if in_down {
+            down.push(line);
+        } else {
+            up.push(line);
+        }
+    }
+
+    Migration {
+        version: Utc::now().format("%Y%m%d%H%M%S").to_string(),
+        description: "Migration".to_string(),
+        statements: split_statements(&up),
+        rollback_statements: split_statements(&down),
+        created_at: Utc::now(),
+    }
+}
+
+fn split_statements(lines: &[&str]) -> Vec<String> {
+    let sql = lines
+        .iter()
+        .filter(|line| {
+            !line.is_empty()
+                && !line.starts_with("--")
+                && !line.starts_with("/*")
+                && !line.starts_with("*/")
+        })
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Split on semicolons outside string literals, quoted identifiers and
+    // dollar-quoted bodies, so function definitions stay whole
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            // A doubled quote closes the literal and immediately reopens it
+            quote @ (b'\'' | b'"') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            b'$' => {
+                if let Some(len) = dollar_tag_len(&sql[i..]) {
+                    let tag = &sql[i..i + len];
+                    i = match sql[i + len..].find(tag) {
+                        Some(end) => i + len + end + len - 1,
+                        None => bytes.len(),
+                    };
+                }
+            }
+            b';' => {
+                statements.push(&sql[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    statements.push(&sql[start.min(sql.len())..]);
+
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Length of the `$tag$` opening a dollar-quoted string at the start of `sql`,
+/// if there is one; `$1` style parameters are not tags
+fn dollar_tag_len(sql: &str) -> Option<usize> {
+    let tag = &sql[1..];
+    let end = tag.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    let valid = tag[end..].starts_with('$') && !tag.starts_with(|c: char| c.is_ascii_digit());
+    valid.then_some(end + 2)
+}
+
+/// Render a migration in the on-disk format with up and down sections
+pub fn format_migration(migration: &Migration) -> String {
+    format!(
         "-- Migration: {}\n\
          -- Generated: {}\n\
          -- Up Migration\n\
@@ -866,9 +1659,5 @@ pub fn write_migration(path: &Path, migration: &Migration) -> Result<()> {
         migration.created_at,
         migration.statements.join("\n"),
         migration.rollback_statements.join("\n")
-    );
-
-    std::fs::write(path, content)?;
-
-    Ok(())
+    )
 }
@@ -0,0 +1,243 @@
+//! Canonical forms used by the diff engine so that semantically equal SQL
+//! fragments written differently (by hand vs. by `pg_get_expr`) compare equal.
+
+/// Built-in type aliases mapped to the name `format_type` reports
+const TYPE_ALIASES: &[(&str, &str)] = &[
+    ("int", "integer"),
+    ("int4", "integer"),
+    ("int2", "smallint"),
+    ("int8", "bigint"),
+    ("bool", "boolean"),
+    ("varchar", "character varying"),
+    ("char", "character"),
+    ("bpchar", "character"),
+    ("float4", "real"),
+    ("float8", "double precision"),
+    ("float", "double precision"),
+    ("decimal", "numeric"),
+    ("timestamp", "timestamp without time zone"),
+    ("timestamptz", "timestamp with time zone"),
+    ("time", "time without time zone"),
+    ("timetz", "time with time zone"),
+    ("varbit", "bit varying"),
+];
+
+/// Words that continue a multi-word type name in a cast, e.g. `character varying`
+const TYPE_NAME_CONTINUATIONS: &[&str] =
+    &["varying", "precision", "with", "without", "time", "zone"];
+
+/// Normalize a column default expression for comparison.
+///
+/// Strips redundant outer parentheses and casts on literals (`'x'::t` -> `'x'`),
+/// lowercases keywords, collapses whitespace and rewrites `CURRENT_TIMESTAMP`
+/// and `transaction_timestamp()` to `now()`. String literals and quoted
+/// identifiers are kept verbatim.
+pub fn normalize_default_expression(expr: &str) -> String {
+    let chars: Vec<char> = strip_outer_parens(expr.trim()).chars().collect();
+    let mut out = String::with_capacity(chars.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            let end = quoted_end(&chars, i, '\'');
+            let (next, had_cast) = skip_cast(&chars, end);
+            let literal: String = chars[i..end].iter().collect();
+            let inner = literal.get(1..literal.len() - 1).unwrap_or("");
+            // A cast numeric literal such as '-1'::integer is the same as -1
+            if had_cast && inner.parse::<f64>().is_ok() {
+                out.push_str(inner);
+            } else {
+                out.push_str(&literal);
+            }
+            i = next;
+        } else if c == '"' {
+            let end = quoted_end(&chars, i, '"');
+            out.extend(&chars[i..end]);
+            i = end;
+        } else if c.is_ascii_digit() {
+            let end = scan_while(&chars, i, |ch| ch.is_ascii_digit() || ch == '.');
+            out.extend(&chars[i..end]);
+            i = skip_cast(&chars, end).0;
+        } else if c.is_alphabetic() || c == '_' {
+            let end = scan_while(&chars, i, |ch| {
+                ch.is_alphanumeric() || ch == '_' || ch == '$'
+            });
+            let word: String = chars[i..end].iter().collect::<String>().to_lowercase();
+            let followed_by_paren = chars[end..]
+                .iter()
+                .find(|ch| !ch.is_whitespace())
+                .is_some_and(|ch| *ch == '(');
+            match word.as_str() {
+                "current_timestamp" if !followed_by_paren => out.push_str("now()"),
+                "transaction_timestamp" => out.push_str("now"),
+                _ => out.push_str(&word),
+            }
+            i = end;
+        } else if c.is_whitespace() {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+            i = scan_while(&chars, i, char::is_whitespace);
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Canonical spelling of a type name for comparison, e.g. `int4` -> `integer`
+/// and `VARCHAR(255)` -> `character varying(255)`. Type modifiers and array
+/// suffixes are preserved, with `numeric(p)` spelled `numeric(p,0)` as
+/// `format_type` reports it.
+pub fn canonical_type_name(type_name: &str) -> String {
+    let lowered = type_name.trim().to_lowercase();
+
+    let mut array_suffix = String::new();
+    let mut base = lowered.as_str();
+    while let Some(stripped) = base.trim_end().strip_suffix("[]") {
+        array_suffix.push_str("[]");
+        base = stripped;
+    }
+
+    // Modifiers may sit mid-name, as in `timestamp(3) with time zone`
+    let (base, modifier) = match (base.find('('), base.find(')')) {
+        (Some(open), Some(close)) if open < close => (
+            format!("{} {}", &base[..open], &base[close + 1..]),
+            base[open..=close].replace(' ', ""),
+        ),
+        _ => (base.to_string(), String::new()),
+    };
+    let base = base.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let canonical = TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == base)
+        .map_or(base.as_str(), |(_, name)| name);
+
+    // A numeric precision without a scale implies a scale of zero
+    let modifier = match modifier.strip_suffix(')') {
+        Some(precision) if canonical == "numeric" && !precision.contains(',') => {
+            format!("{},0)", precision)
+        }
+        _ => modifier,
+    };
+
+    format!("{}{}{}", canonical, modifier, array_suffix)
+}
+
+/// Whether two type names refer to the same type
+pub fn types_equivalent(a: &str, b: &str) -> bool {
+    canonical_type_name(a) == canonical_type_name(b)
+}
+
+/// Whether two optional default expressions are semantically the same
+pub fn defaults_equivalent(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => normalize_default_expression(a) == normalize_default_expression(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Remove parentheses wrapping the whole expression, e.g. `((0))` -> `0`
+fn strip_outer_parens(expr: &str) -> &str {
+    let mut expr = expr;
+    while expr.starts_with('(')
+        && expr.ends_with(')')
+        && closing_paren(expr) == Some(expr.len() - 1)
+    {
+        expr = expr[1..expr.len() - 1].trim();
+    }
+    expr
+}
+
+/// Byte index of the parenthesis closing the one that opens `expr`
+fn closing_paren(expr: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_literal = false;
+    for (idx, c) in expr.char_indices() {
+        match c {
+            '\'' => in_literal = !in_literal,
+            '(' if !in_literal => depth += 1,
+            ')' if !in_literal => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Index just past a quoted token starting at `start`, honouring doubled quotes
+fn quoted_end(chars: &[char], start: usize, quote: char) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+fn scan_while(chars: &[char], start: usize, pred: impl Fn(char) -> bool) -> usize {
+    let mut i = start;
+    while i < chars.len() && pred(chars[i]) {
+        i += 1;
+    }
+    i
+}
+
+/// Skip any `::type` casts following position `pos`, returning the new position
+/// and whether a cast was skipped
+fn skip_cast(chars: &[char], pos: usize) -> (usize, bool) {
+    let start = scan_while(chars, pos, char::is_whitespace);
+    if chars.get(start) != Some(&':') || chars.get(start + 1) != Some(&':') {
+        return (pos, false);
+    }
+
+    let mut i = scan_while(chars, start + 2, char::is_whitespace);
+    i = if chars.get(i) == Some(&'"') {
+        quoted_end(chars, i, '"')
+    } else {
+        scan_while(chars, i, |ch| {
+            ch.is_alphanumeric() || ch == '_' || ch == '.'
+        })
+    };
+
+    loop {
+        let ws_end = scan_while(chars, i, char::is_whitespace);
+        match chars.get(ws_end) {
+            Some('(') => {
+                i = scan_while(chars, ws_end, |ch| ch != ')') + 1;
+            }
+            Some('[') => {
+                i = scan_while(chars, ws_end, |ch| ch != ']') + 1;
+            }
+            Some(ch) if ch.is_alphabetic() => {
+                let word_end = scan_while(chars, ws_end, |ch| ch.is_alphabetic());
+                let word: String = chars[ws_end..word_end]
+                    .iter()
+                    .collect::<String>()
+                    .to_lowercase();
+                if !TYPE_NAME_CONTINUATIONS.contains(&word.as_str()) {
+                    break;
+                }
+                i = word_end;
+            }
+            _ => break,
+        }
+    }
+
+    (skip_cast(chars, i.min(chars.len())).0, true)
+}
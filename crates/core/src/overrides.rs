@@ -0,0 +1,147 @@
+//! Wrapping a dialect generator so library users can adjust the SQL it emits
+//! (always adding `IF NOT EXISTS`, custom naming, keyword casing, ...) without
+//! reimplementing every `SqlGenerator` method.
+
+use crate::error::Result;
+use crate::schema::{
+    ArrayType, BaseType, Collation, CompositeType, ConstraintTrigger, Domain, EnumType,
+    EventTrigger, Extension, ForeignDataWrapper, ForeignTable, Function, Index, MaterializedView,
+    MultirangeType, Policy, Procedure, Publication, RangeType, Role, Rule, Sequence, Server,
+    Subscription, Table, Tablespace, Trigger, View,
+};
+use crate::traits::{GenerateOptions, SqlGenerator};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Hook registered on a driver that rewrites every statement its generator produces
+pub trait SqlGeneratorOverride: Send + Sync + Debug {
+    /// Rewrite one generated statement
+    fn rewrite(&self, sql: String) -> String;
+}
+
+/// `SqlGenerator` delegating to an inner generator and passing each result
+/// through a `SqlGeneratorOverride`
+#[derive(Debug)]
+pub struct OverriddenSqlGenerator<G> {
+    inner: G,
+    overrides: Arc<dyn SqlGeneratorOverride>,
+}
+
+impl<G: SqlGenerator> OverriddenSqlGenerator<G> {
+    pub fn new(inner: G, overrides: Arc<dyn SqlGeneratorOverride>) -> Self {
+        Self { inner, overrides }
+    }
+
+    fn rewrite(&self, sql: Result<String>) -> Result<String> {
+        sql.map(|sql| self.overrides.rewrite(sql))
+    }
+
+    fn rewrite_pair(
+        &self,
+        statements: Result<(Vec<String>, Vec<String>)>,
+    ) -> Result<(Vec<String>, Vec<String>)> {
+        statements.map(|(up, down)| {
+            let rewrite_all = |statements: Vec<String>| {
+                statements
+                    .into_iter()
+                    .map(|sql| self.overrides.rewrite(sql))
+                    .collect()
+            };
+            (rewrite_all(up), rewrite_all(down))
+        })
+    }
+}
+
+/// Forward single-statement methods to the inner generator, rewriting the result
+macro_rules! delegate {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method(&self, $($arg: $ty),*) -> Result<String> {
+                self.rewrite(self.inner.$method($($arg),*))
+            }
+        )*
+    };
+}
+
+/// Forward up/down statement methods to the inner generator, rewriting both halves
+macro_rules! delegate_pair {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method(&self, $($arg: $ty),*) -> Result<(Vec<String>, Vec<String>)> {
+                self.rewrite_pair(self.inner.$method($($arg),*))
+            }
+        )*
+    };
+}
+
+impl<G: SqlGenerator> SqlGenerator for OverriddenSqlGenerator<G> {
+    delegate_pair! {
+        generate_alter_table(old: &Table, new: &Table);
+        generate_alter_table_with_options(old: &Table, new: &Table, options: &GenerateOptions);
+        alter_enum(old: &EnumType, new: &EnumType);
+        alter_sequence(old: &Sequence, new: &Sequence);
+    }
+
+    delegate! {
+        generate_create_table(table: &Table);
+        generate_drop_table(table: &Table);
+        create_view(view: &View);
+        drop_view(view: &View);
+        create_materialized_view(view: &MaterializedView);
+        drop_materialized_view(view: &MaterializedView);
+        create_function(func: &Function);
+        drop_function(func: &Function);
+        create_procedure(proc: &Procedure);
+        drop_procedure(proc: &Procedure);
+        generate_create_enum(enum_type: &EnumType);
+        create_base_type(base_type: &BaseType);
+        drop_base_type(base_type: &BaseType);
+        create_array_type(array_type: &ArrayType);
+        drop_array_type(array_type: &ArrayType);
+        create_multirange_type(multirange_type: &MultirangeType);
+        drop_multirange_type(multirange_type: &MultirangeType);
+        create_enum(enum_type: &EnumType);
+        create_domain(domain: &Domain);
+        drop_domain(domain: &Domain);
+        create_sequence(seq: &Sequence);
+        drop_sequence(seq: &Sequence);
+        create_extension(ext: &Extension);
+        alter_extension(ext: &Extension);
+        drop_extension(ext: &Extension);
+        create_trigger(trigger: &Trigger);
+        drop_trigger(trigger: &Trigger);
+        create_policy(policy: &Policy);
+        drop_policy(policy: &Policy);
+        create_server(server: &Server);
+        drop_server(server: &Server);
+        create_index(index: &Index);
+        drop_index(index: &Index);
+        create_collation(collation: &Collation);
+        drop_collation(collation: &Collation);
+        create_rule(rule: &Rule);
+        drop_rule(rule: &Rule);
+        create_event_trigger(trigger: &EventTrigger);
+        drop_event_trigger(trigger: &EventTrigger);
+        create_constraint_trigger(trigger: &ConstraintTrigger);
+        drop_constraint_trigger(trigger: &ConstraintTrigger);
+        comment_on(object_type: &str, object_name: &str, comment: &str);
+        grant_privileges(privileges: &[String], on_object: &str, to_roles: &[String]);
+        revoke_privileges(privileges: &[String], on_object: &str, from_roles: &[String]);
+        create_role(role: &Role);
+        drop_role(role: &Role);
+        create_tablespace(tablespace: &Tablespace);
+        drop_tablespace(tablespace: &Tablespace);
+        create_publication(publication: &Publication);
+        drop_publication(publication: &Publication);
+        create_composite_type(composite_type: &CompositeType);
+        drop_composite_type(composite_type: &CompositeType);
+        create_range_type(range_type: &RangeType);
+        drop_range_type(range_type: &RangeType);
+        create_subscription(subscription: &Subscription);
+        drop_subscription(subscription: &Subscription);
+        create_foreign_table(foreign_table: &ForeignTable);
+        drop_foreign_table(foreign_table: &ForeignTable);
+        create_foreign_data_wrapper(fdw: &ForeignDataWrapper);
+        drop_foreign_data_wrapper(fdw: &ForeignDataWrapper);
+    }
+}
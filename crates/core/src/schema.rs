@@ -1,5 +1,6 @@
+use crate::normalize::{canonical_type_name, normalize_default_expression};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Schema {
@@ -33,6 +34,7 @@ pub struct Schema {
     pub base_types: HashMap<String, BaseType>,
     pub array_types: HashMap<String, ArrayType>,
     pub multirange_types: HashMap<String, MultirangeType>,
+    pub security_labels: HashMap<String, SecurityLabel>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -54,6 +56,13 @@ pub struct Table {
     pub inherits: Vec<String>,
     pub partition_by: Option<PartitionBy>,
     pub storage_parameters: HashMap<String, String>,
+    pub grants: Vec<Grant>,    // Added: privileges granted on the table
+    pub owner: Option<String>, // Added: owning role
+    pub unlogged: bool,        // Added: UNLOGGED persistence
+    pub partition_bound: Option<String>, // Added: FOR VALUES ... or DEFAULT when a partition
+    pub row_security: bool,    // Added: ENABLE ROW LEVEL SECURITY
+    pub force_row_security: bool, // Added: FORCE ROW LEVEL SECURITY, policies also bind the owner
+    pub of_type: Option<String>, // Added: composite type of a typed table (CREATE TABLE ... OF)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -65,6 +74,8 @@ pub struct View {
     pub comment: Option<String>,
     pub security_barrier: bool, // Added: security barrier views
     pub columns: Vec<String>,   // Added: explicit column list
+    pub owner: Option<String>,  // Added: owning role
+    pub column_comments: HashMap<String, String>, // Added: comments keyed by column name
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -78,6 +89,8 @@ pub struct MaterializedView {
     pub storage_parameters: HashMap<String, String>, // Added: WITH parameters
     pub indexes: Vec<Index>,        // Added: materialized view indexes
     pub populate_with_data: bool,   // Added: controls WITH DATA vs WITH NO DATA
+    pub column_comments: HashMap<String, String>, // Added: comments keyed by column name
+    pub owner: Option<String>,      // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -95,6 +108,7 @@ pub struct Function {
     pub parallel_safety: ParallelSafety, // Added: parallel execution safety
     pub cost: Option<f64>,      // Added: execution cost hint
     pub rows: Option<f64>,      // Added: rows estimate for set-returning functions
+    pub owner: Option<String>,  // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -117,6 +131,7 @@ pub struct Domain {
     pub default: Option<String>,            // Added: default value
     pub not_null: bool,                     // Added: NOT NULL constraint
     pub comment: Option<String>,
+    pub owner: Option<String>, // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -132,6 +147,7 @@ pub struct Sequence {
     pub cycle: bool,
     pub owned_by: Option<String>, // Added: OWNED BY column
     pub comment: Option<String>,
+    pub owner: Option<String>, // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -171,6 +187,7 @@ pub struct Trigger {
     pub schema: Option<String>,
     pub timing: TriggerTiming,
     pub events: Vec<TriggerEvent>,
+    pub update_columns: Vec<String>, // Added: UPDATE OF column list
     pub function: String,
     pub arguments: Vec<String>,
     pub condition: Option<String>, // Added: WHEN condition
@@ -189,6 +206,7 @@ pub struct Policy {
     pub roles: Vec<String>,
     pub using: Option<String>,
     pub check: Option<String>,
+    pub comment: Option<String>, // Added: COMMENT ON POLICY
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -218,6 +236,7 @@ pub struct Collation {
     pub lc_ctype: Option<String>,    // Enhanced: separate LC_CTYPE
     pub provider: CollationProvider, // Enhanced: structured provider
     pub deterministic: bool,         // Added: deterministic flag
+    pub comment: Option<String>,     // Added: COMMENT ON COLLATION
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -229,6 +248,7 @@ pub struct Rule {
     pub instead: bool,
     pub condition: Option<String>, // Added: WHERE condition
     pub actions: Vec<String>,      // Enhanced: multiple actions
+    pub comment: Option<String>,   // Added: COMMENT ON RULE
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -256,6 +276,7 @@ pub struct RangeType {
     pub subtype_diff: Option<String>,
     pub multirange_type_name: Option<String>, // Added: multirange type
     pub comment: Option<String>,
+    pub owner: Option<String>, // Added: owning role
 }
 
 // New structures for additional PostgreSQL objects
@@ -288,10 +309,12 @@ pub struct Role {
     pub inherit: bool,
     pub login: bool,
     pub replication: bool,
+    pub bypass_rls: bool, // Added: BYPASSRLS attribute
     pub connection_limit: Option<i32>,
     pub password: Option<String>,
     pub valid_until: Option<String>,
     pub member_of: Vec<String>,
+    pub settings: Vec<(String, String)>, // Added: ALTER ROLE ... SET parameters, in catalog order
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -303,6 +326,15 @@ pub struct Tablespace {
     pub comment: Option<String>,
 }
 
+/// A `SECURITY LABEL` a label provider such as sepgsql attached to an object
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SecurityLabel {
+    pub object_type: String, // e.g. `table`, `column`, `materialized view`
+    pub object_name: String, // already quoted, e.g. `public."Accounts".email`
+    pub provider: String,
+    pub label: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ForeignTable {
     pub name: String,
@@ -333,6 +365,8 @@ pub struct Column {
     pub collation: Option<String>,      // Added: column-level collation
     pub storage: Option<ColumnStorage>, // Added: storage type
     pub compression: Option<String>,    // Added: compression method
+    pub inherited: bool,                // Added: inherited from a parent with no local definition
+    pub grants: Vec<Grant>,             // Added: privileges granted on this column only
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -353,6 +387,14 @@ pub struct Index {
     pub where_clause: Option<String>, // Added: partial index condition
     pub tablespace: Option<String>,   // Added: tablespace assignment
     pub storage_parameters: HashMap<String, String>, // Added: WITH parameters
+    pub include: Vec<String>,         // Added: INCLUDE columns
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Grant {
+    pub grantee: String,
+    pub privileges: Vec<String>,
+    pub with_grant_option: bool, // Added: grantee may re-grant the privileges
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -555,6 +597,7 @@ pub struct EnumType {
     pub schema: Option<String>,
     pub values: Vec<String>,
     pub comment: Option<String>,
+    pub owner: Option<String>, // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -565,6 +608,7 @@ pub struct CompositeType {
     pub comment: Option<String>,
     pub attributes: Vec<Column>,
     pub definition: Option<String>,
+    pub owner: Option<String>, // Added: owning role
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -635,6 +679,7 @@ impl Schema {
             base_types: HashMap::new(),
             array_types: HashMap::new(),
             multirange_types: HashMap::new(),
+            security_labels: HashMap::new(),
         }
     }
 
@@ -644,4 +689,202 @@ impl Schema {
             ..Self::new()
         }
     }
+
+    /// Sequences neither owned by a column nor used by any column default,
+    /// typically left behind by dropped serial columns
+    pub fn unused_sequences(&self) -> Vec<&Sequence> {
+        let referenced: HashSet<String> = self
+            .tables
+            .values()
+            .flat_map(|table| &table.columns)
+            .filter_map(|column| column.default.as_deref())
+            .flat_map(nextval_targets)
+            .collect();
+
+        let mut unused: Vec<&Sequence> = self
+            .sequences
+            .values()
+            .filter(|seq| seq.owned_by.is_none())
+            .filter(|seq| {
+                let name = QualifiedName::new(seq.schema.clone(), seq.name.as_str());
+                !referenced.contains(&name.to_string())
+            })
+            .collect();
+        unused.sort_by_key(|seq| (seq.schema.clone(), seq.name.clone()));
+        unused
+    }
+
+    /// Copy of the schema in canonical form, so that schemas which only differ
+    /// in how they spell types and defaults or in the order of unordered lists
+    /// (constraints, indexes, grants, policy roles) compare and serialize equal
+    pub fn normalize(&self) -> Schema {
+        let mut schema = self.clone();
+
+        for table in schema.tables.values_mut() {
+            for column in &mut table.columns {
+                column.type_name = canonical_type_name(&column.type_name);
+                column.default = column.default.as_deref().map(normalize_default_expression);
+                normalize_grants(&mut column.grants);
+            }
+            table.constraints.sort_by(|a, b| a.name.cmp(&b.name));
+            table.indexes.sort_by(|a, b| a.name.cmp(&b.name));
+            normalize_grants(&mut table.grants);
+        }
+        for domain in schema.domains.values_mut() {
+            domain.base_type = canonical_type_name(&domain.base_type);
+            domain.default = domain.default.as_deref().map(normalize_default_expression);
+        }
+        for policy in schema.policies.values_mut() {
+            policy.roles.sort();
+        }
+
+        schema
+    }
+}
+
+/// Sort grants by grantee and each grant's privileges by name
+fn normalize_grants(grants: &mut [Grant]) {
+    for grant in grants.iter_mut() {
+        grant.privileges.sort();
+    }
+    grants.sort_by(|a, b| a.grantee.cmp(&b.grantee));
+}
+
+/// Sequences passed to `nextval` in a default expression, rendered like
+/// `QualifiedName`'s `Display`
+fn nextval_targets(default: &str) -> Vec<String> {
+    default
+        .split("nextval('")
+        .skip(1)
+        .filter_map(|rest| rest.split('\'').next())
+        .map(|target| {
+            let parts: Vec<String> = target.split('.').map(|p| p.replace('"', "")).collect();
+            match parts.as_slice() {
+                [schema, name] => QualifiedName::new(Some(schema.clone()), name.as_str()),
+                _ => QualifiedName::new(None, parts.join(".")),
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+/// Types of the parameters a caller passes, comma separated
+fn argument_types(parameters: &[Parameter]) -> String {
+    let arguments: Vec<&str> = parameters
+        .iter()
+        .filter(|p| p.mode != ParameterMode::Out)
+        .map(|p| p.type_name.as_str())
+        .collect();
+    arguments.join(", ")
+}
+
+impl Procedure {
+    /// Input argument types, comma separated, as `COMMENT ON PROCEDURE` takes them
+    pub fn argument_types(&self) -> String {
+        argument_types(&self.parameters)
+    }
+}
+
+impl Function {
+    /// Name and input argument types, e.g. `add(integer, integer)`. Overloads
+    /// share a name, so this is what identifies a function within its schema.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, self.argument_types())
+    }
+
+    /// Input argument types, comma separated, as `COMMENT ON FUNCTION` takes them
+    pub fn argument_types(&self) -> String {
+        argument_types(&self.parameters)
+    }
+
+    /// Volatility, strictness, security, parallel safety, cost and row clauses
+    /// that differ from PostgreSQL's defaults, each with a leading space
+    pub fn attributes(&self) -> String {
+        let mut sql = String::new();
+        match self.volatility {
+            Volatility::Immutable => sql.push_str(" IMMUTABLE"),
+            Volatility::Stable => sql.push_str(" STABLE"),
+            Volatility::Volatile => {}
+        }
+        if self.strict {
+            sql.push_str(" STRICT");
+        }
+        if self.security_definer {
+            sql.push_str(" SECURITY DEFINER");
+        }
+        match self.parallel_safety {
+            ParallelSafety::Safe => sql.push_str(" PARALLEL SAFE"),
+            ParallelSafety::Restricted => sql.push_str(" PARALLEL RESTRICTED"),
+            ParallelSafety::Unsafe => {}
+        }
+        // C and internal functions default to a cost of 1, every other language to 100
+        let default_cost = match self.language.to_lowercase().as_str() {
+            "c" | "internal" => 1.0,
+            _ => 100.0,
+        };
+        if let Some(cost) = self.cost.filter(|cost| *cost != default_cost) {
+            sql.push_str(&format!(" COST {}", cost));
+        }
+        // ROWS only applies to set-returning functions
+        let returns_set = matches!(self.returns.kind, ReturnKind::SetOf | ReturnKind::Table);
+        if let Some(rows) = self.rows.filter(|rows| returns_set && *rows != 1000.0) {
+            sql.push_str(&format!(" ROWS {}", rows));
+        }
+        sql
+    }
+}
+
+/// A possibly schema-qualified object name.
+///
+/// The `public` schema is elided when rendering since it is on the default
+/// search path, so `public.users` and `users` render the same.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct QualifiedName {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+impl QualifiedName {
+    pub fn new(schema: Option<String>, name: impl Into<String>) -> Self {
+        Self {
+            schema,
+            name: name.into(),
+        }
+    }
+
+    /// Render the name as SQL, double-quoting each part when `quote` is set
+    pub fn to_sql(&self, quote: bool) -> String {
+        let part = |ident: &str| {
+            if quote {
+                format!("\"{}\"", ident.replace('"', "\"\""))
+            } else {
+                ident.to_string()
+            }
+        };
+        match self.schema.as_deref() {
+            Some(schema) if schema != "public" => format!("{}.{}", part(schema), part(&self.name)),
+            _ => part(&self.name),
+        }
+    }
+
+    /// Split a possibly schema-qualified, unquoted reference such as `archive.orders`
+    pub fn parse(reference: &str) -> Self {
+        match reference.split_once('.') {
+            Some((schema, name)) => Self::new(Some(schema.to_string()), name),
+            None => Self::new(None, reference),
+        }
+    }
+
+    /// Key a schema-scoped object is stored under in `Schema`: the unquoted name,
+    /// qualified outside `public` so same-named objects in different schemas
+    /// don't replace each other
+    pub fn key(schema: &Option<String>, name: &str) -> String {
+        Self::new(schema.clone(), name).to_sql(false)
+    }
+}
+
+impl std::fmt::Display for QualifiedName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_sql(false))
+    }
 }
@@ -5,6 +5,7 @@ use crate::schema::{
     BaseType, ArrayType, MultirangeType, CompositeType, RangeType, Subscription, ForeignTable, ForeignDataWrapper,
 };
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 /// Database driver trait
@@ -38,6 +39,9 @@ pub trait DatabaseConnection: Send + Sync {
     /// Introspect the database schema
     async fn introspect(&self) -> Result<Schema>;
 
+    /// Introspect the database schema with explicit options
+    async fn introspect_with_options(&self, options: &IntrospectOptions) -> Result<Schema>;
+
     /// Execute SQL statement
     async fn execute(&self, sql: &str) -> Result<()>;
 
@@ -54,6 +58,32 @@ pub trait DatabaseConnection: Send + Sync {
     async fn metadata(&self) -> Result<ConnectionMetadata>;
 }
 
+/// Options controlling schema introspection
+#[derive(Debug, Clone, Default)]
+pub struct IntrospectOptions {
+    /// Maximum number of concurrent introspection queries, derived from the server when unset
+    pub concurrency: Option<usize>,
+    /// Replace passwords in introspected connection strings with `*****`
+    pub redact_secrets: bool,
+    /// Schemas skipped in addition to `pg_catalog`, `information_schema` and `pg_toast`
+    pub exclude_schemas: Vec<String>,
+    /// Keep objects owned by bootstrap roles or installed by extensions
+    pub include_system_objects: bool,
+    /// Report columns backed by their own default sequence as `serial`/`bigserial`
+    /// and leave the sequence out
+    pub prefer_serial: bool,
+}
+
+/// Options controlling generated migration SQL
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Guard column additions and removals with IF [NOT] EXISTS so migrations can be re-run
+    pub idempotent: bool,
+    /// USING expressions for column type changes, keyed by `table.column`;
+    /// other columns are converted with a plain `column::new_type` cast
+    pub column_using: HashMap<String, String>,
+}
+
 /// Transaction trait
 #[async_trait]
 pub trait Transaction: Send + Sync {
@@ -79,6 +109,14 @@ pub trait SqlGenerator: Send + Sync {
     /// Generate ALTER TABLE SQL
     fn generate_alter_table(&self, old: &Table, new: &Table) -> Result<(Vec<String>, Vec<String>)>;
 
+    /// Generate ALTER TABLE SQL with options
+    fn generate_alter_table_with_options(
+        &self,
+        old: &Table,
+        new: &Table,
+        options: &GenerateOptions,
+    ) -> Result<(Vec<String>, Vec<String>)>;
+
     /// Generate DROP TABLE SQL
     fn generate_drop_table(&self, table: &Table) -> Result<String>;
 
@@ -391,6 +429,10 @@ impl DatabaseConnection for Box<dyn DatabaseConnection> {
         self.as_ref().introspect().await
     }
 
+    async fn introspect_with_options(&self, options: &IntrospectOptions) -> Result<Schema> {
+        self.as_ref().introspect_with_options(options).await
+    }
+
     async fn execute(&self, sql: &str) -> Result<()> {
         self.as_ref().execute(sql).await
     }
@@ -0,0 +1,935 @@
+use shem_core::migration::{Migration, format_migration, generate_migration, parse_migration};
+use shem_core::normalize::{canonical_type_name, normalize_default_expression};
+use shem_core::schema::{
+    CheckOption, ParallelSafety, Parameter, ParameterMode, ReturnKind, ReturnType, SortOrder, TriggerEvent, TriggerLevel,
+    TriggerTiming, Volatility,
+};
+use shem_core::{
+    Column, Constraint, ConstraintKind, Domain, DomainConstraint, EnumType, Extension, Function,
+    Grant, Index, IndexColumn, IndexMethod, Schema, Table, Trigger, View,
+};
+use std::collections::HashMap;
+
+/// Test helper building a single-column table with the given type and default
+fn table_with_column(type_name: &str, default: Option<&str>) -> Table {
+    Table {
+        name: "accounts".to_string(),
+        schema: None,
+        columns: vec![Column {
+            name: "value".to_string(),
+            type_name: type_name.to_string(),
+            nullable: true,
+            default: default.map(str::to_string),
+            identity: None,
+            generated: None,
+            comment: None,
+            collation: None,
+            storage: None,
+            compression: None,
+            inherited: false,
+            grants: Vec::new(),
+        }],
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    }
+}
+
+/// Test helper generating the up statements between two single-table schemas
+fn diff_tables(from: Table, to: Table) -> Vec<String> {
+    let mut from_schema = Schema::new();
+    from_schema.tables.insert(from.name.clone(), from);
+    let mut to_schema = Schema::new();
+    to_schema.tables.insert(to.name.clone(), to);
+    generate_migration(&from_schema, &to_schema)
+        .unwrap()
+        .statements
+}
+
+/// Test helper building a domain over integer with the given default and checks
+fn positive_domain(default: Option<&str>, checks: &[(&str, &str)]) -> Domain {
+    Domain {
+        name: "quantity".to_string(),
+        schema: None,
+        base_type: "integer".to_string(),
+        constraints: checks
+            .iter()
+            .map(|(name, check)| DomainConstraint {
+                name: Some(name.to_string()),
+                check: check.to_string(),
+                not_valid: false,
+            })
+            .collect(),
+        default: default.map(str::to_string),
+        not_null: false,
+        comment: None,
+        owner: None,
+    }
+}
+
+/// Test helper generating the up and down statements between two domains
+fn diff_domains(from: Domain, to: Domain) -> (Vec<String>, Vec<String>) {
+    let mut from_schema = Schema::new();
+    from_schema.domains.insert(from.name.clone(), from);
+    let mut to_schema = Schema::new();
+    to_schema.domains.insert(to.name.clone(), to);
+    let migration = generate_migration(&from_schema, &to_schema).unwrap();
+    (migration.statements, migration.rollback_statements)
+}
+
+#[test]
+fn test_now_and_current_timestamp_defaults_are_equal() {
+    let introspected = table_with_column("timestamp with time zone", Some("now()"));
+    let declared = table_with_column("timestamp with time zone", Some("CURRENT_TIMESTAMP"));
+
+    assert!(diff_tables(introspected, declared).is_empty());
+}
+
+#[test]
+fn test_cast_literal_default_equals_plain_literal() {
+    let introspected = table_with_column("user_status", Some("'active'::user_status"));
+    let declared = table_with_column("user_status", Some("'active'"));
+
+    assert!(diff_tables(introspected, declared).is_empty());
+}
+
+#[test]
+fn test_changed_default_is_still_detected() {
+    let introspected = table_with_column("user_status", Some("'active'::user_status"));
+    let declared = table_with_column("user_status", Some("'inactive'"));
+
+    let statements = diff_tables(introspected, declared);
+    assert_eq!(
+        statements,
+        vec!["ALTER TABLE accounts ALTER COLUMN value SET DEFAULT 'inactive';".to_string()]
+    );
+}
+
+#[test]
+fn test_normalize_default_expression() {
+    assert_eq!(normalize_default_expression("CURRENT_TIMESTAMP"), "now()");
+    assert_eq!(
+        normalize_default_expression("transaction_timestamp()"),
+        "now()"
+    );
+    assert_eq!(
+        normalize_default_expression("'x'::character varying"),
+        "'x'"
+    );
+    assert_eq!(normalize_default_expression("'{}'::text[]"), "'{}'");
+    assert_eq!(normalize_default_expression("'-1'::integer"), "-1");
+    assert_eq!(normalize_default_expression("((0))"), "0");
+    assert_eq!(
+        normalize_default_expression("nextval('accounts_id_seq'::regclass)"),
+        "nextval('accounts_id_seq')"
+    );
+    assert_eq!(normalize_default_expression("'It''s'::text"), "'It''s'");
+    assert_eq!(normalize_default_expression("TRUE"), "true");
+}
+
+#[test]
+fn test_schema_normalize_ignores_spelling_and_order() {
+    let grant = |grantee: &str, privileges: &[&str]| Grant {
+        grantee: grantee.to_string(),
+        privileges: privileges.iter().map(|p| p.to_string()).collect(),
+        with_grant_option: false,
+    };
+    let schema_with = |table: Table| {
+        let mut schema = Schema::new();
+        schema.tables.insert(table.name.clone(), table);
+        schema
+    };
+
+    let mut written = table_with_column("int4", Some("(0)"));
+    written.grants = vec![
+        grant("writer", &["UPDATE", "INSERT"]),
+        grant("reader", &["SELECT"]),
+    ];
+    let mut introspected = table_with_column("integer", Some("0"));
+    introspected.grants = vec![
+        grant("reader", &["SELECT"]),
+        grant("writer", &["INSERT", "UPDATE"]),
+    ];
+    assert_eq!(
+        schema_with(written).normalize(),
+        schema_with(introspected.clone()).normalize()
+    );
+
+    let mut changed = introspected.clone();
+    changed.columns[0].type_name = "bigint".to_string();
+    assert_ne!(
+        schema_with(changed).normalize(),
+        schema_with(introspected).normalize()
+    );
+}
+
+#[test]
+fn test_type_aliases_do_not_produce_changes() {
+    for (introspected, declared) in [
+        ("integer", "int4"),
+        ("character varying(255)", "varchar(255)"),
+        ("boolean", "bool"),
+        ("timestamp with time zone", "TIMESTAMPTZ"),
+        ("integer[]", "int4[]"),
+    ] {
+        let statements = diff_tables(
+            table_with_column(introspected, None),
+            table_with_column(declared, None),
+        );
+        assert!(
+            statements.is_empty(),
+            "{} vs {} produced {:?}",
+            introspected,
+            declared,
+            statements
+        );
+    }
+}
+
+#[test]
+fn test_type_change_is_still_detected() {
+    let statements = diff_tables(
+        table_with_column("character varying(255)", None),
+        table_with_column("varchar(100)", None),
+    );
+    assert_eq!(
+        statements,
+        vec!["ALTER TABLE accounts ALTER COLUMN value TYPE varchar(100);".to_string()]
+    );
+}
+
+#[test]
+fn test_canonical_type_name() {
+    assert_eq!(canonical_type_name("int4"), "integer");
+    assert_eq!(canonical_type_name("INTEGER"), "integer");
+    assert_eq!(
+        canonical_type_name("varchar(255)"),
+        "character varying(255)"
+    );
+    assert_eq!(canonical_type_name("bool"), "boolean");
+    assert_eq!(
+        canonical_type_name("timestamp(3) with time zone"),
+        canonical_type_name("timestamptz(3)")
+    );
+    assert_eq!(canonical_type_name("user_status"), "user_status");
+}
+
+#[test]
+fn test_domain_check_constraint_is_added_in_place() {
+    let (up, down) = diff_domains(
+        positive_domain(None, &[("positive", "CHECK ((VALUE > 0))")]),
+        positive_domain(
+            None,
+            &[
+                ("positive", "CHECK ((VALUE > 0))"),
+                ("below_limit", "VALUE < 1000"),
+            ],
+        ),
+    );
+    assert_eq!(
+        up,
+        vec!["ALTER DOMAIN \"quantity\" ADD CONSTRAINT \"below_limit\" CHECK (VALUE < 1000);".to_string()]
+    );
+    assert_eq!(
+        down,
+        vec!["ALTER DOMAIN \"quantity\" DROP CONSTRAINT \"below_limit\";".to_string()]
+    );
+}
+
+#[test]
+fn test_domain_changed_check_is_dropped_and_re_added() {
+    let (up, _) = diff_domains(
+        positive_domain(None, &[("positive", "CHECK ((VALUE > 0))")]),
+        positive_domain(None, &[("positive", "CHECK ((VALUE >= 0))")]),
+    );
+    assert_eq!(
+        up,
+        vec![
+            "ALTER DOMAIN \"quantity\" DROP CONSTRAINT \"positive\";".to_string(),
+            "ALTER DOMAIN \"quantity\" ADD CONSTRAINT \"positive\" CHECK ((VALUE >= 0));".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_domain_default_change_uses_alter_domain() {
+    let (up, down) = diff_domains(
+        positive_domain(Some("1"), &[]),
+        positive_domain(Some("5"), &[]),
+    );
+    assert_eq!(up, vec!["ALTER DOMAIN \"quantity\" SET DEFAULT 5;".to_string()]);
+    assert_eq!(
+        down,
+        vec!["ALTER DOMAIN \"quantity\" SET DEFAULT 1;".to_string()]
+    );
+
+    let (up, _) = diff_domains(positive_domain(Some("1"), &[]), positive_domain(None, &[]));
+    assert_eq!(up, vec!["ALTER DOMAIN \"quantity\" DROP DEFAULT;".to_string()]);
+
+    let (up, _) = diff_domains(
+        positive_domain(Some("'1'::integer"), &[]),
+        positive_domain(Some("1"), &[]),
+    );
+    assert!(up.is_empty());
+}
+
+#[test]
+fn test_domain_not_null_change_uses_alter_domain() {
+    let mut required = positive_domain(None, &[]);
+    required.not_null = true;
+    let (up, down) = diff_domains(positive_domain(None, &[]), required);
+    assert_eq!(up, vec!["ALTER DOMAIN \"quantity\" SET NOT NULL;".to_string()]);
+    assert_eq!(
+        down,
+        vec!["ALTER DOMAIN \"quantity\" DROP NOT NULL;".to_string()]
+    );
+}
+
+/// Test helper diffing two versions of the `status` enum
+fn diff_enums(from: &[&str], to: &[&str]) -> shem_core::Result<Migration> {
+    let status = |values: &[&str]| EnumType {
+        name: "status".to_string(),
+        schema: None,
+        values: values.iter().map(|v| v.to_string()).collect(),
+        comment: None,
+        owner: None,
+    };
+    let mut from_schema = Schema::new();
+    from_schema
+        .enums
+        .insert("status".to_string(), status(from));
+    let mut to_schema = Schema::new();
+    to_schema.enums.insert("status".to_string(), status(to));
+    generate_migration(&from_schema, &to_schema)
+}
+
+#[test]
+fn test_enum_appended_value_uses_add_value() {
+    let migration =
+        diff_enums(&["draft", "published"], &["draft", "published", "archived"]).unwrap();
+    assert_eq!(
+        migration.statements,
+        vec!["ALTER TYPE \"status\" ADD VALUE IF NOT EXISTS 'archived';".to_string()]
+    );
+
+    // The value can't be dropped again, which the down section says
+    assert_eq!(
+        migration.rollback_statements,
+        vec![
+            "-- Enum status keeps the added value(s) 'archived', PostgreSQL can't drop enum values"
+                .to_string()
+        ]
+    );
+    let reparsed = parse_migration(&format_migration(&migration));
+    assert!(reparsed.rollback_statements.is_empty());
+}
+
+#[test]
+fn test_enum_inserted_values_keep_their_position() {
+    let up = diff_enums(
+        &["draft", "published"],
+        &["draft", "in_review", "approved", "published", "archived"],
+    )
+    .unwrap()
+    .statements;
+    assert_eq!(
+        up,
+        vec![
+            "ALTER TYPE \"status\" ADD VALUE IF NOT EXISTS 'in_review' BEFORE 'published';".to_string(),
+            "ALTER TYPE \"status\" ADD VALUE IF NOT EXISTS 'approved' BEFORE 'published';".to_string(),
+            "ALTER TYPE \"status\" ADD VALUE IF NOT EXISTS 'archived';".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_enum_removed_or_reordered_values_are_rejected() {
+    let removed = diff_enums(&["draft", "published"], &["draft"]).unwrap_err();
+    assert!(removed.to_string().contains("drops value(s) published"));
+
+    let reordered = diff_enums(&["draft", "published"], &["published", "draft"]).unwrap_err();
+    assert!(reordered.to_string().contains("reorders its existing values"));
+}
+
+#[test]
+fn test_extension_version_change_emits_update() {
+    let extension = |version: &str| Extension {
+        name: "hstore".to_string(),
+        schema: None,
+        version: version.to_string(),
+        cascade: false,
+        comment: None,
+    };
+    let mut from_schema = Schema::new();
+    from_schema
+        .extensions
+        .insert("hstore".to_string(), extension("1.7"));
+    let mut to_schema = Schema::new();
+    to_schema
+        .extensions
+        .insert("hstore".to_string(), extension("1.8"));
+
+    let migration = generate_migration(&from_schema, &to_schema).unwrap();
+    assert_eq!(
+        migration.statements,
+        vec!["ALTER EXTENSION \"hstore\" UPDATE TO '1.8';".to_string()]
+    );
+    assert_eq!(
+        migration.rollback_statements,
+        vec!["ALTER EXTENSION \"hstore\" UPDATE TO '1.7';".to_string()]
+    );
+
+    // Leaving the version unspecified accepts whatever is installed
+    to_schema
+        .extensions
+        .insert("hstore".to_string(), extension(""));
+    let migration = generate_migration(&from_schema, &to_schema).unwrap();
+    assert!(migration.statements.is_empty());
+}
+
+#[test]
+fn test_added_index_keeps_operator_class() {
+    let mut indexed = table_with_column("text", None);
+    indexed.indexes.push(Index {
+        name: "accounts_value_trgm_idx".to_string(),
+        columns: vec![IndexColumn {
+            name: "value".to_string(),
+            expression: None,
+            order: SortOrder::Ascending,
+            nulls_first: false,
+            opclass: Some("gin_trgm_ops".to_string()),
+        }],
+        unique: false,
+        method: IndexMethod::Gin,
+        where_clause: None,
+        tablespace: None,
+        storage_parameters: HashMap::new(),
+        include: vec![],
+    });
+
+    let statements = diff_tables(table_with_column("text", None), indexed);
+    assert_eq!(
+        statements,
+        vec![
+            "CREATE INDEX accounts_value_trgm_idx ON accounts USING gin (value gin_trgm_ops);"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_table_and_index_fillfactor_are_emitted() {
+    let mut table = table_with_column("integer", None);
+    table
+        .storage_parameters
+        .insert("fillfactor".to_string(), "70".to_string());
+    let mut to_schema = Schema::new();
+    to_schema.tables.insert(table.name.clone(), table.clone());
+    let migration = generate_migration(&Schema::new(), &to_schema).unwrap();
+    assert!(migration.statements[0].ends_with(") WITH (fillfactor = 70);"));
+
+    let mut indexed = table.clone();
+    indexed.indexes.push(Index {
+        name: "accounts_value_idx".to_string(),
+        columns: vec![IndexColumn {
+            name: "value".to_string(),
+            expression: None,
+            order: SortOrder::Ascending,
+            nulls_first: false,
+            opclass: None,
+        }],
+        unique: false,
+        method: IndexMethod::Btree,
+        where_clause: None,
+        tablespace: None,
+        storage_parameters: HashMap::from([("fillfactor".to_string(), "90".to_string())]),
+        include: vec![],
+    });
+    assert_eq!(
+        diff_tables(table, indexed),
+        vec![
+            "CREATE INDEX accounts_value_idx ON accounts USING btree (value) WITH (fillfactor = 90);"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_trigger_level_and_when_condition_are_emitted() {
+    let trigger = |name: &str, for_each: TriggerLevel, when: Option<&str>| Trigger {
+        name: name.to_string(),
+        table: "accounts".to_string(),
+        schema: Some("billing".to_string()),
+        timing: TriggerTiming::After,
+        events: vec![TriggerEvent::Update],
+        update_columns: vec![],
+        function: "log_change".to_string(),
+        arguments: vec![],
+        condition: None,
+        for_each,
+        comment: None,
+        when: when.map(str::to_string),
+    };
+    let mut to_schema = Schema::new();
+    for trigger in [
+        trigger("accounts_audit", TriggerLevel::Statement, None),
+        trigger(
+            "accounts_balance",
+            TriggerLevel::Row,
+            Some("OLD.balance IS DISTINCT FROM NEW.balance"),
+        ),
+    ] {
+        to_schema.triggers.insert(trigger.name.clone(), trigger);
+    }
+
+    let statements = generate_migration(&Schema::new(), &to_schema)
+        .unwrap()
+        .statements;
+    assert_eq!(
+        statements,
+        vec![
+            "CREATE TRIGGER \"accounts_audit\" AFTER UPDATE ON \"billing\".\"accounts\" FOR EACH STATEMENT \
+             EXECUTE FUNCTION log_change();"
+                .to_string(),
+            "CREATE TRIGGER \"accounts_balance\" AFTER UPDATE ON \"billing\".\"accounts\" FOR EACH ROW \
+             WHEN (OLD.balance IS DISTINCT FROM NEW.balance) EXECUTE FUNCTION log_change();"
+                .to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_table_comment_changes_are_emitted() {
+    let commented = |comment: Option<&str>| {
+        let mut table = table_with_column("integer", None);
+        table.comment = comment.map(str::to_string);
+        table
+    };
+
+    let mut from_schema = Schema::new();
+    from_schema
+        .tables
+        .insert("accounts".to_string(), commented(Some("Customer accounts")));
+    let mut to_schema = Schema::new();
+    to_schema.tables.insert(
+        "accounts".to_string(),
+        commented(Some("Customers' accounts")),
+    );
+    let migration = generate_migration(&from_schema, &to_schema).unwrap();
+    assert_eq!(
+        migration.statements,
+        vec!["COMMENT ON TABLE \"accounts\" IS 'Customers'' accounts';".to_string()]
+    );
+    assert_eq!(
+        migration.rollback_statements,
+        vec!["COMMENT ON TABLE \"accounts\" IS 'Customer accounts';".to_string()]
+    );
+
+    assert_eq!(
+        diff_tables(commented(Some("Customer accounts")), commented(None)),
+        vec!["COMMENT ON TABLE \"accounts\" IS NULL;".to_string()]
+    );
+}
+
+#[test]
+fn test_column_comment_changes_are_emitted() {
+    let mut commented = table_with_column("integer", None);
+    commented.columns[0].comment = Some("Balance in cents".to_string());
+
+    assert_eq!(
+        diff_tables(table_with_column("integer", None), commented.clone()),
+        vec!["COMMENT ON COLUMN \"accounts\".\"value\" IS 'Balance in cents';".to_string()]
+    );
+    assert_eq!(
+        diff_tables(commented, table_with_column("integer", None)),
+        vec!["COMMENT ON COLUMN \"accounts\".\"value\" IS NULL;".to_string()]
+    );
+}
+
+#[test]
+fn test_removed_table_is_dropped() {
+    let mut from_schema = Schema::new();
+    from_schema
+        .tables
+        .insert("accounts".to_string(), table_with_column("integer", None));
+
+    let migration = generate_migration(&from_schema, &Schema::new()).unwrap();
+    assert_eq!(
+        migration.statements,
+        vec!["DROP TABLE IF EXISTS accounts CASCADE;".to_string()]
+    );
+    assert_eq!(migration.rollback_statements.len(), 1);
+    assert!(migration.rollback_statements[0].starts_with("CREATE TABLE accounts"));
+}
+
+#[test]
+fn test_parse_migration_down_sections() {
+    let migration = parse_migration(
+        "-- +up\nCREATE TABLE users (id integer);\nCREATE INDEX users_id ON users (id);\n\
+         -- +down\nDROP TABLE users;\n",
+    );
+    assert_eq!(
+        migration.statements,
+        vec![
+            "CREATE TABLE users (id integer)".to_string(),
+            "CREATE INDEX users_id ON users (id)".to_string(),
+        ]
+    );
+    assert_eq!(
+        migration.rollback_statements,
+        vec!["DROP TABLE users".to_string()]
+    );
+
+    // Files written by `shem diff` use the long marker
+    let migration =
+        parse_migration("CREATE TABLE t (id integer);\n-- Down Migration\nDROP TABLE t;");
+    assert_eq!(
+        migration.rollback_statements,
+        vec!["DROP TABLE t".to_string()]
+    );
+
+    let migration = parse_migration("CREATE TABLE t (id integer);");
+    assert!(migration.rollback_statements.is_empty());
+}
+
+#[test]
+fn test_parse_migration_keeps_dollar_quoted_bodies() {
+    let migration = parse_migration(
+        "CREATE FUNCTION one() RETURNS integer LANGUAGE plpgsql AS $$\nBEGIN\n  RETURN 1;\nEND;\n$$;\n\
+         CREATE TABLE \"a;b\" (note text DEFAULT 'x;y');\n",
+    );
+    assert_eq!(
+        migration.statements,
+        vec![
+            "CREATE FUNCTION one() RETURNS integer LANGUAGE plpgsql AS $$\nBEGIN\nRETURN 1;\nEND;\n$$"
+                .to_string(),
+            "CREATE TABLE \"a;b\" (note text DEFAULT 'x;y')".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_initial_migration_creates_dependencies_first() {
+    let mut users = table_with_column("user_status", None);
+    users.name = "users".to_string();
+    let mut posts = table_with_column("integer", None);
+    posts.name = "posts".to_string();
+    posts.constraints.push(Constraint {
+        name: "posts_value_fkey".to_string(),
+        kind: ConstraintKind::ForeignKey {
+            references: "users".to_string(),
+            on_delete: None,
+            on_update: None,
+        },
+        definition: "FOREIGN KEY (value) REFERENCES users(value)".to_string(),
+        deferrable: false,
+        initially_deferred: false,
+    });
+    let view = |name: &str, definition: &str| View {
+        name: name.to_string(),
+        schema: None,
+        definition: definition.to_string(),
+        check_option: CheckOption::None,
+        comment: None,
+        security_barrier: false,
+        columns: vec![],
+        owner: None,
+        column_comments: HashMap::new(),
+    };
+
+    let mut schema = Schema::new();
+    schema.enums.insert(
+        "user_status".to_string(),
+        EnumType {
+            name: "user_status".to_string(),
+            schema: None,
+            values: vec!["active".to_string()],
+            comment: None,
+            owner: None,
+        },
+    );
+    schema.tables.insert("posts".to_string(), posts);
+    schema.tables.insert("users".to_string(), users);
+    // Named so that key order alone would create them the wrong way round
+    schema.views.insert(
+        "a_recent_posts".to_string(),
+        view("a_recent_posts", "SELECT * FROM b_posts_view;"),
+    );
+    schema.views.insert(
+        "b_posts_view".to_string(),
+        view("b_posts_view", "SELECT * FROM posts;"),
+    );
+
+    let migration = generate_migration(&Schema::new(), &schema).unwrap();
+    let creates: Vec<&str> = migration
+        .statements
+        .iter()
+        .map(|s| s.split(" (").next().unwrap().split(" AS ").next().unwrap())
+        .collect();
+    assert_eq!(
+        creates,
+        vec![
+            "CREATE TYPE \"user_status\"",
+            "CREATE TABLE users",
+            "CREATE TABLE posts",
+            "ALTER TABLE posts ADD CONSTRAINT posts_value_fkey FOREIGN KEY",
+            "CREATE VIEW \"b_posts_view\"",
+            "CREATE VIEW \"a_recent_posts\"",
+        ]
+    );
+    assert!(!migration.statements[2].contains("FOREIGN KEY"));
+    assert!(migration.statements[4].ends_with("FROM posts;"));
+
+    // Rolling back undoes the creates in reverse
+    assert_eq!(
+        migration.rollback_statements,
+        vec![
+            "DROP VIEW IF EXISTS \"a_recent_posts\";".to_string(),
+            "DROP VIEW IF EXISTS \"b_posts_view\";".to_string(),
+            "ALTER TABLE posts DROP CONSTRAINT posts_value_fkey;".to_string(),
+            "DROP TABLE IF EXISTS posts CASCADE;".to_string(),
+            "DROP TABLE IF EXISTS users CASCADE;".to_string(),
+            "DROP TYPE IF EXISTS \"user_status\" CASCADE;".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_initial_migration_adds_foreign_keys_after_all_tables() {
+    let foreign_key = |name: &str, references: &str| Constraint {
+        name: name.to_string(),
+        kind: ConstraintKind::ForeignKey {
+            references: references.to_string(),
+            on_delete: None,
+            on_update: None,
+        },
+        definition: format!("FOREIGN KEY (value) REFERENCES {}(value)", references),
+        deferrable: false,
+        initially_deferred: false,
+    };
+    let function = |name: &str, returns: ReturnType, definition: &str| Function {
+        name: name.to_string(),
+        schema: None,
+        parameters: vec![],
+        returns,
+        language: "sql".to_string(),
+        definition: definition.to_string(),
+        comment: None,
+        volatility: Volatility::Volatile,
+        strict: false,
+        security_definer: false,
+        parallel_safety: ParallelSafety::Unsafe,
+        cost: None,
+        rows: None,
+        owner: None,
+    };
+
+    // Each table references the other, and one calls a function for its default
+    let mut authors = table_with_column("integer", Some("next_value()"));
+    authors.name = "authors".to_string();
+    authors
+        .constraints
+        .push(foreign_key("authors_value_fkey", "books"));
+    let mut books = table_with_column("integer", None);
+    books.name = "books".to_string();
+    books
+        .constraints
+        .push(foreign_key("books_value_fkey", "authors"));
+
+    let mut schema = Schema::new();
+    schema.tables.insert("authors".to_string(), authors);
+    schema.tables.insert("books".to_string(), books);
+    schema.functions.insert(
+        "next_value()".to_string(),
+        function(
+            "next_value",
+            ReturnType {
+                kind: ReturnKind::Scalar,
+                type_name: "integer".to_string(),
+                is_set: false,
+            },
+            "SELECT 1",
+        ),
+    );
+    // Named to sort first, but its body and result need the tables
+    schema.functions.insert(
+        "all_books()".to_string(),
+        function(
+            "all_books",
+            ReturnType {
+                kind: ReturnKind::SetOf,
+                type_name: "books".to_string(),
+                is_set: true,
+            },
+            "SELECT * FROM books",
+        ),
+    );
+
+    let migration = generate_migration(&Schema::new(), &schema).unwrap();
+    let heads: Vec<&str> = migration
+        .statements
+        .iter()
+        .map(|s| s.split(" (").next().unwrap().split(" FOREIGN KEY").next().unwrap())
+        .collect();
+    assert_eq!(
+        heads,
+        vec![
+            "CREATE OR REPLACE FUNCTION \"next_value\"",
+            "CREATE TABLE books",
+            "CREATE TABLE authors",
+            "ALTER TABLE books ADD CONSTRAINT books_value_fkey",
+            "ALTER TABLE authors ADD CONSTRAINT authors_value_fkey",
+            "CREATE OR REPLACE FUNCTION \"all_books\"",
+        ]
+    );
+
+    // Constraints are dropped before the tables they point at
+    assert_eq!(
+        migration.rollback_statements[..3],
+        [
+            "DROP FUNCTION IF EXISTS \"all_books\"();".to_string(),
+            "ALTER TABLE authors DROP CONSTRAINT authors_value_fkey;".to_string(),
+            "ALTER TABLE books DROP CONSTRAINT books_value_fkey;".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_case_and_reserved_names_are_quoted() {
+    let mut from_schema = Schema::new();
+    from_schema.views.insert(
+        "Sales.Order Totals".to_string(),
+        View {
+            name: "Order Totals".to_string(),
+            schema: Some("Sales".to_string()),
+            definition: "SELECT 1".to_string(),
+            check_option: CheckOption::None,
+            comment: None,
+            security_barrier: false,
+            columns: vec![],
+            owner: None,
+            column_comments: HashMap::new(),
+        },
+    );
+    from_schema.functions.insert(
+        "user(integer)".to_string(),
+        Function {
+            name: "user".to_string(),
+            schema: None,
+            parameters: vec![Parameter {
+                name: "id".to_string(),
+                type_name: "integer".to_string(),
+                mode: ParameterMode::In,
+                default: None,
+            }],
+            returns: ReturnType {
+                kind: ReturnKind::Scalar,
+                type_name: "text".to_string(),
+                is_set: false,
+            },
+            language: "sql".to_string(),
+            definition: "SELECT 'x'".to_string(),
+            comment: None,
+            volatility: Volatility::Volatile,
+            strict: false,
+            security_definer: false,
+            parallel_safety: ParallelSafety::Unsafe,
+            cost: None,
+            rows: None,
+            owner: None,
+        },
+    );
+    let mut to_schema = Schema::new();
+    let mut table = table_with_column("integer", None);
+    table.name = "Accounts".to_string();
+    table.comment = Some("Mixed case".to_string());
+    to_schema.tables.insert("Accounts".to_string(), table);
+    to_schema.enums.insert(
+        "order".to_string(),
+        EnumType {
+            name: "order".to_string(),
+            schema: None,
+            values: vec!["open".to_string()],
+            comment: None,
+            owner: None,
+        },
+    );
+
+    let migration = generate_migration(&from_schema, &to_schema).unwrap();
+    for expected in [
+        "DROP VIEW IF EXISTS \"Sales\".\"Order Totals\";",
+        "DROP FUNCTION IF EXISTS \"user\"(integer);",
+        "CREATE TYPE \"order\" AS ENUM ('open');",
+        "COMMENT ON TABLE \"Accounts\" IS 'Mixed case';",
+    ] {
+        assert!(
+            migration.statements.iter().any(|s| s == expected),
+            "missing {} in {:?}",
+            expected,
+            migration.statements
+        );
+    }
+    for expected in [
+        "DROP TYPE IF EXISTS \"order\" CASCADE;",
+        "CREATE OR REPLACE FUNCTION \"user\" (id integer)",
+        "CREATE VIEW \"Sales\".\"Order Totals\" AS SELECT 1",
+    ] {
+        assert!(
+            migration
+                .rollback_statements
+                .iter()
+                .any(|s| s.starts_with(expected)),
+            "missing {} in {:?}",
+            expected,
+            migration.rollback_statements
+        );
+    }
+}
+
+#[test]
+fn test_numeric_precision_and_scale_are_preserved() {
+    assert_eq!(canonical_type_name("NUMERIC"), "numeric");
+    assert_eq!(canonical_type_name("NUMERIC(10)"), "numeric(10,0)");
+    assert_eq!(canonical_type_name("decimal(10, 2)"), "numeric(10,2)");
+
+    // numeric(10) is reported by format_type as numeric(10,0)
+    for (introspected, declared) in [
+        ("numeric", "NUMERIC"),
+        ("numeric(10,0)", "NUMERIC(10)"),
+        ("numeric(10,2)", "NUMERIC(10, 2)"),
+    ] {
+        assert!(
+            diff_tables(
+                table_with_column(introspected, None),
+                table_with_column(declared, None),
+            )
+            .is_empty()
+        );
+    }
+
+    // Dropping or changing the modifiers is a real type change
+    for (from, to) in [
+        ("numeric", "numeric(10,2)"),
+        ("numeric(10,2)", "numeric"),
+        ("numeric(10,0)", "numeric(10,2)"),
+    ] {
+        assert_eq!(
+            diff_tables(table_with_column(from, None), table_with_column(to, None)),
+            vec![format!(
+                "ALTER TABLE accounts ALTER COLUMN value TYPE {};",
+                to
+            )]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use shem_core::QualifiedName;
+
+#[test]
+fn test_unqualified_name() {
+    let name = QualifiedName::new(None, "users");
+    assert_eq!(name.to_sql(false), "users");
+    assert_eq!(name.to_sql(true), "\"users\"");
+}
+
+#[test]
+fn test_public_schema_is_elided() {
+    let name = QualifiedName::new(Some("public".to_string()), "users");
+    assert_eq!(name.to_sql(false), "users");
+    assert_eq!(name.to_sql(true), "\"users\"");
+    assert_eq!(name.to_string(), "users");
+}
+
+#[test]
+fn test_other_schema_is_kept() {
+    let name = QualifiedName::new(Some("billing".to_string()), "invoices");
+    assert_eq!(name.to_sql(false), "billing.invoices");
+    assert_eq!(name.to_sql(true), "\"billing\".\"invoices\"");
+}
+
+#[test]
+fn test_quoting_escapes_embedded_quotes() {
+    let name = QualifiedName::new(Some("My Schema".to_string()), "odd\"name");
+    assert_eq!(name.to_sql(true), "\"My Schema\".\"odd\"\"name\"");
+}
@@ -7,6 +7,7 @@ use shared_types::*;
 pub struct SchemaDefinition {
     pub named_schemas: Vec<CreateSchema>,
     pub tables: Vec<CreateTable>,
+    pub indexes: Vec<CreateIndex>, // Indexes on tables not declared in the same schema
     pub views: Vec<CreateView>,
     pub materialized_views: Vec<CreateMaterializedView>,
     pub functions: Vec<CreateFunction>,
@@ -38,6 +39,7 @@ impl SchemaDefinition {
         Self {
             named_schemas: Vec::new(),
             tables: Vec::new(),
+            indexes: Vec::new(),
             views: Vec::new(),
             materialized_views: Vec::new(),
             functions: Vec::new(),
@@ -71,6 +73,7 @@ impl SchemaDefinition {
 pub enum Statement {
     CreateSchema(CreateSchema),
     CreateTable(CreateTable),
+    CreateIndex(CreateIndex),
     CreateView(CreateView),
     CreateMaterializedView(CreateMaterializedView),
     CreateFunction(CreateFunction),
@@ -97,6 +100,7 @@ pub enum Statement {
     CreateForeignKeyConstraint(CreateForeignKeyConstraint),
     AlterTable(AlterTable),
     DropObject(DropObject),
+    Comment(Comment),
 }
 
 // Parser-specific types that extend or modify shared types
@@ -144,6 +148,29 @@ pub struct CreateTable {
     pub with_options: HashMap<String, String>,
     pub tablespace: Option<String>,
     pub comment: Option<String>,
+    pub indexes: Vec<CreateIndex>, // Standalone CREATE INDEX statements on this table
+}
+
+/// Index definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateIndex {
+    pub name: String,
+    pub schema: Option<String>,
+    pub table: String,
+    pub columns: Vec<IndexColumnDefinition>,
+    pub method: String,
+    pub unique: bool,
+    pub where_clause: Option<String>,
+    pub include: Vec<String>,
+}
+
+/// Index key: a column or an expression with its ordering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexColumnDefinition {
+    pub name: Option<String>,
+    pub expression: Option<String>,
+    pub order: Option<SortOrder>,
+    pub nulls_first: Option<bool>,
 }
 
 /// View definition
@@ -569,10 +596,20 @@ pub struct DropObject {
     pub restrict: bool,
 }
 
+/// COMMENT ON statement; `comment` is `None` for `IS NULL`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub object_type: ObjectType,
+    pub object_name: String, // Dotted name; columns are `table.column`, policies and rules `table.name`
+    pub arguments: Option<Vec<DataType>>, // Argument types of a function or procedure, when listed
+    pub comment: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ObjectType {
     Schema,
     Table,
+    Column,
     View,
     MaterializedView,
     Function,
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use shem_core::QualifiedName;
 use std::path::Path;
 
 pub mod ast;
@@ -15,15 +16,86 @@ pub fn parse_file(path: &Path) -> Result<Vec<Statement>> {
 
 /// Parse SQL string into an array of statements (AST - Abstract Syntax Tree)
 pub fn parse_sql(sql: &str) -> Result<Vec<Statement>> {
-    let result = pg_query::parse(sql)?;
+    let result = pg_query::parse(sql).map_err(|e| locate_parse_error(sql, e))?;
     let statements = visitor::parse_statements(&result)?;
     Ok(statements)
 }
 
+/// pg_query reports syntax errors without their position, so find the first
+/// statement that fails on its own and the token the message points at
+fn locate_parse_error(sql: &str, error: pg_query::Error) -> anyhow::Error {
+    let message = match error {
+        pg_query::Error::Parse(message) => message,
+        other => return other.into(),
+    };
+
+    let offset = match pg_query::split_with_scanner(sql) {
+        Ok(statements) => statements
+            .into_iter()
+            .find(|statement| pg_query::parse(statement).is_err())
+            // Statements are slices of `sql`, so their offset is the pointer distance
+            .map(|statement| {
+                statement.as_ptr() as usize - sql.as_ptr() as usize
+                    + offset_in_statement(statement, &message)
+            })
+            .unwrap_or(0),
+        // The scanner fails on the same input, e.g. an unterminated string
+        Err(_) => offset_in_statement(sql, &message),
+    };
+    shem_core::Error::parse_at(sql, offset, message).into()
+}
+
+/// Offset of the token named by `syntax error at or near "..."`, the end for
+/// `at end of input`, and the start of the statement otherwise
+fn offset_in_statement(statement: &str, message: &str) -> usize {
+    let start = statement.len() - statement.trim_start().len();
+    if message.ends_with("at end of input") {
+        return statement.trim_end().len();
+    }
+    let Some((token, _)) = message
+        .split_once("at or near \"")
+        .and_then(|(_, rest)| rest.rsplit_once('"'))
+    else {
+        return start;
+    };
+
+    // Only whole tokens count, e.g. not `id` inside `user_id`
+    let occurrences: Vec<usize> = match pg_query::scan(statement) {
+        Ok(scan) => scan
+            .tokens
+            .iter()
+            .map(|t| (t.start as usize, t.end as usize))
+            .filter(|&(from, to)| statement.get(from..to) == Some(token))
+            .map(|(from, _)| from)
+            .collect(),
+        Err(_) => statement.match_indices(token).map(|(from, _)| from).collect(),
+    };
+    // The token may also appear before the error; the error is at the last
+    // occurrence everything before which still parses up to the end of input
+    occurrences
+        .iter()
+        .copied()
+        .filter(|&from| from >= start)
+        .take_while(|&from| parses_until_end(&statement[..from]))
+        .last()
+        .or_else(|| occurrences.first().copied())
+        .unwrap_or(start)
+}
+
+/// Whether `prefix` is valid SQL, or valid so far and merely cut short
+fn parses_until_end(prefix: &str) -> bool {
+    match pg_query::parse(prefix) {
+        Ok(_) => true,
+        Err(pg_query::Error::Parse(message)) => message.ends_with("at end of input"),
+        Err(_) => false,
+    }
+}
+
 /// Parse SQL into schema definition (Schema Definition Language)
 pub fn parse_schema(sql: &str) -> Result<SchemaDefinition> {
     let statements = parse_sql(sql)?;
     let mut schema = SchemaDefinition::new();
+    let mut alters = Vec::new();
 
     for stmt in statements {
         match stmt {
@@ -41,9 +113,46 @@ pub fn parse_schema(sql: &str) -> Result<SchemaDefinition> {
             Statement::CreateTrigger(create) => schema.triggers.push(create),
             Statement::CreatePolicy(create) => schema.policies.push(create),
             Statement::CreateServer(create) => schema.servers.push(create),
+            Statement::CreateCollation(create) => schema.collations.push(create),
+            Statement::CreateIndex(create) => schema.indexes.push(create),
+            Statement::AlterTable(alter) => alters.push(alter),
             _ => continue,
         }
     }
 
+    // Merge columns and constraints added by ALTER TABLE into the table they extend,
+    // comparing keys so `users` and `public.users` name the same table
+    for alter in alters {
+        let key = QualifiedName::key(&alter.schema, &alter.name);
+        let table = schema
+            .tables
+            .iter_mut()
+            .find(|table| QualifiedName::key(&table.schema, &table.name) == key)
+            .ok_or_else(|| {
+                anyhow::anyhow!("ALTER TABLE references unknown table {}", alter.name)
+            })?;
+        for action in alter.actions {
+            match action {
+                AlterTableAction::AddColumn(column) => table.columns.push(column),
+                AlterTableAction::AddConstraint(constraint) => table.constraints.push(constraint),
+                _ => continue,
+            }
+        }
+    }
+
+    // Attach indexes to the table they are declared on, wherever the table appears
+    let indexes = std::mem::take(&mut schema.indexes);
+    for index in indexes {
+        let key = QualifiedName::key(&index.schema, &index.table);
+        match schema
+            .tables
+            .iter_mut()
+            .find(|table| QualifiedName::key(&table.schema, &table.name) == key)
+        {
+            Some(table) => table.indexes.push(index),
+            None => schema.indexes.push(index),
+        }
+    }
+
     Ok(schema)
 }
\ No newline at end of file
@@ -5,9 +5,11 @@ use pg_query::{
     protobuf::{self, node},
 };
 use shared_types::{
-    CheckOption, DataType, Expression, FunctionBehavior, FunctionParameter, FunctionReturn,
-    Literal, ParallelType, ParameterMode, PolicyCommand, RuleEvent, SecurityType, TableConstraint,
-    TriggerEvent, TriggerWhen,
+    CheckOption, CollationProvider, DataType, ExclusionElement, Expression, ForeignKeyReference,
+    FunctionBehavior,
+    FunctionParameter, FunctionReturn, Literal, ParallelType, ParameterMode, PolicyCommand,
+    ReferentialAction, RuleEvent, SecurityType, SortOrder, TableConstraint, TriggerEvent,
+    TriggerWhen,
 };
 use std::collections::HashMap;
 
@@ -19,6 +21,7 @@ pub fn parse_statements(result: &ParseResult) -> Result<Vec<Statement>> {
         let node = stmt.stmt.as_ref().context("Missing statement node")?;
         let statement = match node.node.as_ref().context("Missing node variant")? {
             node::Node::CreateStmt(stmt) => parse_create_table(stmt)?,
+            node::Node::IndexStmt(stmt) => parse_create_index(stmt)?,
             node::Node::ViewStmt(stmt) => parse_create_view(stmt)?,
             node::Node::CreateFunctionStmt(stmt) => parse_create_function(stmt)?,
             node::Node::CreateEnumStmt(stmt) => parse_create_enum(stmt)?,
@@ -40,9 +43,22 @@ pub fn parse_statements(result: &ParseResult) -> Result<Vec<Statement>> {
             node::Node::CreateFdwStmt(stmt) => parse_create_foreign_data_wrapper(stmt)?,
             node::Node::CreateSubscriptionStmt(stmt) => parse_create_subscription(stmt)?,
             node::Node::CreateTableSpaceStmt(stmt) => parse_create_tablespace(stmt)?,
-            // node::Node::CreateMatViewStmt(stmt) => parse_create_materialized_view(stmt)?,
-            // node::Node::CreateProcedureStmt(stmt) => parse_create_procedure(stmt)?,
-            // node::Node::CreateCollationStmt(stmt) => parse_create_collation(stmt)?,
+            node::Node::CommentStmt(stmt) => match parse_comment(stmt)? {
+                Some(comment) => comment,
+                None => continue,
+            },
+            // CREATE COLLATION is a generic definition statement
+            node::Node::DefineStmt(stmt)
+                if stmt.kind == protobuf::ObjectType::ObjectCollation as i32 =>
+            {
+                parse_create_collation(stmt)?
+            }
+            // CREATE MATERIALIZED VIEW shares its statement with CREATE TABLE AS
+            node::Node::CreateTableAsStmt(stmt)
+                if stmt.objtype == protobuf::ObjectType::ObjectMatview as i32 =>
+            {
+                parse_create_materialized_view(stmt)?
+            }
             _ => continue,
         };
         statements.push(statement);
@@ -112,11 +128,95 @@ fn parse_create_table(stmt: &protobuf::CreateStmt) -> Result<Statement> {
         with_options,
         tablespace: None, // TODO: Parse tablespace
         comment: None,    // TODO: Parse comment
+        indexes: Vec::new(),
     }))
 }
 
+fn parse_create_index(stmt: &protobuf::IndexStmt) -> Result<Statement> {
+    let rel = stmt.relation.as_ref().context("Missing index relation")?;
+    let schema = if !rel.schemaname.is_empty() {
+        Some(rel.schemaname.clone())
+    } else {
+        None
+    };
+
+    let mut columns = Vec::new();
+    for param in &stmt.index_params {
+        if let Some(node::Node::IndexElem(elem)) = &param.node {
+            columns.push(parse_index_elem(elem)?);
+        }
+    }
+
+    let include = stmt
+        .index_including_params
+        .iter()
+        .filter_map(|param| match &param.node {
+            Some(node::Node::IndexElem(elem)) => Some(elem.name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let where_clause = match &stmt.where_clause {
+        Some(expr) => Some(deparse_expression(expr)?),
+        None => None,
+    };
+
+    Ok(Statement::CreateIndex(CreateIndex {
+        name: stmt.idxname.clone(),
+        schema,
+        table: rel.relname.clone(),
+        columns,
+        method: if stmt.access_method.is_empty() {
+            "btree".to_string()
+        } else {
+            stmt.access_method.clone()
+        },
+        unique: stmt.unique,
+        where_clause,
+        include,
+    }))
+}
+
+fn parse_index_elem(elem: &protobuf::IndexElem) -> Result<IndexColumnDefinition> {
+    let expression = match &elem.expr {
+        Some(expr) => Some(deparse_expression(expr)?),
+        None => None,
+    };
+    let order = match protobuf::SortByDir::try_from(elem.ordering) {
+        Ok(protobuf::SortByDir::SortbyAsc) => Some(SortOrder::Asc),
+        Ok(protobuf::SortByDir::SortbyDesc) => Some(SortOrder::Desc),
+        _ => None,
+    };
+    let nulls_first = match protobuf::SortByNulls::try_from(elem.nulls_ordering) {
+        Ok(protobuf::SortByNulls::SortbyNullsFirst) => Some(true),
+        Ok(protobuf::SortByNulls::SortbyNullsLast) => Some(false),
+        _ => None,
+    };
+
+    Ok(IndexColumnDefinition {
+        name: (!elem.name.is_empty()).then(|| elem.name.clone()),
+        expression,
+        order,
+        nulls_first,
+    })
+}
+
+/// Render an expression node back to SQL by deparsing it as `SELECT WHERE <expr>`
+fn deparse_expression(expr: &Node) -> Result<String> {
+    let select = protobuf::SelectStmt {
+        where_clause: Some(Box::new(expr.clone())),
+        ..Default::default()
+    };
+    // Deparsing through the node sets the parse tree version libpg_query asserts on
+    let sql = node::Node::SelectStmt(Box::new(select)).deparse()?;
+    Ok(sql
+        .strip_prefix("SELECT WHERE ")
+        .unwrap_or(&sql)
+        .to_string())
+}
+
 fn parse_create_view(stmt: &protobuf::ViewStmt) -> Result<Statement> {
-    let name = get_qualified_name(stmt.view.as_ref().context("Missing view name")?)?;
+    let view = stmt.view.as_ref().context("Missing view name")?;
     let columns = stmt
         .aliases
         .iter()
@@ -153,8 +253,8 @@ fn parse_create_view(stmt: &protobuf::ViewStmt) -> Result<Statement> {
         _ => None,
     };
     Ok(Statement::CreateView(CreateView {
-        name,
-        schema: None, // TODO: Parse schema
+        name: view.relname.clone(),
+        schema: (!view.schemaname.is_empty()).then(|| view.schemaname.clone()),
         columns,
         query,
         with_options,
@@ -247,6 +347,23 @@ fn parse_create_function(stmt: &protobuf::CreateFunctionStmt) -> Result<Statemen
             "SELECT 1".to_string()
         });
 
+    // CREATE PROCEDURE is the same statement without a return type
+    if stmt.is_procedure {
+        return Ok(Statement::CreateProcedure(CreateProcedure {
+            name,
+            schema,
+            parameters,
+            language,
+            behavior,
+            security,
+            parallel,
+            cost,
+            rows,
+            body,
+            comment: None,
+        }));
+    }
+
     Ok(Statement::CreateFunction(CreateFunction {
         name,
         schema,
@@ -264,6 +381,33 @@ fn parse_create_function(stmt: &protobuf::CreateFunctionStmt) -> Result<Statemen
     }))
 }
 
+fn parse_create_materialized_view(stmt: &protobuf::CreateTableAsStmt) -> Result<Statement> {
+    let into = stmt.into.as_ref().context("Missing materialized view name")?;
+    let rel = into.rel.as_ref().context("Missing materialized view name")?;
+    let columns = into
+        .col_names
+        .iter()
+        .filter_map(|column| match &column.node {
+            Some(node::Node::String(str_val)) => Some(str_val.sval.clone()),
+            _ => None,
+        })
+        .collect();
+    let query = match &stmt.query {
+        Some(query) => query.deparse()?,
+        None => String::new(),
+    };
+
+    Ok(Statement::CreateMaterializedView(CreateMaterializedView {
+        name: rel.relname.clone(),
+        schema: (!rel.schemaname.is_empty()).then(|| rel.schemaname.clone()),
+        columns,
+        query,
+        with_options: parse_with_options(&into.options)?,
+        tablespace: (!into.table_space_name.is_empty()).then(|| into.table_space_name.clone()),
+        comment: None,
+    }))
+}
+
 // Helper functions for parsing
 
 fn get_qualified_name_from_nodes(nodes: &[Node]) -> Result<String> {
@@ -300,7 +444,7 @@ fn parse_column_def(col: &protobuf::ColumnDef) -> Result<(ColumnDefinition, Vec<
         None
     };
 
-    let not_null = col.is_not_null;
+    let mut not_null = col.is_not_null;
 
     // Handle generated column - col.generated is a String
     let generated = if !col.generated.is_empty() {
@@ -326,9 +470,10 @@ fn parse_column_def(col: &protobuf::ColumnDef) -> Result<(ColumnDefinition, Vec<
     let mut inline_constraints = Vec::new();
     for constraint_node in &col.constraints {
         if let Some(node::Node::Constraint(constraint)) = &constraint_node.node {
-            match constraint.contype {
-                1 => {
-                    // PRIMARY KEY
+            match protobuf::ConstrType::try_from(constraint.contype) {
+                // The raw parse tree carries NOT NULL as a constraint, not in is_not_null
+                Ok(protobuf::ConstrType::ConstrNotnull) => not_null = true,
+                Ok(protobuf::ConstrType::ConstrPrimary) => {
                     inline_constraints.push(TableConstraint::PrimaryKey {
                         columns: vec![name.clone()],
                         name: if constraint.conname.is_empty() {
@@ -336,10 +481,11 @@ fn parse_column_def(col: &protobuf::ColumnDef) -> Result<(ColumnDefinition, Vec<
                         } else {
                             Some(constraint.conname.clone())
                         },
+                        deferrable: constraint.deferrable,
+                        initially_deferred: constraint.initdeferred,
                     });
                 }
-                2 => {
-                    // UNIQUE
+                Ok(protobuf::ConstrType::ConstrUnique) => {
                     inline_constraints.push(TableConstraint::Unique {
                         columns: vec![name.clone()],
                         name: if constraint.conname.is_empty() {
@@ -347,6 +493,8 @@ fn parse_column_def(col: &protobuf::ColumnDef) -> Result<(ColumnDefinition, Vec<
                         } else {
                             Some(constraint.conname.clone())
                         },
+                        deferrable: constraint.deferrable,
+                        initially_deferred: constraint.initdeferred,
                     });
                 }
                 _ => {}
@@ -376,7 +524,15 @@ fn parse_data_type(type_name: &protobuf::TypeName) -> Result<DataType> {
         }
     }
     let name = name_parts.join(".");
-    match name.as_str() {
+    // SQL-standard spellings such as NUMERIC come back as pg_catalog.numeric
+    match name.strip_prefix("pg_catalog.").unwrap_or(&name) {
+        "numeric" | "decimal" => {
+            let typmods = parse_typmods(type_name);
+            Ok(DataType::Numeric(
+                typmods.first().copied(),
+                typmods.get(1).copied(),
+            ))
+        }
         "serial" => Ok(DataType::Serial),
         "bigserial" => Ok(DataType::BigSerial),
         "smallserial" => Ok(DataType::SmallSerial),
@@ -399,6 +555,21 @@ fn parse_data_type(type_name: &protobuf::TypeName) -> Result<DataType> {
     }
 }
 
+/// Integer type modifiers, e.g. `[10, 2]` for `numeric(10, 2)`
+fn parse_typmods(type_name: &protobuf::TypeName) -> Vec<u32> {
+    type_name
+        .typmods
+        .iter()
+        .filter_map(|typmod| match &typmod.node {
+            Some(node::Node::AConst(constant)) => match &constant.val {
+                Some(protobuf::a_const::Val::Ival(integer)) => u32::try_from(integer.ival).ok(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 fn parse_expression(_expr: &protobuf::Node) -> Result<Expression> {
     // TODO: Implement full expression parsing
     Ok(Expression::Literal(Literal::Null)) // Placeholder
@@ -433,38 +604,122 @@ fn parse_table_constraint(constraint: &protobuf::Constraint) -> Result<TableCons
             None
         }
     }
-    match constraint.contype {
-        1 => {
-            // PRIMARY KEY
+    let name = if constraint.conname.is_empty() {
+        None
+    } else {
+        Some(constraint.conname.clone())
+    };
+    match protobuf::ConstrType::try_from(constraint.contype) {
+        Ok(protobuf::ConstrType::ConstrPrimary) => {
             let columns = constraint.keys.iter().filter_map(node_to_string).collect();
             Ok(TableConstraint::PrimaryKey {
                 columns,
-                name: if constraint.conname.is_empty() {
-                    None
-                } else {
-                    Some(constraint.conname.clone())
-                },
+                name,
+                deferrable: constraint.deferrable,
+                initially_deferred: constraint.initdeferred,
             })
         }
-        2 => {
-            // UNIQUE
+        Ok(protobuf::ConstrType::ConstrUnique) => {
             let columns = constraint.keys.iter().filter_map(node_to_string).collect();
             Ok(TableConstraint::Unique {
                 columns,
-                name: if constraint.conname.is_empty() {
-                    None
-                } else {
-                    Some(constraint.conname.clone())
+                name,
+                deferrable: constraint.deferrable,
+                initially_deferred: constraint.initdeferred,
+            })
+        }
+        Ok(protobuf::ConstrType::ConstrForeign) => {
+            let pktable = constraint
+                .pktable
+                .as_ref()
+                .context("Missing referenced table for FOREIGN KEY")?;
+            let columns = constraint
+                .fk_attrs
+                .iter()
+                .filter_map(node_to_string)
+                .collect();
+            let referenced = constraint
+                .pk_attrs
+                .iter()
+                .filter_map(node_to_string)
+                .collect();
+            Ok(TableConstraint::ForeignKey {
+                columns,
+                references: ForeignKeyReference {
+                    table: get_qualified_name(pktable)?,
+                    columns: referenced,
+                    on_delete: parse_referential_action(&constraint.fk_del_action),
+                    on_update: parse_referential_action(&constraint.fk_upd_action),
                 },
+                name,
+            })
+        }
+        Ok(protobuf::ConstrType::ConstrCheck) => {
+            let expression = match &constraint.raw_expr {
+                Some(expr) => parse_expression(expr)?,
+                None => Expression::Literal(Literal::Null),
+            };
+            Ok(TableConstraint::Check { expression, name })
+        }
+        Ok(protobuf::ConstrType::ConstrExclusion) => {
+            // Each exclusion is a list of the index element and its operator name
+            let elements = constraint
+                .exclusions
+                .iter()
+                .map(|exclusion| {
+                    let Some(node::Node::List(pair)) = &exclusion.node else {
+                        anyhow::bail!("Malformed EXCLUDE element");
+                    };
+                    let Some(node::Node::IndexElem(elem)) =
+                        pair.items.first().and_then(|item| item.node.as_ref())
+                    else {
+                        anyhow::bail!("Malformed EXCLUDE element");
+                    };
+                    let operator = match pair.items.get(1).and_then(|item| item.node.as_ref()) {
+                        Some(node::Node::List(names)) => {
+                            names.items.iter().filter_map(node_to_string).collect::<Vec<_>>().join(".")
+                        }
+                        _ => String::new(),
+                    };
+                    let column = parse_index_elem(elem)?;
+                    // Expressions keep the parentheses EXCLUDE requires around them
+                    let expression = match column.expression {
+                        Some(expression) => format!("({})", expression),
+                        None => column.name.unwrap_or_default(),
+                    };
+                    Ok(ExclusionElement {
+                        expression: Expression::Column(expression),
+                        operator,
+                        order: column.order,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(TableConstraint::Exclusion {
+                elements,
+                using: constraint.access_method.clone(),
+                name,
             })
         }
         _ => Ok(TableConstraint::PrimaryKey {
             columns: Vec::new(),
             name: None,
+            deferrable: false,
+            initially_deferred: false,
         }), // fallback
     }
 }
 
+/// Map a FOREIGN KEY action code; NO ACTION (the default) is left unset
+fn parse_referential_action(action: &str) -> Option<ReferentialAction> {
+    match action {
+        "r" => Some(ReferentialAction::Restrict),
+        "c" => Some(ReferentialAction::Cascade),
+        "n" => Some(ReferentialAction::SetNull),
+        "d" => Some(ReferentialAction::SetDefault),
+        _ => None,
+    }
+}
+
 fn parse_partition_definition(_part: &protobuf::Node) -> Result<PartitionDefinition> {
     // TODO: Implement partition parsing
     Ok(PartitionDefinition {
@@ -510,9 +765,17 @@ fn parse_function_parameter(param: &protobuf::FunctionParameter) -> Result<Funct
     })
 }
 
-fn parse_function_return(_return_type: &protobuf::Node) -> Result<FunctionReturn> {
-    // TODO: Implement return type parsing
-    Ok(FunctionReturn::Type(DataType::Text))
+fn parse_function_return(return_type: &protobuf::Node) -> Result<FunctionReturn> {
+    let Some(node::Node::TypeName(type_name)) = &return_type.node else {
+        return Ok(FunctionReturn::Type(DataType::Text));
+    };
+    // TODO: RETURNS TABLE comes back as SETOF record with TABLE-mode parameters
+    let data_type = parse_data_type(type_name)?;
+    if type_name.setof {
+        Ok(FunctionReturn::SetOf(data_type))
+    } else {
+        Ok(FunctionReturn::Type(data_type))
+    }
 }
 
 fn parse_function_options(
@@ -746,8 +1009,13 @@ fn parse_create_server(stmt: &protobuf::CreateForeignServerStmt) -> Result<State
 }
 
 fn parse_alter_table(stmt: &protobuf::AlterTableStmt) -> Result<Statement> {
-    let name = get_qualified_name(stmt.relation.as_ref().context("Missing relation")?)?;
-    let schema = None; // TODO: Parse schema
+    let rel = stmt.relation.as_ref().context("Missing relation")?;
+    let name = rel.relname.clone();
+    let schema = if !rel.schemaname.is_empty() {
+        Some(rel.schemaname.clone())
+    } else {
+        None
+    };
     let mut actions = Vec::new();
 
     for cmd in &stmt.cmds {
@@ -801,6 +1069,54 @@ fn parse_drop_object(stmt: &protobuf::DropStmt) -> Result<Statement> {
     }))
 }
 
+/// `None` for comments on objects the schema doesn't model (schemas, indexes,
+/// constraints, ...), which are skipped rather than failing the whole file
+fn parse_comment(stmt: &protobuf::CommentStmt) -> Result<Option<Statement>> {
+    let object_type = match protobuf::ObjectType::try_from(stmt.objtype) {
+        Ok(typ) => match parse_object_type(&typ) {
+            Ok(object_type) => object_type,
+            Err(_) => return Ok(None),
+        },
+        Err(_) => return Err(anyhow::anyhow!("Invalid object type")),
+    };
+
+    // Types and functions are named by TypeName/ObjectWithArgs nodes, the rest by name lists
+    let object = stmt.object.as_ref().context("Missing comment object")?;
+    let object_name = match &object.node {
+        Some(node::Node::TypeName(type_name)) => get_qualified_name_from_nodes(&type_name.names)?,
+        Some(node::Node::ObjectWithArgs(func)) => get_qualified_name_from_nodes(&func.objname)?,
+        _ => get_object_name(object)?,
+    };
+
+    // Overloads are told apart by their argument types
+    let arguments = match &object.node {
+        Some(node::Node::ObjectWithArgs(func)) if !func.args_unspecified => Some(
+            func.objargs
+                .iter()
+                .filter_map(|arg| match &arg.node {
+                    Some(node::Node::TypeName(type_name)) => Some(parse_data_type(type_name)),
+                    _ => None,
+                })
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        _ => None,
+    };
+
+    // `IS NULL` and `IS ''` both remove the comment
+    let comment = if stmt.comment.is_empty() {
+        None
+    } else {
+        Some(stmt.comment.clone())
+    };
+
+    Ok(Some(Statement::Comment(Comment {
+        object_type,
+        object_name,
+        arguments,
+        comment,
+    })))
+}
+
 // Helper functions
 fn get_object_name(obj: &protobuf::Node) -> Result<String> {
     if let Some(node) = &obj.node {
@@ -834,7 +1150,9 @@ fn get_object_name(obj: &protobuf::Node) -> Result<String> {
 fn parse_object_type(typ: &protobuf::ObjectType) -> Result<ObjectType> {
     match typ {
         protobuf::ObjectType::ObjectTable => Ok(ObjectType::Table),
+        protobuf::ObjectType::ObjectColumn => Ok(ObjectType::Column),
         protobuf::ObjectType::ObjectView => Ok(ObjectType::View),
+        protobuf::ObjectType::ObjectMatview => Ok(ObjectType::MaterializedView),
         protobuf::ObjectType::ObjectFunction => Ok(ObjectType::Function),
         protobuf::ObjectType::ObjectProcedure => Ok(ObjectType::Procedure),
         protobuf::ObjectType::ObjectType => Ok(ObjectType::Type),
@@ -844,6 +1162,8 @@ fn parse_object_type(typ: &protobuf::ObjectType) -> Result<ObjectType> {
         protobuf::ObjectType::ObjectTrigger => Ok(ObjectType::Trigger),
         protobuf::ObjectType::ObjectPolicy => Ok(ObjectType::Policy),
         protobuf::ObjectType::ObjectForeignServer => Ok(ObjectType::Server),
+        protobuf::ObjectType::ObjectRule => Ok(ObjectType::Rule),
+        protobuf::ObjectType::ObjectCollation => Ok(ObjectType::Collation),
         _ => Err(anyhow::anyhow!("Unsupported object type")),
     }
 }
@@ -1081,6 +1401,66 @@ fn parse_create_range_type(stmt: &protobuf::CreateRangeStmt) -> Result<Statement
         multirange_type_name: None,
     }))
 }
+
+fn parse_create_collation(stmt: &protobuf::DefineStmt) -> Result<Statement> {
+    let mut names: Vec<String> = stmt
+        .defnames
+        .iter()
+        .filter_map(|name| match &name.node {
+            Some(node::Node::String(str_val)) => Some(str_val.sval.clone()),
+            _ => None,
+        })
+        .collect();
+    let name = names.pop().context("Missing collation name")?;
+    let schema = names.pop();
+
+    let mut lc_collate = None;
+    let mut lc_ctype = None;
+    let mut provider = CollationProvider::Libc;
+    let mut deterministic = true;
+    for option in &stmt.definition {
+        let Some(node::Node::DefElem(def)) = &option.node else {
+            continue;
+        };
+        // Values are string constants or bare words, which arrive as type names
+        let value = def.arg.as_ref().and_then(|arg| match &arg.node {
+            Some(node::Node::String(str_val)) => Some(str_val.sval.clone()),
+            Some(node::Node::TypeName(type_name)) => {
+                get_qualified_name_from_nodes(&type_name.names).ok()
+            }
+            _ => None,
+        });
+        match def.defname.as_str() {
+            "locale" => {
+                lc_collate = value.clone();
+                lc_ctype = value;
+            }
+            "lc_collate" => lc_collate = value,
+            "lc_ctype" => lc_ctype = value,
+            "provider" => {
+                provider = match value.as_deref() {
+                    Some("icu") => CollationProvider::Icu,
+                    Some("builtin") => CollationProvider::Builtin,
+                    _ => CollationProvider::Libc,
+                }
+            }
+            "deterministic" => {
+                deterministic = !matches!(value.as_deref(), Some("false" | "off" | "no" | "0"))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Statement::CreateCollation(CreateCollation {
+        name,
+        schema,
+        lc_collate,
+        lc_ctype,
+        provider,
+        deterministic,
+    }))
+}
+
 fn parse_create_role(stmt: &protobuf::CreateRoleStmt) -> Result<Statement> {
     let name = stmt.role.clone();
     let mut superuser = false;
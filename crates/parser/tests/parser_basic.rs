@@ -1,4 +1,4 @@
-use parser::{parse_sql, parse_schema, Statement};
+use parser::{Statement, parse_schema, parse_sql};
 use shared_types::PolicyCommand;
 
 #[test]
@@ -72,4 +72,43 @@ fn test_parse_schema_multiple_objects() {
     assert_eq!(schema.policies.len(), 1);
     assert_eq!(schema.tables[0].name, "users");
     assert_eq!(schema.policies[0].name, "p1");
-} 
\ No newline at end of file
+}
+
+#[test]
+fn test_parse_error_reports_position() {
+    let sql = "CREATE TABLE users (id integer);\n\nCREATE TABL posts (id integer);\n";
+    let error = parse_sql(sql).unwrap_err();
+    match error.downcast_ref::<shem_core::Error>() {
+        Some(shem_core::Error::Parse {
+            line,
+            column,
+            message,
+            snippet,
+        }) => {
+            assert_eq!(*line, 3);
+            assert_eq!(*column, 8);
+            assert!(message.contains("TABL"));
+            assert_eq!(snippet, "CREATE TABL posts (id integer);");
+        }
+        other => panic!("Expected a parse error with a position, got {:?}", other),
+    }
+    assert!(
+        error
+            .to_string()
+            .starts_with("Parse error at line 3, column 8")
+    );
+}
+
+#[test]
+fn test_parse_error_points_at_failing_occurrence_of_token() {
+    // The earlier commas are fine, the error is at the second one of `,,`
+    let sql = "CREATE TABLE users (id integer, name text,, email text);";
+    let error = parse_sql(sql).unwrap_err();
+    match error.downcast_ref::<shem_core::Error>() {
+        Some(shem_core::Error::Parse { line, column, .. }) => {
+            assert_eq!(*line, 1);
+            assert_eq!(*column, 43);
+        }
+        other => panic!("Expected a parse error with a position, got {:?}", other),
+    }
+}
@@ -1,4 +1,4 @@
-use parser::{parse_sql, parse_schema, Statement};
+use parser::{parse_sql, parse_schema, ObjectType, Statement};
 use shared_types::*;
 
 #[test]
@@ -42,6 +42,32 @@ fn test_parse_create_table_complex() {
     }
 }
 
+#[test]
+fn test_parse_numeric_precision_and_scale() {
+    let sql = r#"
+        CREATE TABLE prices (
+            amount NUMERIC,
+            whole NUMERIC(10),
+            exact DECIMAL(10, 2)
+        );
+    "#;
+    let stmts = parse_sql(sql).unwrap();
+    match &stmts[0] {
+        Statement::CreateTable(table) => {
+            let types: Vec<&DataType> = table.columns.iter().map(|c| &c.data_type).collect();
+            assert_eq!(
+                types,
+                vec![
+                    &DataType::Numeric(None, None),
+                    &DataType::Numeric(Some(10), None),
+                    &DataType::Numeric(Some(10), Some(2)),
+                ]
+            );
+        }
+        _ => panic!("Expected CreateTable statement"),
+    }
+}
+
 #[test]
 fn test_parse_create_view() {
     let sql = r#"
@@ -84,6 +110,26 @@ fn test_parse_create_function_complex() {
             assert_eq!(func.name, "complex_func");
             assert_eq!(func.parameters.len(), 3);
             assert_eq!(func.language, "plpgsql");
+            assert_eq!(func.returns, FunctionReturn::Type(DataType::Integer));
+        }
+        _ => panic!("Expected CreateFunction statement"),
+    }
+}
+
+#[test]
+fn test_parse_create_function_returning_setof_table() {
+    let sql = r#"
+        CREATE FUNCTION active_users() RETURNS SETOF users LANGUAGE sql AS $$
+            SELECT * FROM users WHERE active
+        $$;
+    "#;
+    let stmts = parse_sql(sql).unwrap();
+    match &stmts[0] {
+        Statement::CreateFunction(func) => {
+            assert_eq!(
+                func.returns,
+                FunctionReturn::SetOf(DataType::Custom("users".to_string()))
+            );
         }
         _ => panic!("Expected CreateFunction statement"),
     }
@@ -468,4 +514,269 @@ fn test_parse_complex_schema() {
     assert_eq!(schema.functions[0].name, "get_user_posts");
     assert_eq!(schema.policies[0].name, "user_policy");
     assert_eq!(schema.triggers[0].name, "audit_users");
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_parse_create_index() {
+    let sql = r#"
+        CREATE UNIQUE INDEX users_email_idx ON app.users USING btree (
+            lower(email),
+            created_at DESC NULLS LAST
+        ) INCLUDE (name) WHERE deleted_at IS NULL;
+    "#;
+    let stmts = parse_sql(sql).unwrap();
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Statement::CreateIndex(index) => {
+            assert_eq!(index.name, "users_email_idx");
+            assert_eq!(index.schema, Some("app".to_string()));
+            assert_eq!(index.table, "users");
+            assert_eq!(index.method, "btree");
+            assert!(index.unique);
+            assert_eq!(index.include, vec!["name"]);
+            assert_eq!(index.where_clause.as_deref(), Some("deleted_at IS NULL"));
+            assert_eq!(index.columns.len(), 2);
+            assert_eq!(index.columns[0].name, None);
+            assert_eq!(index.columns[0].expression.as_deref(), Some("lower(email)"));
+            assert_eq!(index.columns[1].name.as_deref(), Some("created_at"));
+            assert_eq!(index.columns[1].order, Some(SortOrder::Desc));
+            assert_eq!(index.columns[1].nulls_first, Some(false));
+        }
+        _ => panic!("Expected CreateIndex statement"),
+    }
+}
+
+#[test]
+fn test_parse_schema_attaches_indexes_to_tables() {
+    let sql = r#"
+        CREATE INDEX posts_user_id_idx ON posts (user_id);
+        CREATE TABLE posts (id SERIAL PRIMARY KEY, user_id INTEGER);
+        CREATE INDEX events_kind_idx ON events USING hash (kind);
+    "#;
+    let schema = parse_schema(sql).unwrap();
+    assert_eq!(schema.tables.len(), 1);
+    assert_eq!(schema.tables[0].indexes.len(), 1);
+    assert_eq!(schema.tables[0].indexes[0].name, "posts_user_id_idx");
+    assert_eq!(schema.tables[0].indexes[0].columns[0].order, None);
+
+    // Indexes on tables declared elsewhere are kept at the schema level
+    assert_eq!(schema.indexes.len(), 1);
+    assert_eq!(schema.indexes[0].method, "hash");
+}
+
+#[test]
+fn test_parse_schema_attaches_indexes_across_public_qualification() {
+    let sql = r#"
+        CREATE TABLE users (id INTEGER, email TEXT);
+        CREATE TABLE public.posts (id INTEGER);
+        CREATE TABLE app.users (id INTEGER);
+        CREATE INDEX users_email_idx ON public.users (email);
+        CREATE INDEX posts_id_idx ON posts (id);
+        CREATE INDEX app_users_id_idx ON app.users (id);
+    "#;
+    let schema = parse_schema(sql).unwrap();
+    assert_eq!(schema.tables.len(), 3);
+
+    // `public.users` and `users` name the same table, `app.users` stays apart
+    assert_eq!(schema.tables[0].indexes.len(), 1);
+    assert_eq!(schema.tables[0].indexes[0].name, "users_email_idx");
+    assert_eq!(schema.tables[1].indexes.len(), 1);
+    assert_eq!(schema.tables[1].indexes[0].name, "posts_id_idx");
+    assert_eq!(schema.tables[2].indexes.len(), 1);
+    assert_eq!(schema.tables[2].indexes[0].name, "app_users_id_idx");
+    assert!(schema.indexes.is_empty());
+}
+
+#[test]
+fn test_parse_schema_merges_alter_table() {
+    let sql = r#"
+        CREATE TABLE users (id SERIAL);
+        CREATE TABLE app.posts (id SERIAL, user_id INTEGER);
+        ALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (id);
+        ALTER TABLE app.posts ADD COLUMN title TEXT NOT NULL;
+        ALTER TABLE app.posts
+            ADD CONSTRAINT posts_user_id_fkey FOREIGN KEY (user_id)
+            REFERENCES users (id) ON DELETE CASCADE;
+    "#;
+    let schema = parse_schema(sql).unwrap();
+    assert_eq!(schema.tables.len(), 2);
+
+    let users = &schema.tables[0];
+    assert_eq!(users.constraints.len(), 1);
+    match &users.constraints[0] {
+        TableConstraint::PrimaryKey { columns, name, .. } => {
+            assert_eq!(columns, &vec!["id".to_string()]);
+            assert_eq!(name.as_deref(), Some("users_pkey"));
+        }
+        other => panic!("Expected primary key, got {:?}", other),
+    }
+
+    let posts = &schema.tables[1];
+    assert_eq!(posts.schema, Some("app".to_string()));
+    assert_eq!(posts.columns.len(), 3);
+    assert_eq!(posts.columns[2].name, "title");
+    assert!(posts.columns[2].not_null);
+    match &posts.constraints[0] {
+        TableConstraint::ForeignKey {
+            columns,
+            references,
+            name,
+        } => {
+            assert_eq!(columns, &vec!["user_id".to_string()]);
+            assert_eq!(references.table, "users");
+            assert_eq!(references.columns, vec!["id".to_string()]);
+            assert_eq!(references.on_delete, Some(ReferentialAction::Cascade));
+            assert_eq!(references.on_update, None);
+            assert_eq!(name.as_deref(), Some("posts_user_id_fkey"));
+        }
+        other => panic!("Expected foreign key, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_schema_merges_alter_table_across_public_qualification() {
+    let sql = r#"
+        CREATE TABLE users (id INTEGER);
+        CREATE TABLE public.posts (id INTEGER);
+        ALTER TABLE public.users ADD COLUMN email TEXT;
+        ALTER TABLE posts ADD CONSTRAINT posts_pkey PRIMARY KEY (id);
+    "#;
+    let schema = parse_schema(sql).unwrap();
+    assert_eq!(schema.tables.len(), 2);
+
+    // `public.users` extends the unqualified `users`, and the other way around
+    let users = &schema.tables[0];
+    assert_eq!(users.columns.len(), 2);
+    assert_eq!(users.columns[1].name, "email");
+    let posts = &schema.tables[1];
+    assert_eq!(posts.constraints.len(), 1);
+
+    // Other schemas still don't match the unqualified name
+    let sql = "CREATE TABLE users (id INTEGER); ALTER TABLE app.users ADD COLUMN a TEXT;";
+    assert!(parse_schema(sql).is_err());
+}
+
+#[test]
+fn test_parse_exclusion_constraint() {
+    let sql = r#"
+        CREATE TABLE bookings (
+            room INTEGER,
+            during TSRANGE,
+            CONSTRAINT bookings_no_overlap EXCLUDE USING gist (room WITH =, during WITH &&)
+        );
+        ALTER TABLE bookings ADD CONSTRAINT bookings_lower_excl EXCLUDE ((lower(during)) WITH =);
+    "#;
+    let schema = parse_schema(sql).unwrap();
+    let bookings = &schema.tables[0];
+    assert_eq!(bookings.constraints.len(), 2);
+    match &bookings.constraints[0] {
+        TableConstraint::Exclusion {
+            elements,
+            using,
+            name,
+        } => {
+            assert_eq!(name.as_deref(), Some("bookings_no_overlap"));
+            assert_eq!(using, "gist");
+            assert_eq!(elements.len(), 2);
+            assert_eq!(elements[0].expression, Expression::Column("room".to_string()));
+            assert_eq!(elements[0].operator, "=");
+            assert_eq!(elements[1].expression, Expression::Column("during".to_string()));
+            assert_eq!(elements[1].operator, "&&");
+        }
+        other => panic!("Expected exclusion constraint, got {:?}", other),
+    }
+
+    // Without USING the index method is btree, expressions keep their parentheses
+    match &bookings.constraints[1] {
+        TableConstraint::Exclusion {
+            elements, using, ..
+        } => {
+            assert_eq!(using, "btree");
+            assert_eq!(
+                elements[0].expression,
+                Expression::Column("(lower(during))".to_string())
+            );
+        }
+        other => panic!("Expected exclusion constraint, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_inline_column_constraints() {
+    let sql = "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, bio TEXT);";
+    let schema = parse_schema(sql).unwrap();
+    let users = &schema.tables[0];
+    assert!(!users.columns[0].not_null);
+    assert!(users.columns[1].not_null);
+    assert!(!users.columns[2].not_null);
+
+    // NOT NULL is a column property, only the keys become constraints
+    assert_eq!(users.constraints.len(), 2);
+    match &users.constraints[0] {
+        TableConstraint::PrimaryKey { columns, .. } => {
+            assert_eq!(columns, &vec!["id".to_string()]);
+        }
+        other => panic!("Expected primary key, got {:?}", other),
+    }
+    match &users.constraints[1] {
+        TableConstraint::Unique { columns, .. } => {
+            assert_eq!(columns, &vec!["email".to_string()]);
+        }
+        other => panic!("Expected unique constraint, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_schema_alter_unknown_table() {
+    let sql = "ALTER TABLE missing ADD COLUMN id INTEGER;";
+    assert!(parse_schema(sql).is_err());
+}
+
+#[test]
+fn test_parse_comment_on() {
+    let sql = r#"
+        COMMENT ON TABLE public.users IS 'Application users';
+        COMMENT ON COLUMN users.email IS $comment$Primary
+contact$comment$;
+        COMMENT ON FUNCTION add(integer, integer) IS 'Adds';
+        COMMENT ON TYPE mood IS NULL;
+    "#;
+    let stmts = parse_sql(sql).unwrap();
+    assert_eq!(stmts.len(), 4);
+    let comments: Vec<_> = stmts
+        .iter()
+        .map(|stmt| match stmt {
+            Statement::Comment(comment) => comment,
+            _ => panic!("Expected Comment statement"),
+        })
+        .collect();
+
+    assert!(matches!(comments[0].object_type, ObjectType::Table));
+    assert_eq!(comments[0].object_name, "public.users");
+    assert_eq!(comments[0].comment.as_deref(), Some("Application users"));
+    assert!(matches!(comments[1].object_type, ObjectType::Column));
+    assert_eq!(comments[1].object_name, "users.email");
+    assert_eq!(comments[1].comment.as_deref(), Some("Primary\ncontact"));
+    assert!(matches!(comments[2].object_type, ObjectType::Function));
+    assert_eq!(comments[2].object_name, "add");
+    assert_eq!(comments[2].arguments.as_ref().map(Vec::len), Some(2));
+    assert!(comments[0].arguments.is_none());
+    assert!(matches!(comments[3].object_type, ObjectType::Type));
+    assert_eq!(comments[3].object_name, "mood");
+    assert_eq!(comments[3].comment, None);
+}
+
+#[test]
+fn test_parse_comment_on_unsupported_object_is_skipped() {
+    let sql = r#"
+        COMMENT ON SCHEMA public IS 'Standard public schema';
+        COMMENT ON INDEX users_email_idx IS 'Lookup by email';
+        COMMENT ON CONSTRAINT users_pkey ON users IS 'Identity';
+        COMMENT ON TABLE users IS 'Application users';
+    "#;
+    let stmts = parse_sql(sql).unwrap();
+    assert_eq!(stmts.len(), 1);
+    match &stmts[0] {
+        Statement::Comment(comment) => assert_eq!(comment.object_name, "users"),
+        other => panic!("Expected Comment statement, got {:?}", other),
+    }
+}
@@ -1,26 +1,84 @@
 use async_trait::async_trait;
 use base64::engine::Engine as _;
 use base64::engine::general_purpose::STANDARD as BASE64;
+use deadpool_postgres::{
+    Manager, ManagerConfig, Object, Pool, PoolError, RecyclingMethod, Timeouts,
+};
 use shem_core::traits::{ConnectionMetadata, Feature, SqlGenerator, Transaction};
-use shem_core::{DatabaseConnection, DatabaseDriver, Result, Schema};
+use shem_core::{
+    DatabaseConnection, DatabaseDriver, IntrospectOptions, OverriddenSqlGenerator, Result, Schema,
+    SqlGeneratorOverride,
+};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio_postgres::{Client, Config, NoTls};
+use std::time::Duration;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::tls::{MakeTlsConnect, TlsConnect};
+use tokio_postgres::types::{Kind, Type};
+use tokio_postgres::{Client, Config, NoTls, Socket};
 
 pub mod db_util;
 pub mod introspection;
 pub mod sql_generator;
+pub mod tls;
 pub use db_util::TestDb;
-pub use introspection::introspect_schema;
+pub use introspection::{
+    introspect_schema, introspect_schema_with_clients, introspect_schema_with_options,
+};
 pub use sql_generator::PostgresSqlGenerator;
 
+/// `application_name` sessions report when neither the URL nor the driver sets one
+pub const DEFAULT_APPLICATION_NAME: &str = "shem";
+
 /// PostgreSQL database driver
-#[derive(Debug, Clone)]
-pub struct PostgresDriver;
+#[derive(Debug, Clone, Default)]
+pub struct PostgresDriver {
+    statement_timeout_ms: Option<u64>,
+    generator_override: Option<Arc<dyn SqlGeneratorOverride>>,
+    ssl_root_cert: Option<PathBuf>,
+    application_name: Option<String>,
+    options: Option<String>,
+}
 
 impl PostgresDriver {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Set `statement_timeout` (in milliseconds) on every session this driver opens
+    pub fn with_statement_timeout(mut self, statement_timeout_ms: Option<u64>) -> Self {
+        self.statement_timeout_ms = statement_timeout_ms;
+        self
+    }
+
+    pub fn statement_timeout_ms(&self) -> Option<u64> {
+        self.statement_timeout_ms
+    }
+
+    /// Trust the PEM certificates in `path` for `sslmode=verify-full` connections
+    pub fn with_ssl_root_cert(mut self, path: Option<PathBuf>) -> Self {
+        self.ssl_root_cert = path;
+        self
+    }
+
+    /// Name sessions report in `pg_stat_activity`; an `application_name` in the
+    /// URL takes precedence, and `shem` is used when neither sets one
+    pub fn with_application_name(mut self, application_name: Option<String>) -> Self {
+        self.application_name = application_name;
+        self
+    }
+
+    /// Extra libpq `options` (such as `-c search_path=app`) for every session,
+    /// appended to any `options` in the URL
+    pub fn with_options(mut self, options: Option<String>) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Pass every statement from `sql_generator()` through `overrides`
+    pub fn with_generator_override(mut self, overrides: Arc<dyn SqlGeneratorOverride>) -> Self {
+        self.generator_override = Some(overrides);
+        self
     }
 }
 
@@ -129,34 +187,126 @@ impl DatabaseDriver for PostgresDriver {
     }
 
     fn sql_generator(&self) -> Box<dyn SqlGenerator> {
-        Box::new(PostgresSqlGenerator)
+        match &self.generator_override {
+            Some(overrides) => Box::new(OverriddenSqlGenerator::new(
+                PostgresSqlGenerator,
+                overrides.clone(),
+            )),
+            None => Box::new(PostgresSqlGenerator),
+        }
     }
 
     async fn connect(&self, url: &str) -> Result<Box<dyn DatabaseConnection>> {
-        let config = url.parse::<Config>()?;
-        let (client, connection) = config.connect(NoTls).await?;
+        // `tokio_postgres` rejects `verify-full`, so TLS is configured from `sslmode` here
+        let (url, ssl_mode) = tls::split_ssl_mode(url)?;
+        let mut config = url.parse::<Config>()?;
+        config.ssl_mode(ssl_mode.negotiation());
+
+        if config.get_application_name().is_none() {
+            config.application_name(
+                self.application_name
+                    .as_deref()
+                    .unwrap_or(DEFAULT_APPLICATION_NAME),
+            );
+        }
 
-        // Spawn connection task
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("connection error: {}", e);
-            }
-        });
+        let mut options: Vec<String> = config.get_options().map(String::from).into_iter().collect();
+        options.extend(self.options.clone());
+        // Pooled sessions come and go, so the timeout is a startup option of each of them
+        if let Some(timeout_ms) = self.statement_timeout_ms {
+            options.push(format!("-c statement_timeout={}", timeout_ms));
+        }
+        if !options.is_empty() {
+            config.options(options.join(" "));
+        }
 
-        Ok(Box::new(PostgresConnection {
-            client: Arc::new(Mutex::new(client)),
+        let pool = match tls::connector(ssl_mode, self.ssl_root_cert.as_deref())? {
+            tls::TlsConnector::NoTls => build_pool(config, NoTls)?,
+            tls::TlsConnector::Rustls(tls) => build_pool(config, tls)?,
+        };
+
+        // Open the first session now so a bad URL fails here rather than on first use
+        let connection = PostgresConnection {
+            pool,
             driver: Arc::new(self.clone()),
-        }))
+        };
+        drop(connection.client().await?);
+        Ok(Box::new(connection))
     }
 }
 
-/// PostgreSQL database connection
+fn build_pool<T>(config: Config, tls: T) -> Result<Pool>
+where
+    T: MakeTlsConnect<Socket> + Clone + Sync + Send + 'static,
+    T::Stream: Sync + Send,
+    T::TlsConnect: Sync + Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    let manager = Manager::from_config(
+        config,
+        tls,
+        ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        },
+    );
+    Pool::builder(manager)
+        .build()
+        .map_err(|e| shem_core::Error::Database(format!("Failed to create connection pool: {}", e)))
+}
+
+/// Surface the driver error behind a failed checkout
+fn pool_error(e: PoolError) -> shem_core::Error {
+    match e {
+        PoolError::Backend(e) => e.into(),
+        e => shem_core::Error::Database(format!("Failed to get a pooled connection: {}", e)),
+    }
+}
+
+/// PostgreSQL database connection, backed by a pool of sessions so concurrent
+/// callers don't wait on each other
 #[derive(Debug)]
 pub struct PostgresConnection {
-    client: Arc<Mutex<Client>>,
+    pool: Pool,
     driver: Arc<PostgresDriver>,
 }
 
+impl PostgresConnection {
+    /// Check a session out of the pool
+    async fn client(&self) -> Result<Object> {
+        self.pool.get().await.map_err(pool_error)
+    }
+
+    /// Check out up to `count` sessions for introspection, without waiting for
+    /// sessions other callers hold
+    async fn clients(&self, count: usize) -> Result<Vec<Object>> {
+        let mut clients = vec![self.client().await?];
+        let no_wait = Timeouts {
+            wait: Some(Duration::ZERO),
+            ..self.pool.timeouts()
+        };
+        while clients.len() < count {
+            match self.pool.timeout_get(&no_wait).await {
+                Ok(client) => clients.push(client),
+                Err(_) => break,
+            }
+        }
+        Ok(clients)
+    }
+
+    /// Report statements cancelled by the session `statement_timeout` as such
+    fn timeout_error(&self, e: tokio_postgres::Error) -> shem_core::Error {
+        match self.driver.statement_timeout_ms {
+            Some(timeout_ms) if e.code() == Some(&SqlState::QUERY_CANCELED) => {
+                shem_core::Error::Database(format!(
+                    "statement cancelled after exceeding statement_timeout of {} ms: {}",
+                    timeout_ms, e
+                ))
+            }
+            _ => e.into(),
+        }
+    }
+}
+
 #[async_trait]
 impl DatabaseConnection for PostgresConnection {
     fn driver(&self) -> &dyn DatabaseDriver {
@@ -164,7 +314,7 @@ impl DatabaseConnection for PostgresConnection {
     }
 
     async fn metadata(&self) -> Result<ConnectionMetadata> {
-        let client = self.client.lock().await;
+        let client = self.client().await?;
         let row = client.query_one(
             "SELECT version(), current_database(), current_user, inet_server_addr(), inet_server_port(), pg_encoding_to_char(encoding), current_setting('timezone')",
             &[]
@@ -209,177 +359,278 @@ impl DatabaseConnection for PostgresConnection {
     }
 
     async fn introspect(&self) -> Result<Schema> {
-        let client = self.client.lock().await;
-        let client_ref = &*client;
-        introspect_schema(client_ref).await
+        self.introspect_with_options(&IntrospectOptions::default())
+            .await
+    }
+
+    async fn introspect_with_options(&self, options: &IntrospectOptions) -> Result<Schema> {
+        let client = self.client().await?;
+        let concurrency = match options.concurrency {
+            Some(concurrency) => concurrency,
+            None => introspection::default_concurrency(&**client).await?,
+        };
+        drop(client);
+
+        // One session per concurrent query, as many as the pool can spare right now
+        let sessions = self.clients(concurrency).await?;
+        let clients: Vec<&Client> = sessions.iter().map(|session| &***session).collect();
+        let options = IntrospectOptions {
+            concurrency: Some(concurrency),
+            ..options.clone()
+        };
+        introspect_schema_with_clients(&clients, &options).await
     }
 
     async fn execute(&self, sql: &str) -> Result<()> {
-        let client = self.client.lock().await;
-        client.execute(sql, &[]).await?;
+        let client = self.client().await?;
+        client
+            .execute(sql, &[])
+            .await
+            .map_err(|e| self.timeout_error(e))?;
         Ok(())
     }
 
     async fn query(&self, sql: &str) -> Result<Vec<serde_json::Value>> {
-        let client = self.client.lock().await;
-        let rows = client.query(sql, &[]).await?;
-        let mut results = Vec::new();
-
-        for row in rows {
-            let mut map = serde_json::Map::new();
-            for (i, column) in row.columns().iter().enumerate() {
-                let value = match column.type_().name() {
-                    "json" | "jsonb" => {
-                        let json_str: String = row.get(i);
-                        serde_json::from_str(&json_str)?
-                    }
-                    "bool" => serde_json::Value::Bool(row.get(i)),
-                    "int2" | "int4" | "int8" => {
-                        serde_json::Value::Number(serde_json::Number::from(row.get::<_, i64>(i)))
-                    }
-                    "float4" | "float8" => {
-                        let float_val: f64 = row.get(i);
-                        match serde_json::Number::from_f64(float_val) {
-                            Some(num) => serde_json::Value::Number(num),
-                            None => serde_json::Value::Number(
-                                serde_json::Number::from_f64(0.0).unwrap(),
-                            ),
-                        }
-                    }
-                    "text" | "varchar" | "char" | "name" | "uuid" => {
-                        serde_json::Value::String(row.get(i))
-                    }
-                    "timestamp" | "timestamptz" | "date" | "time" | "timetz" => {
-                        let ts: chrono::DateTime<chrono::Utc> = row.get(i);
-                        serde_json::Value::String(ts.to_rfc3339())
-                    }
-                    "bytea" => {
-                        let bytes: Vec<u8> = row.get(i);
-                        serde_json::Value::String(BASE64.encode(&bytes))
-                    }
-                    _ => {
-                        // For other types, convert to string
-                        let s: String = row.get(i);
-                        serde_json::Value::String(s)
-                    }
-                };
-                map.insert(column.name().to_string(), value);
-            }
-            results.push(serde_json::Value::Object(map));
-        }
-
-        Ok(results)
+        let client = self.client().await?;
+        let rows = client
+            .query(sql, &[])
+            .await
+            .map_err(|e| self.timeout_error(e))?;
+        rows.iter().map(row_to_json).collect()
     }
 
     async fn begin(&self) -> Result<Box<dyn Transaction>> {
-        let mut client = self.client.lock().await;
-        let transaction = client.transaction().await?;
-        // Convert the transaction to 'static by moving it into a Box
-        let transaction = unsafe {
-            std::mem::transmute::<
-                tokio_postgres::Transaction<'_>,
-                tokio_postgres::Transaction<'static>,
-            >(transaction)
-        };
+        // The transaction keeps its session checked out until it commits or rolls back
+        let client = self.client().await?;
+        client.batch_execute("BEGIN").await?;
         Ok(Box::new(PostgresTransaction {
-            transaction: Some(transaction),
+            client: Some(client),
         }))
     }
 
     async fn close(self: Box<Self>) -> Result<()> {
-        // Drop the Arc to ensure the client is properly closed
-        drop(Arc::try_unwrap(self.client).map_err(|_| anyhow::anyhow!("Failed to drop client"))?);
+        // Closes idle sessions now, checked-out ones as they are returned
+        self.pool.close();
         Ok(())
     }
 }
 
-/// PostgreSQL transaction
+/// `numeric` value decoded from the binary wire format into its exact decimal text,
+/// since it can carry more precision than any Rust numeric type
+struct PgNumeric(String);
+
+impl<'a> tokio_postgres::types::FromSql<'a> for PgNumeric {
+    fn from_sql(
+        _ty: &tokio_postgres::types::Type,
+        raw: &'a [u8],
+    ) -> std::result::Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let word = |at: usize| {
+            raw.get(at..at + 2)
+                .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+                .ok_or("truncated numeric value")
+        };
+        let ndigits = word(0)? as usize;
+        let weight = word(2)? as i16 as i32;
+        let sign = word(4)?;
+        let dscale = word(6)? as usize;
+        let digits = (0..ndigits)
+            .map(|k| word(8 + k * 2))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let special = match sign {
+            0xC000 => Some("NaN"),
+            0xD000 => Some("Infinity"),
+            0xF000 => Some("-Infinity"),
+            _ => None,
+        };
+        if let Some(special) = special {
+            return Ok(PgNumeric(special.to_string()));
+        }
+
+        // Digits are base-10000 groups; group `k` is worth 10000^(weight - k)
+        let digit = |k: i32| {
+            usize::try_from(k)
+                .ok()
+                .and_then(|k| digits.get(k).copied())
+                .unwrap_or(0)
+        };
+        let mut text = String::new();
+        if sign == 0x4000 {
+            text.push('-');
+        }
+        if weight < 0 {
+            text.push('0');
+        } else {
+            text.push_str(&digit(0).to_string());
+            for k in 1..=weight {
+                text.push_str(&format!("{:04}", digit(k)));
+            }
+        }
+        if dscale > 0 {
+            let mut fraction = String::new();
+            let mut k = weight + 1;
+            while fraction.len() < dscale {
+                fraction.push_str(&format!("{:04}", digit(k)));
+                k += 1;
+            }
+            fraction.truncate(dscale);
+            text.push('.');
+            text.push_str(&fraction);
+        }
+        Ok(PgNumeric(text))
+    }
+
+    fn accepts(ty: &tokio_postgres::types::Type) -> bool {
+        *ty == tokio_postgres::types::Type::NUMERIC
+    }
+}
+
+/// Convert a result row into a JSON object keyed by column name; NULLs are read as
+/// `Option` so they become `null` instead of panicking
+fn row_to_json(row: &tokio_postgres::Row) -> Result<serde_json::Value> {
+    use serde_json::{Number, Value};
+
+    let mut map = serde_json::Map::new();
+    for (i, column) in row.columns().iter().enumerate() {
+        if let Kind::Array(element) = column.type_().kind()
+            && let Some(value) = array_to_json(row, i, element)
+        {
+            map.insert(column.name().to_string(), value);
+            continue;
+        }
+        let value = match column.type_().name() {
+            "json" | "jsonb" => match row.get::<_, Option<String>>(i) {
+                Some(json_str) => serde_json::from_str(&json_str)?,
+                None => Value::Null,
+            },
+            "bool" => row.get::<_, Option<bool>>(i).into(),
+            // Each width must be read as its own Rust type, then widen for JSON
+            "int2" => row.get::<_, Option<i16>>(i).into(),
+            "int4" => row.get::<_, Option<i32>>(i).into(),
+            "int8" => row.get::<_, Option<i64>>(i).into(),
+            // Large object references are unsigned 32-bit
+            "oid" => row.get::<_, Option<u32>>(i).into(),
+            "float4" | "float8" => match row.get::<_, Option<f64>>(i) {
+                Some(float_val) => match Number::from_f64(float_val) {
+                    Some(num) => Value::Number(num),
+                    None => Value::Number(Number::from_f64(0.0).unwrap()),
+                },
+                None => Value::Null,
+            },
+            // Kept as text so no precision is lost; NaN and infinities come through as-is
+            "numeric" => row
+                .get::<_, Option<PgNumeric>>(i)
+                .map_or(Value::Null, |numeric| Value::String(numeric.0)),
+            "text" | "varchar" | "char" | "name" | "uuid" => row.get::<_, Option<String>>(i).into(),
+            "timestamp" | "timestamptz" | "date" | "time" | "timetz" => row
+                .get::<_, Option<chrono::DateTime<chrono::Utc>>>(i)
+                .map_or(Value::Null, |ts| Value::String(ts.to_rfc3339())),
+            "bytea" => row
+                .get::<_, Option<Vec<u8>>>(i)
+                .map_or(Value::Null, |bytes| Value::String(BASE64.encode(&bytes))),
+            // For other types, convert to string
+            _ => row.get::<_, Option<String>>(i).into(),
+        };
+        map.insert(column.name().to_string(), value);
+    }
+    Ok(Value::Object(map))
+}
+
+/// Convert a one-dimensional array column into a JSON array, keeping NULL elements as
+/// `null`; returns `None` for element types without a dedicated decoder
+fn array_to_json(row: &tokio_postgres::Row, i: usize, element: &Type) -> Option<serde_json::Value> {
+    use serde_json::Value;
+
+    let value = match element.name() {
+        "text" | "varchar" | "bpchar" | "name" => {
+            row.get::<_, Option<Vec<Option<String>>>>(i).into()
+        }
+        "bool" => row.get::<_, Option<Vec<Option<bool>>>>(i).into(),
+        "int2" => row.get::<_, Option<Vec<Option<i16>>>>(i).into(),
+        "int4" => row.get::<_, Option<Vec<Option<i32>>>>(i).into(),
+        "int8" => row.get::<_, Option<Vec<Option<i64>>>>(i).into(),
+        "uuid" => row
+            .get::<_, Option<Vec<Option<uuid::Uuid>>>>(i)
+            .map_or(Value::Null, |items| {
+                items
+                    .into_iter()
+                    .map(|item| item.map(|uuid| uuid.to_string()))
+                    .collect::<Vec<_>>()
+                    .into()
+            }),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// PostgreSQL transaction, holding one pooled session for its whole lifetime
 pub struct PostgresTransaction {
-    transaction: Option<tokio_postgres::Transaction<'static>>,
+    client: Option<Object>,
 }
 
-// Manually implement Debug since tokio_postgres::Transaction doesn't implement it
+impl PostgresTransaction {
+    /// Finish the transaction with `COMMIT` or `ROLLBACK`, releasing the client
+    async fn finish(&mut self, statement: &str) -> Result<()> {
+        if let Some(client) = self.client.take() {
+            client.batch_execute(statement).await?;
+        }
+        Ok(())
+    }
+}
+
+// Manually implement Debug since the pooled client isn't meaningful to print
 impl std::fmt::Debug for PostgresTransaction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PostgresTransaction")
-            .field("transaction", &"<tokio_postgres::Transaction>")
+            .field("active", &self.client.is_some())
             .finish()
     }
 }
 
+// Like tokio_postgres::Transaction, roll back when dropped without commit or rollback.
+// The session moves into the task, so it only returns to the pool after the ROLLBACK;
+// a session that can't be rolled back is detached from the pool and closed instead.
+impl Drop for PostgresTransaction {
+    fn drop(&mut self) {
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(e) = client.batch_execute("ROLLBACK").await {
+                        tracing::warn!("Failed to roll back dropped transaction: {}", e);
+                        drop(Object::take(client));
+                    }
+                });
+            }
+            Err(_) => drop(Object::take(client)),
+        }
+    }
+}
+
 #[async_trait]
 impl Transaction for PostgresTransaction {
     async fn execute(&self, sql: &str) -> Result<()> {
-        if let Some(transaction) = &self.transaction {
-            transaction.execute(sql, &[]).await?;
+        if let Some(client) = &self.client {
+            client.execute(sql, &[]).await?;
         }
         Ok(())
     }
 
     async fn query(&self, sql: &str) -> Result<Vec<serde_json::Value>> {
-        if let Some(transaction) = &self.transaction {
-            let rows = transaction.query(sql, &[]).await?;
-            let mut results = Vec::new();
-
-            for row in rows {
-                let mut map = serde_json::Map::new();
-                for (i, column) in row.columns().iter().enumerate() {
-                    let value = match column.type_().name() {
-                        "json" | "jsonb" => {
-                            let json_str: String = row.get(i);
-                            serde_json::from_str(&json_str)?
-                        }
-                        "bool" => serde_json::Value::Bool(row.get(i)),
-                        "int2" | "int4" | "int8" => serde_json::Value::Number(
-                            serde_json::Number::from(row.get::<_, i64>(i)),
-                        ),
-                        "float4" | "float8" => {
-                            let float_val: f64 = row.get(i);
-                            match serde_json::Number::from_f64(float_val) {
-                                Some(num) => serde_json::Value::Number(num),
-                                None => serde_json::Value::Number(
-                                    serde_json::Number::from_f64(0.0).unwrap(),
-                                ),
-                            }
-                        }
-                        "text" | "varchar" | "char" | "name" | "uuid" => {
-                            serde_json::Value::String(row.get(i))
-                        }
-                        "timestamp" | "timestamptz" | "date" | "time" | "timetz" => {
-                            let ts: chrono::DateTime<chrono::Utc> = row.get(i);
-                            serde_json::Value::String(ts.to_rfc3339())
-                        }
-                        "bytea" => {
-                            let bytes: Vec<u8> = row.get(i);
-                            serde_json::Value::String(BASE64.encode(&bytes))
-                        }
-                        _ => {
-                            // For other types, convert to string
-                            let s: String = row.get(i);
-                            serde_json::Value::String(s)
-                        }
-                    };
-                    map.insert(column.name().to_string(), value);
-                }
-                results.push(serde_json::Value::Object(map));
-            }
-            return Ok(results);
+        if let Some(client) = &self.client {
+            let rows = client.query(sql, &[]).await?;
+            return rows.iter().map(row_to_json).collect();
         }
         Ok(Vec::new())
     }
 
     async fn commit(mut self: Box<Self>) -> Result<()> {
-        if let Some(transaction) = self.transaction.take() {
-            transaction.commit().await?;
-        }
-        Ok(())
+        self.finish("COMMIT").await
     }
 
     async fn rollback(mut self: Box<Self>) -> Result<()> {
-        if let Some(transaction) = self.transaction.take() {
-            transaction.rollback().await?;
-        }
-        Ok(())
+        self.finish("ROLLBACK").await
     }
 }
-
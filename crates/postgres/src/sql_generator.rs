@@ -13,27 +13,33 @@
  * and automation of database changes, allowing tools to programmatically manage PostgreSQL schemas.
  */
 use shem_core::{
-    Collation, ConstraintTrigger, Domain, EventTrigger, Extension, ForeignDataWrapper,
-    ForeignTable, Function, Index, IndexMethod, MaterializedView, Policy, Procedure, Publication,
-    Role, Rule, Sequence, Server, Subscription, Table, Tablespace, Trigger, View,
+    Collation, ColumnStorage, ConstraintTrigger, Domain, EventTrigger, Extension,
+    ForeignDataWrapper, ForeignTable, Function, Grant, Index, IndexMethod, MaterializedView,
+    PartitionMethod, Policy, Procedure, Publication, Role, Rule, Sequence, Server, Subscription,
+    Table, Tablespace, Trigger, View,
+    migration::storage_clause,
+    normalize::{defaults_equivalent, types_equivalent},
     schema::{
         ArrayType, BaseType, CheckOption, CollationProvider, CompositeType, EventTriggerEvent,
         MultirangeType, ParameterMode, PolicyCommand, RangeType, RuleEvent, SortOrder,
         TriggerEvent, TriggerLevel, TriggerTiming,
     },
-    traits::SqlGenerator,
+    traits::{GenerateOptions, SqlGenerator},
 };
-use shem_core::{EnumType, Result};
+use shem_core::{EnumType, QualifiedName, Result};
 
 /// PostgreSQL SQL generator
 #[derive(Debug, Clone)]
 pub struct PostgresSqlGenerator;
 
 impl PostgresSqlGenerator {
-    /// Quote an identifier to handle reserved keywords and preserve case sensitivity
-    fn _quote_identifier(identifier: &str) -> String {
+    /// Quote an identifier only when it needs it: reserved keywords, mixed case,
+    /// special characters or a leading digit
+    pub fn quote_identifier(identifier: &str) -> String {
         // Check if quoting is needed
-        let needs_quoting = identifier.chars().any(|c| !c.is_alphanumeric() && c != '_')
+        let needs_quoting = identifier
+            .chars()
+            .any(|c| (!c.is_alphanumeric() && c != '_') || c.is_uppercase())
             || {
                 // Check if it's a reserved keyword (simplified list)
                 let lower = identifier.to_lowercase();
@@ -145,22 +151,155 @@ impl PostgresSqlGenerator {
         format!("\"{}\"", identifier.replace("\"", "\"\""))
     }
 
+    /// Render a role setting's value as `ALTER ROLE ... SET` expects it. List
+    /// parameters such as `search_path` quote each element, everything else is a
+    /// single literal.
+    pub fn role_setting_value(name: &str, value: &str) -> String {
+        let is_list = matches!(
+            name,
+            "search_path"
+                | "temp_tablespaces"
+                | "local_preload_libraries"
+                | "session_preload_libraries"
+                | "shared_preload_libraries"
+        );
+        if !is_list {
+            return format!("'{}'", value.replace('\'', "''"));
+        }
+
+        value
+            .split(',')
+            .map(|item| {
+                let item = item.trim();
+                let item = item
+                    .strip_prefix('"')
+                    .and_then(|item| item.strip_suffix('"'))
+                    .map(|item| item.replace("\"\"", "\""))
+                    .unwrap_or_else(|| item.to_string());
+                format!("'{}'", item.replace('\'', "''"))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     fn is_reserved_keyword(name: &str) -> bool {
         // Add more reserved keywords as needed
         matches!(name.to_ascii_lowercase().as_str(), "order")
     }
+
+    /// Append the GRANT statements for a table, followed by its column-level grants
+    fn push_table_grants(sql: &mut String, table: &Table, table_name: &str) {
+        for grant in &table.grants {
+            Self::push_grant(sql, grant, &grant.privileges.join(", "), table_name);
+        }
+        for column in &table.columns {
+            let column_name = Self::force_quote_identifier(&column.name);
+            for grant in &column.grants {
+                let privileges = grant
+                    .privileges
+                    .iter()
+                    .map(|privilege| format!("{} ({})", privilege, column_name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Self::push_grant(sql, grant, &privileges, table_name);
+            }
+        }
+    }
+
+    fn push_grant(sql: &mut String, grant: &Grant, privileges: &str, table_name: &str) {
+        let grantee = if grant.grantee == "PUBLIC" {
+            "PUBLIC".to_string()
+        } else {
+            Self::force_quote_identifier(&grant.grantee)
+        };
+        sql.push_str(&format!(
+            "\nGRANT {} ON TABLE {} TO {}",
+            privileges, table_name, grantee
+        ));
+        if grant.with_grant_option {
+            sql.push_str(" WITH GRANT OPTION");
+        }
+        sql.push(';');
+    }
+
+    /// Append the WITH (...) storage parameters and TABLESPACE of a table
+    fn push_table_options(sql: &mut String, table: &Table) {
+        sql.push_str(&storage_clause(&table.storage_parameters));
+        if let Some(tablespace) = &table.tablespace {
+            sql.push_str(&format!(
+                " TABLESPACE {}",
+                Self::force_quote_identifier(tablespace)
+            ));
+        }
+    }
+
+    /// Append the row level security switches for a table
+    fn push_row_security(sql: &mut String, table: &Table, table_name: &str) {
+        if table.row_security {
+            sql.push_str(&format!(
+                "\nALTER TABLE {} ENABLE ROW LEVEL SECURITY;",
+                table_name
+            ));
+        }
+        if table.force_row_security {
+            sql.push_str(&format!(
+                "\nALTER TABLE {} FORCE ROW LEVEL SECURITY;",
+                table_name
+            ));
+        }
+    }
 }
 
 impl SqlGenerator for PostgresSqlGenerator {
     fn generate_create_table(&self, table: &Table) -> Result<String> {
-        let table_name = Self::force_quote_identifier(&table.name);
-        let mut sql = format!("CREATE TABLE {} (\n    ", table_name);
+        let table_name = QualifiedName::new(table.schema.clone(), &table.name).to_sql(true);
+        let persistence = if table.unlogged { "UNLOGGED " } else { "" };
+
+        // Partitions take their columns from the parent, only the bound is declared
+        if let (Some(bound), Some(parent)) = (&table.partition_bound, table.inherits.first()) {
+            let mut sql = format!(
+                "CREATE {}TABLE {} PARTITION OF {} {}",
+                persistence,
+                table_name,
+                QualifiedName::parse(parent).to_sql(true),
+                bound
+            );
+            Self::push_table_options(&mut sql, table);
+            sql.push(';');
+            Self::push_row_security(&mut sql, table, &table_name);
+            Self::push_table_grants(&mut sql, table, &table_name);
+            return Ok(sql);
+        }
+
+        let mut sql = format!("CREATE {}TABLE {}", persistence, table_name);
+        // Typed tables take their columns from the composite type
+        if let Some(of_type) = &table.of_type {
+            sql.push_str(&format!(" OF {}", of_type));
+        }
         let mut columns = Vec::new();
 
-        // Add columns
-        for column in &table.columns {
+        // Add columns, leaving inherited ones to the INHERITS clause
+        for column in table.columns.iter().filter(|c| !c.inherited) {
             let column_name = Self::force_quote_identifier(&column.name);
-            let mut col_def = format!("{} {}", column_name, column.type_name);
+            let mut col_def = match &table.of_type {
+                // Only the options a typed table adds to the type's column are declared
+                Some(_) => format!("{} WITH OPTIONS", column_name),
+                None => format!("{} {}", column_name, column.type_name),
+            };
+            let declared = col_def.len();
+            if let Some(compression) = column
+                .compression
+                .as_ref()
+                .filter(|_| table.of_type.is_none())
+            {
+                col_def.push_str(&format!(" COMPRESSION {}", compression));
+            }
+            if let Some(collation) = column.collation.as_deref().filter(|c| *c != "default") {
+                col_def.push_str(&format!(
+                    " COLLATE {}",
+                    Self::force_quote_identifier(collation)
+                ));
+            }
             if !column.nullable {
                 col_def.push_str(" NOT NULL");
             }
@@ -180,45 +319,136 @@ impl SqlGenerator for PostgresSqlGenerator {
                     generated.expression
                 ));
             }
+            if table.of_type.is_some() && col_def.len() == declared {
+                continue;
+            }
             columns.push(col_def);
         }
 
-        // Add constraints
+        // Add constraints under their own names so a replay doesn't rename them
         for constraint in &table.constraints {
-            columns.push(constraint.definition.clone());
+            if constraint.name.is_empty() || constraint.definition.starts_with("CONSTRAINT ") {
+                columns.push(constraint.definition.clone());
+            } else {
+                columns.push(format!(
+                    "CONSTRAINT {} {}",
+                    Self::force_quote_identifier(&constraint.name),
+                    constraint.definition
+                ));
+            }
+        }
+
+        // A table whose columns were all dropped is still valid with an empty list,
+        // a typed table without options leaves the list out entirely
+        if columns.is_empty() {
+            if table.of_type.is_none() {
+                sql.push_str(" ()");
+            }
+        } else {
+            sql.push_str(&format!(" (\n    {}\n)", columns.join(",\n    ")));
         }
 
-        sql.push_str(&columns.join(",\n    "));
-        sql.push_str("\n);");
+        if !table.inherits.is_empty() {
+            let parents: Vec<String> = table
+                .inherits
+                .iter()
+                .map(|parent| QualifiedName::parse(parent).to_sql(true))
+                .collect();
+            sql.push_str(&format!(" INHERITS ({})", parents.join(", ")));
+        }
+
+        if let Some(partition_by) = &table.partition_by {
+            let method = match partition_by.method {
+                PartitionMethod::Range => "RANGE",
+                PartitionMethod::List => "LIST",
+                PartitionMethod::Hash => "HASH",
+            };
+            sql.push_str(&format!(
+                " PARTITION BY {} ({})",
+                method,
+                partition_by.columns.join(", ")
+            ));
+        }
+        Self::push_table_options(&mut sql, table);
+        sql.push(';');
+
+        // Inherited columns keep the parent's default unless the child overrides it
+        for column in table.columns.iter().filter(|c| c.inherited) {
+            if let Some(default) = &column.default {
+                sql.push_str(&format!(
+                    "\nALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
+                    table_name,
+                    Self::force_quote_identifier(&column.name),
+                    default
+                ));
+            }
+        }
+
+        // Storage can't be declared inline, so overrides follow the CREATE TABLE
+        for column in &table.columns {
+            if let Some(storage) = &column.storage {
+                let storage = match storage {
+                    ColumnStorage::Plain => "PLAIN",
+                    ColumnStorage::External => "EXTERNAL",
+                    ColumnStorage::Extended => "EXTENDED",
+                    ColumnStorage::Main => "MAIN",
+                };
+                sql.push_str(&format!(
+                    "\nALTER TABLE {} ALTER COLUMN {} SET STORAGE {};",
+                    table_name,
+                    Self::force_quote_identifier(&column.name),
+                    storage
+                ));
+            }
+        }
+
+        Self::push_row_security(&mut sql, table, &table_name);
+        Self::push_table_grants(&mut sql, table, &table_name);
 
         Ok(sql)
     }
 
     fn generate_alter_table(&self, old: &Table, new: &Table) -> Result<(Vec<String>, Vec<String>)> {
+        self.generate_alter_table_with_options(old, new, &GenerateOptions::default())
+    }
+
+    fn generate_alter_table_with_options(
+        &self,
+        old: &Table,
+        new: &Table,
+        options: &GenerateOptions,
+    ) -> Result<(Vec<String>, Vec<String>)> {
         let mut up_statements = Vec::new();
         let mut down_statements = Vec::new();
 
-        let old_table_name = Self::force_quote_identifier(&old.name);
-        let new_table_name = Self::force_quote_identifier(&new.name);
+        let old_table_name = QualifiedName::new(old.schema.clone(), &old.name).to_sql(true);
+        let new_table_name = QualifiedName::new(new.schema.clone(), &new.name).to_sql(true);
+        let (add_column, drop_column) = if options.idempotent {
+            ("ADD COLUMN IF NOT EXISTS", "DROP COLUMN IF EXISTS")
+        } else {
+            ("ADD COLUMN", "DROP COLUMN")
+        };
 
-        // Handle column changes
+        // Handle column changes, walking columns in declaration order so the
+        // statements come out the same on every run
         let old_columns: std::collections::HashMap<&str, &shem_core::Column> =
             old.columns.iter().map(|c| (c.name.as_str(), c)).collect();
         let new_columns: std::collections::HashMap<&str, &shem_core::Column> =
             new.columns.iter().map(|c| (c.name.as_str(), c)).collect();
 
         // Find dropped columns (in old but not in new)
-        for (col_name, old_col) in &old_columns {
+        for old_col in &old.columns {
+            let col_name = old_col.name.as_str();
             if !new_columns.contains_key(col_name) {
                 let column_name = Self::force_quote_identifier(col_name);
                 up_statements.push(format!(
-                    "ALTER TABLE {} DROP COLUMN {}",
-                    new_table_name, column_name
+                    "ALTER TABLE {} {} {}",
+                    new_table_name, drop_column, column_name
                 ));
                 // Down migration: add the column back
                 let mut col_def = format!(
-                    "ALTER TABLE {} ADD COLUMN {} {}",
-                    old_table_name, column_name, old_col.type_name
+                    "ALTER TABLE {} {} {} {}",
+                    old_table_name, add_column, column_name, old_col.type_name
                 );
                 if !old_col.nullable {
                     col_def.push_str(" NOT NULL");
@@ -244,12 +474,13 @@ impl SqlGenerator for PostgresSqlGenerator {
         }
 
         // Find added columns (in new but not in old)
-        for (col_name, new_col) in &new_columns {
+        for new_col in &new.columns {
+            let col_name = new_col.name.as_str();
             if !old_columns.contains_key(col_name) {
                 let column_name = Self::force_quote_identifier(col_name);
                 let mut col_def = format!(
-                    "ALTER TABLE {} ADD COLUMN {} {}",
-                    new_table_name, column_name, new_col.type_name
+                    "ALTER TABLE {} {} {} {}",
+                    new_table_name, add_column, column_name, new_col.type_name
                 );
                 if !new_col.nullable {
                     col_def.push_str(" NOT NULL");
@@ -272,26 +503,40 @@ impl SqlGenerator for PostgresSqlGenerator {
                 }
                 up_statements.push(col_def);
                 down_statements.push(format!(
-                    "ALTER TABLE {} DROP COLUMN {}",
-                    old_table_name, column_name
+                    "ALTER TABLE {} {} {}",
+                    old_table_name, drop_column, column_name
                 ));
             }
         }
 
         // Find modified columns (in both old and new but different)
-        for (col_name, new_col) in &new_columns {
+        for new_col in &new.columns {
+            let col_name = new_col.name.as_str();
             if let Some(old_col) = old_columns.get(col_name) {
                 let column_name = Self::force_quote_identifier(col_name);
 
                 // Check for type changes
-                if old_col.type_name != new_col.type_name {
+                if !types_equivalent(&old_col.type_name, &new_col.type_name) {
+                    let key = format!(
+                        "{}.{}",
+                        QualifiedName::key(&new.schema, &new.name),
+                        col_name
+                    );
+                    let using = match options.column_using.get(&key) {
+                        Some(expression) => expression.clone(),
+                        None => format!("{}::{}", column_name, new_col.type_name),
+                    };
                     up_statements.push(format!(
-                        "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
-                        new_table_name, column_name, new_col.type_name
+                        "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}",
+                        new_table_name, column_name, new_col.type_name, using
                     ));
                     down_statements.push(format!(
-                        "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
-                        old_table_name, column_name, old_col.type_name
+                        "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{}",
+                        old_table_name,
+                        column_name,
+                        old_col.type_name,
+                        column_name,
+                        old_col.type_name
                     ));
                 }
 
@@ -319,7 +564,7 @@ impl SqlGenerator for PostgresSqlGenerator {
                 }
 
                 // Check for default value changes
-                if old_col.default != new_col.default {
+                if !defaults_equivalent(old_col.default.as_deref(), new_col.default.as_deref()) {
                     match &new_col.default {
                         Some(default) => {
                             up_statements.push(format!(
@@ -428,70 +673,84 @@ impl SqlGenerator for PostgresSqlGenerator {
             }
         }
 
-        // Handle constraint changes
-        let old_constraints: std::collections::HashMap<&str, &shem_core::Constraint> = old
-            .constraints
-            .iter()
-            .map(|c| (c.name.as_str(), c))
-            .collect();
-        let new_constraints: std::collections::HashMap<&str, &shem_core::Constraint> = new
-            .constraints
-            .iter()
-            .map(|c| (c.name.as_str(), c))
-            .collect();
-
-        // Find dropped constraints (in old but not in new)
-        for (constraint_name, old_constraint) in &old_constraints {
-            if !new_constraints.contains_key(constraint_name) {
-                up_statements.push(format!(
+        // Constraints are matched by name, so a renamed one is dropped and added.
+        // Drops run before the column changes, since dropping a column takes its
+        // constraints with it, and additions after, once new columns exist
+        let mut drop_up = Vec::new();
+        let mut drop_down = Vec::new();
+        for old_constraint in &old.constraints {
+            let changed = new
+                .constraints
+                .iter()
+                .find(|c| c.name == old_constraint.name)
+                .is_none_or(|c| c.definition != old_constraint.definition);
+            if changed {
+                let constraint_name = Self::force_quote_identifier(&old_constraint.name);
+                drop_up.push(format!(
                     "ALTER TABLE {} DROP CONSTRAINT {}",
                     new_table_name, constraint_name
                 ));
-                down_statements.push(format!(
+                drop_down.push(format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} {}",
                     old_table_name, constraint_name, old_constraint.definition
                 ));
             }
         }
-
-        // Find added constraints (in new but not in old)
-        for (constraint_name, new_constraint) in &new_constraints {
-            if !old_constraints.contains_key(constraint_name) {
-                up_statements.push(format!(
+        let mut add_up = Vec::new();
+        let mut add_down = Vec::new();
+        for new_constraint in &new.constraints {
+            let changed = old
+                .constraints
+                .iter()
+                .find(|c| c.name == new_constraint.name)
+                .is_none_or(|c| c.definition != new_constraint.definition);
+            if changed {
+                let constraint_name = Self::force_quote_identifier(&new_constraint.name);
+                add_up.push(format!(
                     "ALTER TABLE {} ADD CONSTRAINT {} {}",
                     new_table_name, constraint_name, new_constraint.definition
                 ));
-                down_statements.push(format!(
+                add_down.push(format!(
                     "ALTER TABLE {} DROP CONSTRAINT {}",
                     old_table_name, constraint_name
                 ));
             }
         }
+        up_statements.splice(0..0, drop_up);
+        up_statements.extend(add_up);
+        down_statements.splice(0..0, add_down);
+        down_statements.extend(drop_down);
 
-        // Find modified constraints (in both old and new but different)
-        for (constraint_name, new_constraint) in &new_constraints {
-            if let Some(old_constraint) = old_constraints.get(constraint_name) {
-                if old_constraint.definition != new_constraint.definition {
-                    // Drop and recreate the constraint
-                    up_statements.push(format!(
-                        "ALTER TABLE {} DROP CONSTRAINT {}",
-                        new_table_name, constraint_name
-                    ));
-                    up_statements.push(format!(
-                        "ALTER TABLE {} ADD CONSTRAINT {} {}",
-                        new_table_name, constraint_name, new_constraint.definition
-                    ));
-
-                    down_statements.push(format!(
-                        "ALTER TABLE {} DROP CONSTRAINT {}",
-                        old_table_name, constraint_name
-                    ));
-                    down_statements.push(format!(
-                        "ALTER TABLE {} ADD CONSTRAINT {} {}",
-                        old_table_name, constraint_name, old_constraint.definition
-                    ));
-                }
-            }
+        // ENABLE and FORCE are independent switches, FORCE only matters once enabled
+        if old.row_security != new.row_security {
+            let (up, down) = if new.row_security {
+                ("ENABLE", "DISABLE")
+            } else {
+                ("DISABLE", "ENABLE")
+            };
+            up_statements.push(format!(
+                "ALTER TABLE {} {} ROW LEVEL SECURITY",
+                new_table_name, up
+            ));
+            down_statements.push(format!(
+                "ALTER TABLE {} {} ROW LEVEL SECURITY",
+                old_table_name, down
+            ));
+        }
+        if old.force_row_security != new.force_row_security {
+            let (up, down) = if new.force_row_security {
+                ("FORCE", "NO FORCE")
+            } else {
+                ("NO FORCE", "FORCE")
+            };
+            up_statements.push(format!(
+                "ALTER TABLE {} {} ROW LEVEL SECURITY",
+                new_table_name, up
+            ));
+            down_statements.push(format!(
+                "ALTER TABLE {} {} ROW LEVEL SECURITY",
+                old_table_name, down
+            ));
         }
 
         Ok((up_statements, down_statements))
@@ -510,13 +769,18 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn generate_drop_table(&self, table: &Table) -> Result<String> {
-        let table_name = Self::force_quote_identifier(&table.name);
+        let table_name = QualifiedName::new(table.schema.clone(), &table.name).to_sql(true);
         Ok(format!("DROP TABLE IF EXISTS {} CASCADE;", table_name))
     }
 
     fn create_view(&self, view: &View) -> Result<String> {
         let view_name = Self::force_quote_identifier(&view.name);
-        let mut sql = format!("CREATE VIEW {} AS {}", view_name, view.definition);
+        // Introspected definitions end with their own semicolon
+        let mut sql = format!(
+            "CREATE VIEW {} AS {}",
+            view_name,
+            view.definition.trim_end().trim_end_matches(';')
+        );
         match view.check_option {
             CheckOption::None => {}
             CheckOption::Local => sql.push_str(" WITH LOCAL CHECK OPTION"),
@@ -538,7 +802,9 @@ impl SqlGenerator for PostgresSqlGenerator {
 
         Ok(format!(
             "CREATE MATERIALIZED VIEW {} AS {}\n{};",
-            view_name, view.definition, with_clause
+            view_name,
+            view.definition.trim_end().trim_end_matches(';'),
+            with_clause
         ))
     }
 
@@ -566,8 +832,14 @@ impl SqlGenerator for PostgresSqlGenerator {
         let returns = format!("RETURNS {}", function.returns.type_name);
 
         Ok(format!(
-            "CREATE OR REPLACE FUNCTION {}.{}({}) {} LANGUAGE {} AS $function$\n{}\n$function$;",
-            schema, function_name, params, returns, language, body
+            "CREATE OR REPLACE FUNCTION {}.{}({}) {} LANGUAGE {}{} AS $function$\n{}\n$function$;",
+            schema,
+            function_name,
+            params,
+            returns,
+            language,
+            function.attributes(),
+            body
         ))
     }
 
@@ -599,14 +871,7 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn create_enum(&self, enum_type: &EnumType) -> Result<String> {
-        let enum_name = match &enum_type.schema {
-            Some(schema) => format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&enum_type.name)
-            ),
-            None => Self::force_quote_identifier(&enum_type.name),
-        };
+        let enum_name = QualifiedName::new(enum_type.schema.clone(), &enum_type.name).to_sql(true);
 
         let values = enum_type
             .values
@@ -623,10 +888,7 @@ impl SqlGenerator for PostgresSqlGenerator {
         let mut down_statements = Vec::new();
 
         // Get the enum name with schema
-        let enum_name = match &new.schema {
-            Some(schema) => format!("{}.{}", schema, Self::force_quote_identifier(&new.name)),
-            None => Self::force_quote_identifier(&new.name),
-        };
+        let enum_name = QualifiedName::new(new.schema.clone(), &new.name).to_sql(true);
 
         // Find values that are in new but not in old (added values)
         let old_values: std::collections::HashSet<&str> =
@@ -694,23 +956,28 @@ impl SqlGenerator for PostgresSqlGenerator {
         let domain_name = Self::force_quote_identifier(&domain.name);
         let mut sql = format!("CREATE DOMAIN {} AS {}", domain_name, domain.base_type);
 
-        // Add constraints
-        let check_expr = domain
-            .constraints
-            .iter()
-            .map(|c| &c.check)
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(" AND ");
-        if !check_expr.is_empty() {
-            sql.push_str(&format!(" CHECK ({})", check_expr));
-        }
         if let Some(default) = &domain.default {
             sql.push_str(&format!(" DEFAULT {}", default));
         }
         if domain.not_null {
             sql.push_str(" NOT NULL");
         }
+
+        // Add constraints, keeping their names so they can be dropped individually
+        for constraint in &domain.constraints {
+            if let Some(name) = &constraint.name {
+                sql.push_str(&format!(
+                    " CONSTRAINT {}",
+                    Self::force_quote_identifier(name)
+                ));
+            }
+            // Introspected checks come from pg_get_constraintdef and already carry CHECK
+            if constraint.check.starts_with("CHECK ") {
+                sql.push_str(&format!(" {}", constraint.check));
+            } else {
+                sql.push_str(&format!(" CHECK ({})", constraint.check));
+            }
+        }
         sql.push(';');
         Ok(sql)
     }
@@ -864,16 +1131,24 @@ impl SqlGenerator for PostgresSqlGenerator {
         } else {
             Self::force_quote_identifier(&trigger.name)
         };
-        let table_name = Self::force_quote_identifier(&trigger.table);
+        let table_name = QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(true);
 
-        let events: Vec<&str> = trigger
+        let events: Vec<String> = trigger
             .events
             .iter()
             .map(|e| match e {
-                TriggerEvent::Insert => "INSERT",
-                TriggerEvent::Update { .. } => "UPDATE",
-                TriggerEvent::Delete => "DELETE",
-                TriggerEvent::Truncate => "TRUNCATE",
+                TriggerEvent::Insert => "INSERT".to_string(),
+                TriggerEvent::Update if !trigger.update_columns.is_empty() => {
+                    let columns: Vec<String> = trigger
+                        .update_columns
+                        .iter()
+                        .map(|c| Self::force_quote_identifier(c))
+                        .collect();
+                    format!("UPDATE OF {}", columns.join(", "))
+                }
+                TriggerEvent::Update => "UPDATE".to_string(),
+                TriggerEvent::Delete => "DELETE".to_string(),
+                TriggerEvent::Truncate => "TRUNCATE".to_string(),
             })
             .collect();
 
@@ -897,7 +1172,7 @@ impl SqlGenerator for PostgresSqlGenerator {
             "()".to_string()
         };
 
-        let when = if let Some(condition) = &trigger.condition {
+        let when = if let Some(condition) = trigger.when.as_ref().or(trigger.condition.as_ref()) {
             format!(" WHEN ({})", condition)
         } else {
             String::new()
@@ -947,6 +1222,17 @@ impl SqlGenerator for PostgresSqlGenerator {
         }
 
         sql.push(';');
+
+        // Add comment if present
+        if let Some(comment) = &policy.comment {
+            sql.push_str(&format!(
+                "\nCOMMENT ON POLICY {} ON {} IS '{}';",
+                policy_name,
+                table_name,
+                comment.replace('\'', "''")
+            ));
+        }
+
         Ok(sql)
     }
 
@@ -983,20 +1269,12 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_view(&self, view: &View) -> Result<String> {
-        let name = if let Some(schema) = &view.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&view.name))
-        } else {
-            Self::force_quote_identifier(&view.name)
-        };
+        let name = QualifiedName::new(view.schema.clone(), &view.name).to_sql(true);
         Ok(format!("DROP VIEW IF EXISTS {} CASCADE;", name))
     }
 
     fn drop_materialized_view(&self, view: &MaterializedView) -> Result<String> {
-        let name = if let Some(schema) = &view.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&view.name))
-        } else {
-            Self::force_quote_identifier(&view.name)
-        };
+        let name = QualifiedName::new(view.schema.clone(), &view.name).to_sql(true);
         Ok(format!(
             "DROP MATERIALIZED VIEW IF EXISTS {} CASCADE;",
             name
@@ -1004,11 +1282,7 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_function(&self, func: &Function) -> Result<String> {
-        let name = if let Some(schema) = &func.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&func.name))
-        } else {
-            Self::force_quote_identifier(&func.name)
-        };
+        let name = QualifiedName::new(func.schema.clone(), &func.name).to_sql(true);
 
         // Build parameter signature for function identification
         let params = func
@@ -1031,11 +1305,7 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_procedure(&self, proc: &Procedure) -> Result<String> {
-        let name = if let Some(schema) = &proc.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&proc.name))
-        } else {
-            Self::force_quote_identifier(&proc.name)
-        };
+        let name = QualifiedName::new(proc.schema.clone(), &proc.name).to_sql(true);
 
         // Build parameter signature for procedure identification
         let params = proc
@@ -1058,20 +1328,12 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_domain(&self, domain: &Domain) -> Result<String> {
-        let name = if let Some(schema) = &domain.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&domain.name))
-        } else {
-            Self::force_quote_identifier(&domain.name)
-        };
+        let name = QualifiedName::new(domain.schema.clone(), &domain.name).to_sql(true);
         Ok(format!("DROP DOMAIN IF EXISTS {} CASCADE;", name))
     }
 
     fn drop_sequence(&self, seq: &Sequence) -> Result<String> {
-        let name = if let Some(schema) = &seq.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&seq.name))
-        } else {
-            Self::force_quote_identifier(&seq.name)
-        };
+        let name = QualifiedName::new(seq.schema.clone(), &seq.name).to_sql(true);
         Ok(format!("DROP SEQUENCE IF EXISTS {} CASCADE;", name))
     }
 
@@ -1096,21 +1358,9 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_trigger(&self, trigger: &Trigger) -> Result<String> {
-        let trigger_name = if let Some(schema) = &trigger.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&trigger.name))
-        } else {
-            Self::force_quote_identifier(&trigger.name)
-        };
+        let trigger_name = Self::force_quote_identifier(&trigger.name);
 
-        let table_name = if let Some(schema) = &trigger.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&trigger.table)
-            )
-        } else {
-            Self::force_quote_identifier(&trigger.table)
-        };
+        let table_name = QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(true);
 
         Ok(format!(
             "DROP TRIGGER IF EXISTS {} ON {} CASCADE;",
@@ -1119,17 +1369,9 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_policy(&self, policy: &Policy) -> Result<String> {
-        let policy_name = if let Some(schema) = &policy.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&policy.name))
-        } else {
-            Self::force_quote_identifier(&policy.name)
-        };
+        let policy_name = Self::force_quote_identifier(&policy.name);
 
-        let table_name = if let Some(schema) = &policy.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&policy.table))
-        } else {
-            Self::force_quote_identifier(&policy.table)
-        };
+        let table_name = QualifiedName::new(policy.schema.clone(), &policy.table).to_sql(true);
 
         Ok(format!(
             "DROP POLICY IF EXISTS {} ON {} CASCADE;",
@@ -1179,15 +1421,16 @@ impl SqlGenerator for PostgresSqlGenerator {
                 if let Some(expr) = &col.expression {
                     col_def = format!("({})", expr);
                 }
+                // The operator class has to precede the ordering options
+                if let Some(opclass) = &col.opclass {
+                    col_def.push_str(&format!(" {}", opclass));
+                }
                 if col.order == SortOrder::Descending {
                     col_def.push_str(" DESC");
                 }
                 if col.nulls_first {
                     col_def.push_str(" NULLS FIRST");
                 }
-                if let Some(opclass) = &col.opclass {
-                    col_def.push_str(&format!(" {}", opclass));
-                }
                 col_def
             })
             .collect::<Vec<_>>()
@@ -1195,24 +1438,25 @@ impl SqlGenerator for PostgresSqlGenerator {
         sql.push_str(&columns);
         sql.push_str(")");
 
-        if let Some(where_clause) = &index.where_clause {
-            sql.push_str(&format!(" WHERE {}", where_clause));
+        if !index.include.is_empty() {
+            let include = index
+                .include
+                .iter()
+                .map(|column| Self::force_quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            sql.push_str(&format!(" INCLUDE ({})", include));
         }
 
+        // PostgreSQL only accepts these clauses in this order
+        sql.push_str(&storage_clause(&index.storage_parameters));
+
         if let Some(tablespace) = &index.tablespace {
             sql.push_str(&format!(" TABLESPACE {}", tablespace));
         }
 
-        if !index.storage_parameters.is_empty() {
-            sql.push_str(" WITH (");
-            let params = index
-                .storage_parameters
-                .iter()
-                .map(|(k, v)| format!("{} = {}", k, v))
-                .collect::<Vec<_>>()
-                .join(", ");
-            sql.push_str(&params);
-            sql.push_str(")");
+        if let Some(where_clause) = &index.where_clause {
+            sql.push_str(&format!(" WHERE {}", where_clause));
         }
 
         sql.push(';');
@@ -1227,13 +1471,10 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn create_collation(&self, collation: &Collation) -> Result<String> {
-        let collation_name = Self::force_quote_identifier(&collation.name);
+        let collation_name =
+            QualifiedName::new(collation.schema.clone(), &collation.name).to_sql(true);
         let mut sql = format!("CREATE COLLATION {}", collation_name);
 
-        if let Some(schema) = &collation.schema {
-            sql = format!("CREATE COLLATION {}.{}", schema, collation_name);
-        }
-
         if let Some(locale) = &collation.locale {
             sql.push_str(&format!(" (LOCALE = '{}')", locale));
         } else if let (Some(lc_collate), Some(lc_ctype)) =
@@ -1263,15 +1504,7 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_collation(&self, collation: &Collation) -> Result<String> {
-        let name = if let Some(schema) = &collation.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&collation.name)
-            )
-        } else {
-            Self::force_quote_identifier(&collation.name)
-        };
+        let name = QualifiedName::new(collation.schema.clone(), &collation.name).to_sql(true);
         Ok(format!("DROP COLLATION IF EXISTS {} CASCADE;", name))
     }
 
@@ -1282,11 +1515,7 @@ impl SqlGenerator for PostgresSqlGenerator {
             Self::force_quote_identifier(&rule.name)
         };
 
-        let table_name = if let Some(schema) = &rule.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&rule.table))
-        } else {
-            Self::force_quote_identifier(&rule.table)
-        };
+        let table_name = QualifiedName::new(rule.schema.clone(), &rule.table).to_sql(true);
 
         let event_str = match rule.event {
             RuleEvent::Select => "TO SELECT",
@@ -1324,21 +1553,24 @@ impl SqlGenerator for PostgresSqlGenerator {
         }
 
         sql.push(';');
+
+        // Add comment if present
+        if let Some(comment) = &rule.comment {
+            sql.push_str(&format!(
+                "\nCOMMENT ON RULE {} ON {} IS '{}';",
+                rule_name,
+                table_name,
+                comment.replace('\'', "''")
+            ));
+        }
+
         Ok(sql)
     }
 
     fn drop_rule(&self, rule: &Rule) -> Result<String> {
-        let rule_name = if let Some(schema) = &rule.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&rule.name))
-        } else {
-            Self::force_quote_identifier(&rule.name)
-        };
+        let rule_name = Self::force_quote_identifier(&rule.name);
 
-        let table_name = if let Some(schema) = &rule.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&rule.table))
-        } else {
-            Self::force_quote_identifier(&rule.table)
-        };
+        let table_name = QualifiedName::new(rule.schema.clone(), &rule.table).to_sql(true);
 
         Ok(format!(
             "DROP RULE IF EXISTS {} ON {} CASCADE;",
@@ -1393,15 +1625,7 @@ impl SqlGenerator for PostgresSqlGenerator {
         } else {
             Self::force_quote_identifier(&trigger.name)
         };
-        let table_name = if let Some(schema) = &trigger.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&trigger.table)
-            )
-        } else {
-            Self::force_quote_identifier(&trigger.table)
-        };
+        let table_name = QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(true);
 
         let events: Vec<&str> = trigger
             .events
@@ -1444,21 +1668,9 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_constraint_trigger(&self, trigger: &ConstraintTrigger) -> Result<String> {
-        let trigger_name = if let Some(schema) = &trigger.schema {
-            format!("{}.{}", schema, Self::force_quote_identifier(&trigger.name))
-        } else {
-            Self::force_quote_identifier(&trigger.name)
-        };
+        let trigger_name = Self::force_quote_identifier(&trigger.name);
 
-        let table_name = if let Some(schema) = &trigger.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&trigger.table)
-            )
-        } else {
-            Self::force_quote_identifier(&trigger.table)
-        };
+        let table_name = QualifiedName::new(trigger.schema.clone(), &trigger.table).to_sql(true);
 
         Ok(format!(
             "DROP TRIGGER IF EXISTS {} ON {} CASCADE;",
@@ -1498,15 +1710,7 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn create_base_type(&self, base_type: &BaseType) -> Result<String> {
-        let type_name = if let Some(schema) = &base_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&base_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&base_type.name)
-        };
+        let type_name = QualifiedName::new(base_type.schema.clone(), &base_type.name).to_sql(true);
 
         let mut sql = format!("CREATE TYPE {} AS (", type_name);
 
@@ -1561,91 +1765,45 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_base_type(&self, base_type: &BaseType) -> Result<String> {
-        let type_name = if let Some(schema) = &base_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&base_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&base_type.name)
-        };
+        let type_name = QualifiedName::new(base_type.schema.clone(), &base_type.name).to_sql(true);
         Ok(format!("DROP TYPE IF EXISTS {} CASCADE;", type_name))
     }
 
     fn create_array_type(&self, array_type: &ArrayType) -> Result<String> {
-        let type_name = if let Some(schema) = &array_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&array_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&array_type.name)
-        };
+        let type_name =
+            QualifiedName::new(array_type.schema.clone(), &array_type.name).to_sql(true);
 
-        let element_type = if let Some(element_schema) = &array_type.element_schema {
-            format!(
-                "{}.{}",
-                element_schema,
-                Self::force_quote_identifier(&array_type.element_type)
-            )
-        } else {
-            Self::force_quote_identifier(&array_type.element_type)
-        };
+        let element_type =
+            QualifiedName::new(array_type.element_schema.clone(), &array_type.element_type)
+                .to_sql(true);
 
         let sql = format!("CREATE TYPE {} AS ARRAY OF {};", type_name, element_type);
         Ok(sql)
     }
 
     fn drop_array_type(&self, array_type: &ArrayType) -> Result<String> {
-        let type_name = if let Some(schema) = &array_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&array_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&array_type.name)
-        };
+        let type_name =
+            QualifiedName::new(array_type.schema.clone(), &array_type.name).to_sql(true);
         Ok(format!("DROP TYPE IF EXISTS {} CASCADE;", type_name))
     }
 
     fn create_multirange_type(&self, multirange_type: &MultirangeType) -> Result<String> {
-        let type_name = if let Some(schema) = &multirange_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&multirange_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&multirange_type.name)
-        };
+        let type_name =
+            QualifiedName::new(multirange_type.schema.clone(), &multirange_type.name).to_sql(true);
 
-        let range_type = if let Some(range_schema) = &multirange_type.range_schema {
-            format!(
-                "{}.{}",
-                range_schema,
-                Self::force_quote_identifier(&multirange_type.range_type)
-            )
-        } else {
-            Self::force_quote_identifier(&multirange_type.range_type)
-        };
+        let range_type = QualifiedName::new(
+            multirange_type.range_schema.clone(),
+            &multirange_type.range_type,
+        )
+        .to_sql(true);
 
         let sql = format!("CREATE TYPE {} AS MULTIRANGE OF {};", type_name, range_type);
         Ok(sql)
     }
 
     fn drop_multirange_type(&self, multirange_type: &MultirangeType) -> Result<String> {
-        let type_name = if let Some(schema) = &multirange_type.schema {
-            format!(
-                "{}.{}",
-                schema,
-                Self::force_quote_identifier(&multirange_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&multirange_type.name)
-        };
+        let type_name =
+            QualifiedName::new(multirange_type.schema.clone(), &multirange_type.name).to_sql(true);
         Ok(format!("DROP TYPE IF EXISTS {} CASCADE;", type_name))
     }
 
@@ -1663,8 +1821,11 @@ impl SqlGenerator for PostgresSqlGenerator {
         if role.createrole {
             sql.push_str(" CREATEROLE");
         }
+        // INHERIT is the default, so only its absence needs spelling out
         if role.inherit {
             sql.push_str(" INHERIT");
+        } else {
+            sql.push_str(" NOINHERIT");
         }
         if role.login {
             sql.push_str(" LOGIN");
@@ -1672,6 +1833,9 @@ impl SqlGenerator for PostgresSqlGenerator {
         if role.replication {
             sql.push_str(" REPLICATION");
         }
+        if role.bypass_rls {
+            sql.push_str(" BYPASSRLS");
+        }
 
         // Add connection limit
         if let Some(limit) = role.connection_limit {
@@ -1697,6 +1861,16 @@ impl SqlGenerator for PostgresSqlGenerator {
         }
 
         sql.push(';');
+
+        for (name, value) in &role.settings {
+            sql.push_str(&format!(
+                "\nALTER ROLE {} SET {} TO {};",
+                role_name,
+                name,
+                Self::role_setting_value(name, value)
+            ));
+        }
+
         Ok(sql)
     }
 
@@ -1798,15 +1972,8 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn create_composite_type(&self, composite_type: &CompositeType) -> Result<String> {
-        let type_name = if let Some(schema) = &composite_type.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&composite_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&composite_type.name)
-        };
+        let type_name =
+            QualifiedName::new(composite_type.schema.clone(), &composite_type.name).to_sql(true);
 
         let mut sql = format!("CREATE TYPE {} AS (", type_name);
 
@@ -1831,33 +1998,29 @@ impl SqlGenerator for PostgresSqlGenerator {
                 comment.replace('\'', "''")
             ));
         }
+        for attr in &composite_type.attributes {
+            if let Some(comment) = &attr.comment {
+                sql.push_str(&format!(
+                    "\nCOMMENT ON COLUMN {}.{} IS '{}';",
+                    type_name,
+                    Self::force_quote_identifier(&attr.name),
+                    comment.replace('\'', "''")
+                ));
+            }
+        }
 
         Ok(sql)
     }
 
     fn drop_composite_type(&self, composite_type: &CompositeType) -> Result<String> {
-        let type_name = if let Some(schema) = &composite_type.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&composite_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&composite_type.name)
-        };
+        let type_name =
+            QualifiedName::new(composite_type.schema.clone(), &composite_type.name).to_sql(true);
         Ok(format!("DROP TYPE IF EXISTS {} CASCADE;", type_name))
     }
 
     fn create_range_type(&self, range_type: &RangeType) -> Result<String> {
-        let type_name = if let Some(schema) = &range_type.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&range_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&range_type.name)
-        };
+        let type_name =
+            QualifiedName::new(range_type.schema.clone(), &range_type.name).to_sql(true);
 
         let mut sql = format!("CREATE TYPE {} AS RANGE (", type_name);
         sql.push_str(&format!("SUBTYPE = {}", range_type.subtype));
@@ -1897,15 +2060,8 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_range_type(&self, range_type: &RangeType) -> Result<String> {
-        let type_name = if let Some(schema) = &range_type.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&range_type.name)
-            )
-        } else {
-            Self::force_quote_identifier(&range_type.name)
-        };
+        let type_name =
+            QualifiedName::new(range_type.schema.clone(), &range_type.name).to_sql(true);
         Ok(format!("DROP TYPE IF EXISTS {} CASCADE;", type_name))
     }
 
@@ -1945,15 +2101,8 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn create_foreign_table(&self, foreign_table: &ForeignTable) -> Result<String> {
-        let table_name = if let Some(schema) = &foreign_table.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&foreign_table.name)
-            )
-        } else {
-            Self::force_quote_identifier(&foreign_table.name)
-        };
+        let table_name =
+            QualifiedName::new(foreign_table.schema.clone(), &foreign_table.name).to_sql(true);
 
         let server_name = Self::force_quote_identifier(&foreign_table.server);
 
@@ -1999,15 +2148,8 @@ impl SqlGenerator for PostgresSqlGenerator {
     }
 
     fn drop_foreign_table(&self, foreign_table: &ForeignTable) -> Result<String> {
-        let table_name = if let Some(schema) = &foreign_table.schema {
-            format!(
-                "{}.{}",
-                Self::force_quote_identifier(schema),
-                Self::force_quote_identifier(&foreign_table.name)
-            )
-        } else {
-            Self::force_quote_identifier(&foreign_table.name)
-        };
+        let table_name =
+            QualifiedName::new(foreign_table.schema.clone(), &foreign_table.name).to_sql(true);
         Ok(format!(
             "DROP FOREIGN TABLE IF EXISTS {} CASCADE;",
             table_name
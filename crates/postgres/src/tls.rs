@@ -0,0 +1,196 @@
+//! TLS for driver connections, chosen by the connection string's `sslmode`
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, ring};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use shem_core::{Error, Result};
+use std::path::Path;
+use std::sync::Arc;
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+/// `sslmode` connection parameter, with libpq's meaning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SslMode {
+    /// Never use TLS
+    Disable,
+    /// Use TLS when the server offers it, without verifying its certificate
+    #[default]
+    Prefer,
+    /// Always use TLS, without verifying the server certificate
+    Require,
+    /// Always use TLS and verify the certificate chain and host name
+    VerifyFull,
+}
+
+impl SslMode {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "disable" => Ok(SslMode::Disable),
+            "prefer" => Ok(SslMode::Prefer),
+            "require" => Ok(SslMode::Require),
+            "verify-full" => Ok(SslMode::VerifyFull),
+            other => Err(Error::Database(format!(
+                "Unsupported sslmode '{}', expected disable, prefer, require or verify-full",
+                other
+            ))),
+        }
+    }
+
+    /// Mode `tokio_postgres` negotiates with; it only knows whether TLS is optional
+    pub(crate) fn negotiation(self) -> tokio_postgres::config::SslMode {
+        match self {
+            SslMode::Disable => tokio_postgres::config::SslMode::Disable,
+            SslMode::Prefer => tokio_postgres::config::SslMode::Prefer,
+            SslMode::Require | SslMode::VerifyFull => tokio_postgres::config::SslMode::Require,
+        }
+    }
+}
+
+/// Connector a connection is opened with
+pub enum TlsConnector {
+    NoTls,
+    Rustls(MakeRustlsConnect),
+}
+
+/// Strip `sslmode` from a URL or key/value connection string, returning the rest
+/// of the string and the requested mode (`prefer` when absent)
+pub fn split_ssl_mode(url: &str) -> Result<(String, SslMode)> {
+    let mut mode = SslMode::default();
+
+    if url.contains("://") {
+        let Some((base, query)) = url.split_once('?') else {
+            return Ok((url.to_string(), mode));
+        };
+        let mut params = Vec::new();
+        for param in query.split('&') {
+            match param.strip_prefix("sslmode=") {
+                Some(value) => mode = SslMode::parse(value)?,
+                None => params.push(param),
+            }
+        }
+        if params.is_empty() {
+            return Ok((base.to_string(), mode));
+        }
+        return Ok((format!("{}?{}", base, params.join("&")), mode));
+    }
+
+    let mut params = Vec::new();
+    for param in url.split_whitespace() {
+        match param.strip_prefix("sslmode=") {
+            Some(value) => mode = SslMode::parse(value)?,
+            None => params.push(param),
+        }
+    }
+    Ok((params.join(" "), mode))
+}
+
+/// Build the connector for `mode`; `verify-full` trusts the PEM certificates in
+/// `root_cert` when given and the bundled web PKI roots otherwise
+pub fn connector(mode: SslMode, root_cert: Option<&Path>) -> Result<TlsConnector> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?;
+
+    let config = match mode {
+        SslMode::Disable => return Ok(TlsConnector::NoTls),
+        SslMode::Prefer | SslMode::Require => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+            .with_no_client_auth(),
+        SslMode::VerifyFull => builder
+            .with_root_certificates(root_store(root_cert)?)
+            .with_no_client_auth(),
+    };
+    Ok(TlsConnector::Rustls(MakeRustlsConnect::new(config)))
+}
+
+fn root_store(root_cert: Option<&Path>) -> Result<RootCertStore> {
+    let Some(path) = root_cert else {
+        return Ok(RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        });
+    };
+
+    let mut roots = RootCertStore::empty();
+    let certs = CertificateDer::pem_file_iter(path).map_err(|e| {
+        Error::Database(format!(
+            "Failed to read root certificate {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    for cert in certs {
+        let cert = cert.map_err(|e| {
+            Error::Database(format!(
+                "Invalid root certificate {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        roots.add(cert).map_err(tls_error)?;
+    }
+    if roots.is_empty() {
+        return Err(Error::Database(format!(
+            "No certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(roots)
+}
+
+fn tls_error(e: rustls::Error) -> Error {
+    Error::Database(format!("TLS configuration error: {}", e))
+}
+
+/// Skips certificate checks for `prefer` and `require`, which only ask for an
+/// encrypted channel; handshake signatures are still verified
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
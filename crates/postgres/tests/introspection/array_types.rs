@@ -66,7 +66,7 @@ async fn test_introspect_array_type_with_schema() -> Result<(), Box<dyn std::err
     let schema = connection.introspect().await?;
 
     // The auto-created array type will be named "_schema_type"
-    let array_type = schema.array_types.get("_schema_type");
+    let array_type = schema.array_types.get("test_array_types._schema_type");
     assert!(
         array_type.is_some(),
         "Array type '_schema_type' should be introspected"
@@ -405,12 +405,12 @@ async fn test_introspect_array_type_schema_consistency() -> Result<(), Box<dyn s
 
     let at1 = schema1
         .array_types
-        .get("_schema_consistency_type")
+        .get("test_array_schema._schema_consistency_type")
         .expect("Array type '_schema_consistency_type' should exist in schema1");
 
     let at2 = schema2
         .array_types
-        .get("_schema_consistency_type")
+        .get("test_array_schema._schema_consistency_type")
         .expect("Array type '_schema_consistency_type' should exist in schema2");
 
     // Verify consistency across multiple introspections
@@ -443,7 +443,7 @@ async fn test_introspect_array_type_element_schema() -> Result<(), Box<dyn std::
     let schema = connection.introspect().await?;
 
     // PostgreSQL automatically creates the array type with name '_element_type' in the same schema.
-    let array_type = schema.array_types.get("_element_type");
+    let array_type = schema.array_types.get("test_element_schema._element_type");
     assert!(
         array_type.is_some(),
         "Array type '_element_type' should be introspected automatically"
@@ -66,7 +66,7 @@ async fn test_introspect_base_type_with_schema() -> Result<(), Box<dyn std::erro
     let schema = connection.introspect().await?;
 
     // Verify the composite type was introspected with correct schema
-    let composite_type = schema.composite_types.get("schema_type");
+    let composite_type = schema.composite_types.get("test_base_types.schema_type");
     assert!(
         composite_type.is_some(),
         "Composite type 'schema_type' should be introspected"
@@ -310,6 +310,29 @@ async fn test_introspect_no_base_types() -> Result<(), Box<dyn std::error::Error
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_array_types_are_not_base_types() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Every enum, composite type and table comes with an implicit array type,
+    // which pg_type lists as a base type in the array category
+    execute_sql(&connection, "CREATE TYPE mood AS ENUM ('happy', 'sad');").await?;
+    execute_sql(&connection, "CREATE TABLE diary (id integer);").await?;
+
+    let schema = connection.introspect().await?;
+    let base_types: Vec<&String> = schema.base_types.keys().collect();
+    assert!(base_types.is_empty(), "Array types listed as base types: {:?}", base_types);
+    assert!(
+        schema.array_types.values().any(|a| a.element_type == "mood"),
+        "The enum's array type should be introspected as an array type"
+    );
+
+    db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_base_type_edge_cases() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -473,11 +473,11 @@ async fn test_introspect_collation_schema_consistency() -> Result<(), Box<dyn st
 
     let coll1 = schema1
         .collations
-        .get("schema_consistency_collation")
+        .get("test_collation_schema_consistency.schema_consistency_collation")
         .unwrap();
     let coll2 = schema2
         .collations
-        .get("schema_consistency_collation")
+        .get("test_collation_schema_consistency.schema_consistency_collation")
         .unwrap();
 
     // Verify consistency across multiple introspections
@@ -87,7 +87,7 @@ async fn test_introspect_composite_type_with_schema() -> Result<(), Box<dyn std:
     let schema = connection.introspect().await?;
 
     // Verify the composite type was introspected with correct schema
-    let composite_type = schema.composite_types.get("person_type");
+    let composite_type = schema.composite_types.get("test_composite.person_type");
     assert!(
         composite_type.is_some(),
         "Composite type 'person_type' should be introspected"
@@ -239,6 +239,46 @@ async fn test_introspect_composite_type_with_detailed_comments() -> Result<(), B
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_composite_type_attribute_comments() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(connection, "CREATE TYPE money_amount AS (amount NUMERIC, currency TEXT);").await?;
+    execute_sql(connection, "COMMENT ON TYPE money_amount IS 'An amount in a currency';").await?;
+    execute_sql(
+        connection,
+        "COMMENT ON COLUMN money_amount.currency IS 'ISO 4217 code';",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let comp_type = schema.composite_types.get("money_amount").unwrap();
+    debug!("Composite type: {:?}", comp_type);
+
+    // Attribute comments come from col_description, not the type's own comment
+    let currency = comp_type.attributes.iter().find(|a| a.name == "currency").unwrap();
+    assert_eq!(currency.comment, Some("ISO 4217 code".to_string()));
+    let amount = comp_type.attributes.iter().find(|a| a.name == "amount").unwrap();
+    assert_eq!(amount.comment, None);
+    assert_eq!(comp_type.comment, Some("An amount in a currency".to_string()));
+
+    let sql = PostgresSqlGenerator.create_composite_type(comp_type)?;
+    assert!(
+        sql.contains("COMMENT ON COLUMN \"money_amount\".\"currency\" IS 'ISO 4217 code';"),
+        "Generated SQL should comment the attribute: {}",
+        sql
+    );
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_composite_type_with_schema_and_comment() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -272,7 +312,7 @@ async fn test_introspect_composite_type_with_schema_and_comment() -> Result<(),
     let schema = connection.introspect().await?;
 
     // Verify the composite type was introspected with schema and comment
-    let composite_type = schema.composite_types.get("address_type");
+    let composite_type = schema.composite_types.get("hr_schema.address_type");
     assert!(
         composite_type.is_some(),
         "Composite type 'address_type' should be introspected"
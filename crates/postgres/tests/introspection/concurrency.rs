@@ -0,0 +1,119 @@
+use postgres::TestDb;
+use postgres::introspection::QueryLimiter;
+use shem_core::{DatabaseConnection, IntrospectOptions};
+use std::time::Duration;
+
+/// Test helper function to execute SQL on the test database
+async fn execute_sql(
+    connection: &Box<dyn DatabaseConnection>,
+    sql: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    connection.execute(sql).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_limiter_caps_concurrency() {
+    let limiter = QueryLimiter::new(2);
+
+    let queries = (0..10).map(|_| {
+        limiter.run(async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        })
+    });
+    futures::future::join_all(queries).await;
+
+    assert_eq!(limiter.peak(), 2, "Concurrency should be capped at 2");
+}
+
+#[tokio::test]
+async fn test_query_limiter_allows_at_least_one_query() {
+    let limiter = QueryLimiter::new(0);
+
+    let output = limiter.run(async { 42 }).await;
+
+    assert_eq!(output, 42);
+    assert_eq!(limiter.peak(), 1);
+}
+
+#[tokio::test]
+async fn test_introspect_with_concurrency_option() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    for i in 0..5 {
+        execute_sql(
+            connection,
+            &format!("CREATE TABLE concurrency_table_{} (id integer PRIMARY KEY, name text);", i),
+        )
+        .await?;
+    }
+
+    // Serial and concurrent introspection must agree
+    let serial = connection
+        .introspect_with_options(&IntrospectOptions {
+            concurrency: Some(1),
+            ..Default::default()
+        })
+        .await?;
+    let concurrent = connection
+        .introspect_with_options(&IntrospectOptions {
+            concurrency: Some(4),
+            ..Default::default()
+        })
+        .await?;
+
+    assert_eq!(serial.tables.len(), 5);
+    assert_eq!(serial.tables, concurrent.tables);
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_passes_match_serial_introspection() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // One object for several passes that now run side by side
+    for sql in [
+        "CREATE TYPE pass_status AS ENUM ('draft', 'published');",
+        "CREATE DOMAIN pass_positive AS integer CHECK (VALUE > 0);",
+        "CREATE SEQUENCE pass_counter;",
+        "CREATE TABLE pass_authors (id integer PRIMARY KEY, name text NOT NULL);",
+        "CREATE TABLE pass_posts (id integer PRIMARY KEY, author_id integer REFERENCES pass_authors (id), status pass_status NOT NULL DEFAULT 'draft', votes pass_positive);",
+        "CREATE VIEW pass_published AS SELECT id FROM pass_posts WHERE status = 'published';",
+        "CREATE FUNCTION pass_touch() RETURNS trigger LANGUAGE plpgsql AS $$ BEGIN RETURN NEW; END $$;",
+        "CREATE TRIGGER pass_posts_touch BEFORE UPDATE ON pass_posts FOR EACH ROW EXECUTE FUNCTION pass_touch();",
+    ] {
+        execute_sql(connection, sql).await?;
+    }
+
+    let serial = connection
+        .introspect_with_options(&IntrospectOptions {
+            concurrency: Some(1),
+            ..Default::default()
+        })
+        .await?;
+    let concurrent = connection
+        .introspect_with_options(&IntrospectOptions {
+            concurrency: Some(4),
+            ..Default::default()
+        })
+        .await?;
+
+    assert!(serial.enums.contains_key("pass_status"));
+    assert!(serial.domains.contains_key("pass_positive"));
+    assert!(serial.sequences.contains_key("pass_counter"));
+    assert!(serial.views.contains_key("pass_published"));
+    assert!(serial.triggers.contains_key("pass_posts.pass_posts_touch"));
+    assert_eq!(serial.foreign_key_constraints.len(), 1);
+    assert_eq!(serial, concurrent);
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
@@ -71,7 +71,7 @@ async fn test_introspect_domain_with_schema() -> Result<(), Box<dyn std::error::
     let schema = connection.introspect().await?;
 
     // Verify the domain was introspected with correct schema
-    let domain = schema.domains.get("schema_domain");
+    let domain = schema.domains.get("test_domain_schema.schema_domain");
     assert!(
         domain.is_some(),
         "Domain 'schema_domain' should be introspected"
@@ -608,8 +608,8 @@ async fn test_introspect_domain_schema_consistency() -> Result<(), Box<dyn std::
     let schema1 = connection.introspect().await?;
     let schema2 = connection.introspect().await?;
 
-    let dom1 = schema1.domains.get("schema_consistency_domain").unwrap();
-    let dom2 = schema2.domains.get("schema_consistency_domain").unwrap();
+    let dom1 = schema1.domains.get("test_domain_schema_consistency.schema_consistency_domain").unwrap();
+    let dom2 = schema2.domains.get("test_domain_schema_consistency.schema_consistency_domain").unwrap();
 
     // Verify consistency across multiple introspections
     assert_eq!(dom1.name, dom2.name);
@@ -668,3 +668,102 @@ async fn test_introspect_domain_comment_consistency() -> Result<(), Box<dyn std:
     db.cleanup().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_introspect_domain_named_constraints_round_trip()
+-> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE DOMAIN test_named_constraints_domain AS integer \
+         CONSTRAINT positive CHECK (VALUE > 0) \
+         CONSTRAINT below_limit CHECK (VALUE < 1000);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let dom = schema
+        .domains
+        .get("test_named_constraints_domain")
+        .expect("Domain 'test_named_constraints_domain' should be introspected");
+
+    let names: Vec<Option<&str>> = dom.constraints.iter().map(|c| c.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("below_limit"), Some("positive")]);
+
+    // Regenerated DDL keeps each constraint separately named
+    let sql = PostgresSqlGenerator.create_domain(dom)?;
+    assert_eq!(
+        sql,
+        "CREATE DOMAIN \"test_named_constraints_domain\" AS integer \
+         CONSTRAINT \"below_limit\" CHECK ((VALUE < 1000)) \
+         CONSTRAINT \"positive\" CHECK ((VALUE > 0));"
+    );
+
+    // Recreate from the generated DDL and drop a single constraint by name
+    execute_sql(connection, "DROP DOMAIN test_named_constraints_domain;").await?;
+    execute_sql(connection, &sql).await?;
+    execute_sql(
+        connection,
+        "ALTER DOMAIN test_named_constraints_domain DROP CONSTRAINT below_limit;",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let dom = schema.domains.get("test_named_constraints_domain").unwrap();
+    assert_eq!(dom.constraints.len(), 1);
+    assert_eq!(dom.constraints[0].name.as_deref(), Some("positive"));
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_domain_default_not_null_round_trip()
+-> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE DOMAIN positive_int AS integer NOT NULL DEFAULT 1 CHECK (VALUE > 0);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let dom = schema
+        .domains
+        .get("positive_int")
+        .expect("Domain 'positive_int' should be introspected");
+    assert_eq!(dom.default.as_deref(), Some("1"));
+    assert!(dom.not_null);
+
+    // Regenerated DDL keeps the default and NOT NULL alongside the check
+    let sql = PostgresSqlGenerator.create_domain(dom)?;
+    assert_eq!(
+        sql,
+        "CREATE DOMAIN \"positive_int\" AS integer DEFAULT 1 NOT NULL \
+         CONSTRAINT \"positive_int_check\" CHECK ((VALUE > 0));"
+    );
+
+    execute_sql(connection, "DROP DOMAIN positive_int;").await?;
+    execute_sql(connection, &sql).await?;
+
+    let schema = connection.introspect().await?;
+    let dom = schema.domains.get("positive_int").unwrap();
+    assert_eq!(dom.default.as_deref(), Some("1"));
+    assert!(dom.not_null);
+    assert_eq!(dom.constraints.len(), 1);
+
+    db.cleanup().await?;
+    Ok(())
+}
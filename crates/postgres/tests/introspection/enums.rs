@@ -55,7 +55,7 @@ async fn test_introspect_enum_with_schema() -> Result<(), Box<dyn std::error::Er
     let schema = connection.introspect().await?;
 
     // Verify the enum was introspected with correct schema
-    let enum_type = schema.enums.get("priority_enum");
+    let enum_type = schema.enums.get("test_enum_schema.priority_enum");
     assert!(
         enum_type.is_some(),
         "Enum 'priority_enum' should be introspected"
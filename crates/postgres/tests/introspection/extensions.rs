@@ -285,6 +285,37 @@ async fn test_introspect_extension_dependencies() -> Result<(), Box<dyn std::err
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_extension_requiring_cascade() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // earthdistance requires cube, which CASCADE installs alongside it
+    execute_sql(connection, "CREATE EXTENSION earthdistance CASCADE;").await?;
+
+    let schema = connection.introspect().await?;
+    let earthdistance = schema.extensions.get("earthdistance").unwrap();
+    assert!(
+        earthdistance.cascade,
+        "earthdistance depends on cube and should be recreated with CASCADE"
+    );
+    let cube = schema.extensions.get("cube").unwrap();
+    assert!(!cube.cascade, "cube has no required extensions");
+
+    let sql = PostgresSqlGenerator.create_extension(earthdistance)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.trim_end_matches(';').ends_with(" CASCADE"));
+
+    // Clean up
+    execute_sql(connection, "DROP EXTENSION earthdistance, cube;").await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_no_extensions() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -433,3 +464,36 @@ async fn test_introspect_extension_performance() -> Result<(), Box<dyn std::erro
     db.cleanup().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_introspect_extension_objects_when_including_system_objects()
+-> Result<(), Box<dyn std::error::Error>> {
+    use shem_core::IntrospectOptions;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // lo installs a `lo` domain and pg_buffercache a `pg_buffercache` view in public
+    execute_sql(connection, "CREATE EXTENSION IF NOT EXISTS lo;").await?;
+    execute_sql(connection, "CREATE EXTENSION IF NOT EXISTS pg_buffercache;").await?;
+
+    // Extension-owned objects are skipped by default
+    let schema = connection.introspect().await?;
+    assert!(schema.extensions.contains_key("lo"));
+    assert!(!schema.domains.contains_key("lo"));
+    assert!(!schema.views.contains_key("pg_buffercache"));
+
+    let options = IntrospectOptions {
+        include_system_objects: true,
+        ..IntrospectOptions::default()
+    };
+    let schema = connection.introspect_with_options(&options).await?;
+    debug!("Domains: {:?}", schema.domains.keys());
+    assert!(schema.domains.contains_key("lo"));
+    assert!(schema.views.contains_key("pg_buffercache"));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
@@ -177,4 +177,44 @@ async fn test_introspect_foreign_key_named_constraint() -> Result<(), Box<dyn st
     assert_eq!(fk.references_columns, vec!["id"]);
     
     Ok(())
-} 
\ No newline at end of file
+} 
+#[tokio::test]
+async fn test_introspect_self_referencing_composite_foreign_key() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Referencing columns are listed in a different order than the table's key
+    connection.execute("CREATE TABLE tree_nodes (
+        tree_id INTEGER,
+        node_id INTEGER,
+        parent_node_id INTEGER,
+        parent_tree_id INTEGER,
+        PRIMARY KEY (tree_id, node_id),
+        CONSTRAINT parent_fk FOREIGN KEY (parent_tree_id, parent_node_id) REFERENCES tree_nodes(tree_id, node_id)
+    );").await?;
+
+    // Same constraint name on another table must not be confused with the one above
+    connection.execute("CREATE TABLE other_nodes (
+        id INTEGER PRIMARY KEY,
+        parent_id INTEGER,
+        CONSTRAINT parent_fk FOREIGN KEY (parent_id) REFERENCES other_nodes(id)
+    );").await?;
+
+    let schema = connection.introspect().await?;
+
+    let fk_constraints: Vec<_> = schema.foreign_key_constraints.values().collect();
+    debug!("Self-referencing foreign key constraints: {:?}", fk_constraints);
+
+    let fk = fk_constraints.iter().find(|fk| fk.table == "tree_nodes").expect("Should find foreign key on tree_nodes");
+    assert_eq!(fk.name, "parent_fk");
+    assert_eq!(fk.references_table, "tree_nodes");
+    assert_eq!(fk.columns, vec!["parent_tree_id", "parent_node_id"]);
+    assert_eq!(fk.references_columns, vec!["tree_id", "node_id"]);
+
+    let other = fk_constraints.iter().find(|fk| fk.table == "other_nodes").expect("Should find foreign key on other_nodes");
+    assert_eq!(other.columns, vec!["parent_id"]);
+    assert_eq!(other.references_columns, vec!["id"]);
+
+    Ok(())
+}
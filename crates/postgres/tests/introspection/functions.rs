@@ -424,4 +424,44 @@ async fn test_introspect_function_with_strict() -> Result<(), Box<dyn std::error
     assert_eq!(func.comment, Some("Strict function".to_string()));
     
     Ok(())
-} 
\ No newline at end of file
+} 
+#[tokio::test]
+async fn test_introspect_overloaded_functions() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    connection.execute("CREATE FUNCTION describe(value integer) RETURNS text LANGUAGE sql AS $$ SELECT 'number' $$;").await?;
+    connection.execute("CREATE FUNCTION describe(value text) RETURNS text LANGUAGE sql AS $$ SELECT 'text' $$;").await?;
+    connection.execute("CREATE FUNCTION describe(value text, suffix text, OUT described text) LANGUAGE sql AS $$ SELECT value || suffix $$;").await?;
+
+    let schema = connection.introspect().await?;
+
+    // Every overload survives, keyed by its input argument types
+    let mut keys: Vec<_> = schema
+        .functions
+        .keys()
+        .filter(|key| key.starts_with("describe("))
+        .cloned()
+        .collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        vec![
+            "describe(integer)".to_string(),
+            "describe(text)".to_string(),
+            "describe(text, text)".to_string(),
+        ]
+    );
+    assert_eq!(
+        schema.functions["describe(integer)"].parameters[0].type_name,
+        "integer"
+    );
+    assert_eq!(
+        schema.functions["describe(text)"].parameters[0].type_name,
+        "text"
+    );
+
+    db.cleanup().await?;
+    Ok(())
+}
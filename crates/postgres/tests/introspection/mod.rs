@@ -2,6 +2,7 @@ pub mod array_types;
 pub mod base_types;
 pub mod collations;
 pub mod composite_types;
+pub mod concurrency;
 pub mod domains;
 pub mod enums;
 pub mod event_triggers;
@@ -17,6 +18,7 @@ pub mod range_types;
 pub mod roles;
 pub mod rules;
 pub mod schemas;
+pub mod sessions;
 pub mod sequences;
 pub mod tables;
 pub mod tablespaces;
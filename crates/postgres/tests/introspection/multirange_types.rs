@@ -66,7 +66,7 @@ async fn test_introspect_multirange_type_with_schema() -> Result<(), Box<dyn std
     let schema = connection.introspect().await?;
 
     // Verify the multirange type was introspected with correct schema
-    let multirange_type = schema.multirange_types.get("schema_multirange");
+    let multirange_type = schema.multirange_types.get("test_multirange_schema.schema_multirange");
     assert!(
         multirange_type.is_some(),
         "Multirange type 'schema_multirange' should be introspected"
@@ -419,11 +419,11 @@ async fn test_introspect_multirange_type_schema_consistency()
 
     let mrt1 = schema1
         .multirange_types
-        .get("schema_consistency_multirange")
+        .get("test_multirange_schema_consistency.schema_consistency_multirange")
         .unwrap();
     let mrt2 = schema2
         .multirange_types
-        .get("schema_consistency_multirange")
+        .get("test_multirange_schema_consistency.schema_consistency_multirange")
         .unwrap();
 
     // Verify consistency across multiple introspections
@@ -457,7 +457,7 @@ async fn test_introspect_multirange_type_range_schema() -> Result<(), Box<dyn st
 
     debug!("Schema: {:?}", schema);
     // Verify the multirange type was introspected with correct range schema
-    let multirange_type = schema.multirange_types.get("multirange_type");
+    let multirange_type = schema.multirange_types.get("test_range_schema.multirange_type");
     assert!(
         multirange_type.is_some(),
         "Multirange type 'multirange_type' should be introspected"
@@ -29,7 +29,7 @@ async fn test_introspect_basic_policy() -> Result<(), Box<dyn std::error::Error>
     debug!("Introspected policies: {:?}", schema.policies);
 
     // Verify the policy exists
-    let policy = schema.policies.get("select_policy").expect("Policy should exist");
+    let policy = schema.policies.get("users.select_policy").expect("Policy should exist");
     assert_eq!(policy.name, "select_policy");
     assert_eq!(policy.table, "users");
     assert_eq!(policy.command, PolicyCommand::Select);
@@ -55,7 +55,7 @@ async fn test_introspect_policy_with_schema() -> Result<(), Box<dyn std::error::
     let schema = connection.introspect().await?;
 
     // Verify the policy exists in the schema
-    let policy = schema.policies.get("product_policy").expect("Policy should exist");
+    let policy = schema.policies.get("app_schema.products.product_policy").expect("Policy should exist");
     assert_eq!(policy.name, "product_policy");
     assert_eq!(policy.table, "products");
     assert_eq!(policy.schema, Some("app_schema".to_string()));
@@ -80,7 +80,7 @@ async fn test_introspect_policy_with_roles() -> Result<(), Box<dyn std::error::E
     let schema = connection.introspect().await?;
 
     // Verify the policy exists with roles
-    let policy = schema.policies.get("admin_policy").expect("Policy should exist");
+    let policy = schema.policies.get("documents.admin_policy").expect("Policy should exist");
     assert_eq!(policy.name, "admin_policy");
     assert_eq!(policy.table, "documents");
     assert_eq!(policy.command, PolicyCommand::All);
@@ -90,6 +90,47 @@ async fn test_introspect_policy_with_roles() -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_policies_sharing_roles() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Roles are cluster-wide, so remove leftovers from an earlier run first
+    execute_sql(connection, "DROP ROLE IF EXISTS policy_reader;").await.ok();
+    execute_sql(connection, "DROP ROLE IF EXISTS policy_writer;").await.ok();
+    execute_sql(connection, "CREATE ROLE policy_reader;").await?;
+    execute_sql(connection, "CREATE ROLE policy_writer;").await?;
+    execute_sql(connection, "CREATE TABLE notes (id SERIAL PRIMARY KEY, body TEXT);").await?;
+    execute_sql(connection, "ALTER TABLE notes ENABLE ROW LEVEL SECURITY;").await?;
+    execute_sql(
+        connection,
+        "CREATE POLICY notes_select ON notes FOR SELECT TO policy_reader, policy_writer USING (true);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE POLICY notes_update ON notes FOR UPDATE TO policy_reader, policy_writer USING (id > 0);",
+    )
+    .await?;
+    execute_sql(connection, "CREATE POLICY notes_public ON notes FOR INSERT WITH CHECK (true);").await?;
+
+    let schema = connection.introspect().await?;
+
+    let expected = vec!["policy_reader".to_string(), "policy_writer".to_string()];
+    assert_eq!(schema.policies.get("notes.notes_select").unwrap().roles, expected);
+    assert_eq!(schema.policies.get("notes.notes_update").unwrap().roles, expected);
+    // PUBLIC has no pg_roles entry and stays implicit
+    assert!(schema.policies.get("notes.notes_public").unwrap().roles.is_empty());
+
+    // Clean up
+    execute_sql(connection, "DROP TABLE notes;").await?;
+    execute_sql(connection, "DROP ROLE policy_reader;").await?;
+    execute_sql(connection, "DROP ROLE policy_writer;").await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_policy_with_check() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -105,7 +146,7 @@ async fn test_introspect_policy_with_check() -> Result<(), Box<dyn std::error::E
     let schema = connection.introspect().await?;
 
     // Verify the policy exists with check condition
-    let policy = schema.policies.get("insert_policy").expect("Policy should exist");
+    let policy = schema.policies.get("orders.insert_policy").expect("Policy should exist");
     assert_eq!(policy.name, "insert_policy");
     assert_eq!(policy.table, "orders");
     assert_eq!(policy.command, PolicyCommand::Insert);
@@ -132,7 +173,7 @@ async fn test_introspect_restrictive_policy() -> Result<(), Box<dyn std::error::
     let schema = connection.introspect().await?;
 
     // Verify the restrictive policy exists
-    let policy = schema.policies.get("restrictive_policy").expect("Policy should exist");
+    let policy = schema.policies.get("sensitive_data.restrictive_policy").expect("Policy should exist");
     assert_eq!(policy.name, "restrictive_policy");
     assert_eq!(policy.table, "sensitive_data");
     assert_eq!(policy.command, PolicyCommand::Select);
@@ -166,23 +207,23 @@ async fn test_introspect_multiple_policies() -> Result<(), Box<dyn std::error::E
     assert_eq!(schema.policies.len(), 4);
 
     // Check select policy
-    let select_policy = schema.policies.get("select_employees").expect("Select policy should exist");
+    let select_policy = schema.policies.get("employees.select_employees").expect("Select policy should exist");
     assert_eq!(select_policy.command, PolicyCommand::Select);
     assert_eq!(select_policy.using.as_deref(), Some("(department = 'IT'::text)"));
 
     // Check insert policy
-    let insert_policy = schema.policies.get("insert_employees").expect("Insert policy should exist");
+    let insert_policy = schema.policies.get("employees.insert_employees").expect("Insert policy should exist");
     assert_eq!(insert_policy.command, PolicyCommand::Insert);
     assert_eq!(insert_policy.check.as_deref(), Some("(name IS NOT NULL)"));
 
     // Check update policy
-    let update_policy = schema.policies.get("update_employees").expect("Update policy should exist");
+    let update_policy = schema.policies.get("employees.update_employees").expect("Update policy should exist");
     assert_eq!(update_policy.command, PolicyCommand::Update);
     assert_eq!(update_policy.using.as_deref(), Some("(id > 0)"));
     assert_eq!(update_policy.check.as_deref(), Some("(department IS NOT NULL)"));
 
     // Check delete policy
-    let delete_policy = schema.policies.get("delete_employees").expect("Delete policy should exist");
+    let delete_policy = schema.policies.get("employees.delete_employees").expect("Delete policy should exist");
     assert_eq!(delete_policy.command, PolicyCommand::Delete);
     assert_eq!(delete_policy.using.as_deref(), Some("(id > 100)"));
 
@@ -204,7 +245,7 @@ async fn test_introspect_policy_with_complex_conditions() -> Result<(), Box<dyn
     let schema = connection.introspect().await?;
 
     // Verify the policy with complex condition exists
-    let policy = schema.policies.get("user_logs").expect("Policy should exist");
+    let policy = schema.policies.get("logs.user_logs").expect("Policy should exist");
     assert_eq!(policy.name, "user_logs");
     assert_eq!(policy.table, "logs");
     assert_eq!(policy.command, PolicyCommand::Select);
@@ -230,8 +271,8 @@ async fn test_introspect_policy_consistency() -> Result<(), Box<dyn std::error::
     let schema1 = connection.introspect().await?;
     let schema2 = connection.introspect().await?;
 
-    let policy1 = schema1.policies.get("test_policy").unwrap();
-    let policy2 = schema2.policies.get("test_policy").unwrap();
+    let policy1 = schema1.policies.get("test_table.test_policy").unwrap();
+    let policy2 = schema2.policies.get("test_table.test_policy").unwrap();
 
     // Verify consistency
     assert_eq!(policy1.name, policy2.name);
@@ -244,4 +285,70 @@ async fn test_introspect_policy_consistency() -> Result<(), Box<dyn std::error::
     assert_eq!(policy1.roles, policy2.roles);
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[tokio::test]
+async fn test_introspect_policy_with_comment() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // COMMENT ON POLICY requires PostgreSQL 9.5 or newer
+    let version = connection
+        .query("SELECT current_setting('server_version_num') AS version")
+        .await?;
+    let version: i32 = version[0]["version"].as_str().unwrap_or("0").parse()?;
+    if version < 90500 {
+        debug!("Skipping policy comment test on server version {}", version);
+        db.cleanup().await?;
+        return Ok(());
+    }
+
+    execute_sql(connection, "CREATE TABLE notes (id SERIAL PRIMARY KEY, body TEXT);").await?;
+    execute_sql(connection, "ALTER TABLE notes ENABLE ROW LEVEL SECURITY;").await?;
+    execute_sql(
+        connection,
+        "CREATE POLICY notes_policy ON notes FOR SELECT USING (true);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "COMMENT ON POLICY notes_policy ON notes IS 'Everyone can read notes';",
+    )
+    .await?;
+
+    // Introspect the database
+    let schema = connection.introspect().await?;
+
+    let policy = schema.policies.get("notes.notes_policy").expect("Policy should exist");
+    assert_eq!(policy.comment.as_deref(), Some("Everyone can read notes"));
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_same_policy_name_on_two_tables() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Policy names are only unique per table
+    execute_sql(connection, "CREATE TABLE projects (id SERIAL PRIMARY KEY, tenant_id INTEGER);").await?;
+    execute_sql(connection, "CREATE TABLE invoices (id SERIAL PRIMARY KEY, tenant_id INTEGER);").await?;
+    execute_sql(connection, "CREATE POLICY tenant_isolation ON projects USING (tenant_id = 1);").await?;
+    execute_sql(connection, "CREATE POLICY tenant_isolation ON invoices USING (tenant_id = 2);").await?;
+
+    let schema = connection.introspect().await?;
+    debug!("Introspected policies: {:?}", schema.policies);
+
+    let projects = schema.policies.get("projects.tenant_isolation").expect("Policy on projects should exist");
+    assert_eq!(projects.table, "projects");
+    assert_eq!(projects.using.as_deref(), Some("(tenant_id = 1)"));
+    let invoices = schema.policies.get("invoices.tenant_isolation").expect("Policy on invoices should exist");
+    assert_eq!(invoices.table, "invoices");
+    assert_eq!(invoices.using.as_deref(), Some("(tenant_id = 2)"));
+
+    db.cleanup().await?;
+    Ok(())
+}
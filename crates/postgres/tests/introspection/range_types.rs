@@ -62,7 +62,7 @@ async fn test_introspect_range_type_with_schema() -> Result<(), Box<dyn std::err
     let schema = connection.introspect().await?;
 
     // Verify the range type was introspected with correct schema
-    let range_type = schema.range_types.get("numeric_range");
+    let range_type = schema.range_types.get("test_range.numeric_range");
     assert!(
         range_type.is_some(),
         "Range type 'numeric_range' should be introspected"
@@ -1,6 +1,8 @@
 use tracing::debug;
 use postgres::TestDb;
 use shem_core::DatabaseConnection;
+use postgres::PostgresSqlGenerator;
+use shem_core::traits::SqlGenerator;
 
 /// Test helper function to execute SQL on the test database
 async fn execute_sql(
@@ -203,6 +205,97 @@ async fn test_introspect_role_with_connection_limit() -> Result<(), Box<dyn std:
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_role_noinherit_bypassrls() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    let result = execute_sql(
+        connection,
+        "CREATE ROLE test_bypass_role LOGIN NOINHERIT BYPASSRLS;",
+    )
+    .await;
+
+    match result {
+        Ok(_) => {
+            let schema = connection.introspect().await?;
+
+            let role_obj = schema.roles.get("test_bypass_role").unwrap();
+            assert!(!role_obj.inherit, "Role should not inherit privileges");
+            assert!(role_obj.bypass_rls, "Role should bypass row level security");
+
+            let sql = PostgresSqlGenerator.create_role(role_obj)?;
+            assert_eq!(
+                sql,
+                "CREATE ROLE \"test_bypass_role\" NOINHERIT LOGIN BYPASSRLS;"
+            );
+
+            // Clean up
+            execute_sql(connection, "DROP ROLE test_bypass_role;").await?;
+        }
+        Err(e) => {
+            debug!("Role creation failed: {e:?}");
+            // Skip test if role creation is not allowed
+        }
+    }
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_role_with_search_path() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    let result = execute_sql(connection, "CREATE ROLE test_settings_role LOGIN;").await;
+
+    match result {
+        Ok(_) => {
+            execute_sql(
+                connection,
+                "ALTER ROLE test_settings_role SET search_path = app, \"$user\", public;",
+            )
+            .await?;
+
+            let schema = connection.introspect().await?;
+
+            let role_obj = schema.roles.get("test_settings_role").unwrap();
+            assert_eq!(
+                role_obj.settings,
+                vec![(
+                    "search_path".to_string(),
+                    "app, \"$user\", public".to_string()
+                )]
+            );
+
+            // Replaying the generated SQL restores the setting
+            let sql = PostgresSqlGenerator.create_role(role_obj)?;
+            assert!(sql.contains(
+                "ALTER ROLE \"test_settings_role\" SET search_path TO 'app', '$user', 'public';"
+            ));
+            execute_sql(connection, "DROP ROLE test_settings_role;").await?;
+            for statement in sql.lines() {
+                execute_sql(connection, statement).await?;
+            }
+            let schema = connection.introspect().await?;
+            assert_eq!(schema.roles.get("test_settings_role").unwrap(), role_obj);
+
+            // Clean up
+            execute_sql(connection, "DROP ROLE test_settings_role;").await?;
+        }
+        Err(e) => {
+            debug!("Role creation failed: {e:?}");
+            // Skip test if role creation is not allowed
+        }
+    }
+
+    db.cleanup().await?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_role_with_expiration() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -18,7 +18,7 @@ async fn test_introspect_basic_rule() -> Result<(), Box<dyn std::error::Error>>
     debug!("Introspected rules: {:?}", schema.rules);
 
     // Verify the rule exists in the schema
-    let rule = schema.rules.get("insert_redirect").expect("Rule should exist");
+    let rule = schema.rules.get("test_rules.insert_redirect").expect("Rule should exist");
     assert_eq!(rule.name, "insert_redirect");
     assert_eq!(rule.table, "test_rules");
     assert_eq!(rule.event, RuleEvent::Insert);
@@ -37,7 +37,7 @@ async fn test_introspect_rule_with_where_condition() -> Result<(), Box<dyn std::
     connection.execute("CREATE RULE update_only_even AS ON UPDATE TO test_rules_where WHERE (NEW.id % 2 = 0) DO INSTEAD NOTHING;").await?;
 
     let schema = connection.introspect().await?;
-    let rule = schema.rules.get("update_only_even").expect("Rule should exist");
+    let rule = schema.rules.get("test_rules_where.update_only_even").expect("Rule should exist");
     assert_eq!(rule.name, "update_only_even");
     assert_eq!(rule.table, "test_rules_where");
     assert_eq!(rule.event, RuleEvent::Update);
@@ -62,7 +62,7 @@ async fn test_introspect_rule_with_do_instead_select() -> Result<(), Box<dyn std
     connection.execute("CREATE RULE complex_condition_rule AS ON UPDATE TO test_rules_complex WHERE NEW.value != OLD.value AND NEW.status = 'active' DO INSTEAD NOTHING;").await?;
 
     let schema = connection.introspect().await?;
-    let rule = schema.rules.get("complex_condition_rule").expect("Rule should exist");
+    let rule = schema.rules.get("test_rules_complex.complex_condition_rule").expect("Rule should exist");
     assert_eq!(rule.name, "complex_condition_rule");
     assert_eq!(rule.table, "test_rules_complex");
     assert_eq!(rule.event, RuleEvent::Update);
@@ -90,7 +90,7 @@ async fn test_introspect_rule_for_delete_event() -> Result<(), Box<dyn std::erro
     connection.execute("CREATE RULE delete_redirect AS ON DELETE TO test_rules_delete DO INSTEAD NOTHING;").await?;
 
     let schema = connection.introspect().await?;
-    let rule = schema.rules.get("delete_redirect").expect("Rule should exist");
+    let rule = schema.rules.get("test_rules_delete.delete_redirect").expect("Rule should exist");
     assert_eq!(rule.name, "delete_redirect");
     assert_eq!(rule.table, "test_rules_delete");
     assert_eq!(rule.event, RuleEvent::Delete);
@@ -109,7 +109,7 @@ async fn test_introspect_rule_with_multiple_actions() -> Result<(), Box<dyn std:
     connection.execute("CREATE RULE multi_action_rule AS ON UPDATE TO test_rules_multi DO ALSO (UPDATE test_rules_multi SET value = 'updated' WHERE id = NEW.id; NOTIFY test_rules_multi;);").await?;
 
     let schema = connection.introspect().await?;
-    let rule = schema.rules.get("multi_action_rule").expect("Rule should exist");
+    let rule = schema.rules.get("test_rules_multi.multi_action_rule").expect("Rule should exist");
     assert_eq!(rule.name, "multi_action_rule");
     assert_eq!(rule.table, "test_rules_multi");
     assert_eq!(rule.event, RuleEvent::Update);
@@ -147,3 +147,39 @@ async fn test_introspect_multiple_schemas() -> Result<(), Box<dyn std::error::Er
     db.cleanup().await?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_introspect_excludes_configured_schemas() -> Result<(), Box<dyn std::error::Error>> {
+    use shem_core::IntrospectOptions;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(connection, "CREATE SCHEMA audit;").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE audit.audit_log (id integer PRIMARY KEY, entry text);",
+    )
+    .await?;
+    execute_sql(connection, "CREATE TABLE app_events (id integer PRIMARY KEY);").await?;
+
+    // By default every user schema is introspected
+    let schema = connection.introspect().await?;
+    assert!(schema.named_schemas.contains_key("audit"));
+    assert!(schema.tables.contains_key("audit.audit_log"));
+
+    let options = IntrospectOptions {
+        exclude_schemas: vec!["audit".to_string()],
+        ..IntrospectOptions::default()
+    };
+    let schema = connection.introspect_with_options(&options).await?;
+    debug!("Tables: {:?}", schema.tables.keys());
+    assert!(!schema.named_schemas.contains_key("audit"));
+    assert!(!schema.tables.contains_key("audit.audit_log"));
+    assert!(schema.tables.contains_key("app_events"));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
@@ -70,7 +70,7 @@ async fn test_introspect_sequence_with_schema() -> Result<(), Box<dyn std::error
     let schema = connection.introspect().await?;
 
     // Verify the sequence was introspected with correct schema
-    let sequence = schema.sequences.get("schema_sequence");
+    let sequence = schema.sequences.get("test_sequence_schema.schema_sequence");
     assert!(
         sequence.is_some(),
         "Sequence 'schema_sequence' should be introspected"
@@ -470,7 +470,7 @@ async fn test_introspect_sequence_all_features() -> Result<(), Box<dyn std::erro
     let seq = sequence.unwrap();
     debug!("Sequence: {:?}", seq);
     assert_eq!(seq.name, "test_all_features_sequence");
-    assert_eq!(seq.data_type, "smallint");
+    assert_eq!(seq.data_type, "integer");
     assert_eq!(seq.start, 1000);
     assert_eq!(seq.increment, 10);
     assert_eq!(seq.min_value, Some(500));
@@ -506,8 +506,8 @@ async fn test_introspect_sequence_schema_consistency() -> Result<(), Box<dyn std
     let schema1 = connection.introspect().await?;
     let schema2 = connection.introspect().await?;
 
-    let seq1 = schema1.sequences.get("schema_consistency_sequence").unwrap();
-    let seq2 = schema2.sequences.get("schema_consistency_sequence").unwrap();
+    let seq1 = schema1.sequences.get("test_sequence_schema_consistency.schema_consistency_sequence").unwrap();
+    let seq2 = schema2.sequences.get("test_sequence_schema_consistency.schema_consistency_sequence").unwrap();
 
     // Verify consistency across multiple introspections
     assert_eq!(seq1.name, seq2.name);
@@ -601,4 +601,34 @@ async fn test_introspect_sequence_owned_by_schema() -> Result<(), Box<dyn std::e
     // Clean up
     db.cleanup().await?;
     Ok(())
-} 
\ No newline at end of file
+} 
+#[tokio::test]
+async fn test_unused_sequences_are_reported() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // A serial column owns its sequence, a default can use a free-standing one
+    execute_sql(connection, "CREATE TABLE orders (id serial PRIMARY KEY);").await?;
+    execute_sql(connection, "CREATE SEQUENCE invoice_numbers;").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE invoices (number bigint DEFAULT nextval('invoice_numbers'));",
+    )
+    .await?;
+    execute_sql(connection, "CREATE SEQUENCE leftover_seq;").await?;
+
+    let schema = connection.introspect().await?;
+    let unused: Vec<&str> = schema
+        .unused_sequences()
+        .iter()
+        .map(|seq| seq.name.as_str())
+        .collect();
+    debug!("Unused sequences: {:?}", unused);
+
+    assert_eq!(unused, vec!["leftover_seq"]);
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
@@ -82,7 +82,7 @@ async fn test_introspect_table_with_schema() -> Result<(), Box<dyn std::error::E
     let schema = connection.introspect().await?;
 
     // Verify the table was introspected with correct schema
-    let table = schema.tables.get("schema_table");
+    let table = schema.tables.get("test_table_schema.schema_table");
     assert!(
         table.is_some(),
         "Table 'schema_table' should be introspected"
@@ -680,8 +680,8 @@ async fn test_introspect_table_schema_consistency() -> Result<(), Box<dyn std::e
     let schema1 = connection.introspect().await?;
     let schema2 = connection.introspect().await?;
 
-    let tbl1 = schema1.tables.get("schema_consistency_table").unwrap();
-    let tbl2 = schema2.tables.get("schema_consistency_table").unwrap();
+    let tbl1 = schema1.tables.get("test_table_schema_consistency.schema_consistency_table").unwrap();
+    let tbl2 = schema2.tables.get("test_table_schema_consistency.schema_consistency_table").unwrap();
 
     // Verify consistency across multiple introspections
     assert_eq!(tbl1.name, tbl2.name);
@@ -941,9 +941,51 @@ async fn test_introspect_unlogged_table() -> Result<(), Box<dyn std::error::Erro
     let unlogged = table.unwrap();
     assert_eq!(unlogged.name, "unlogged_table");
     assert_eq!(unlogged.columns.len(), 2, "Table should have 2 columns");
+    assert!(unlogged.unlogged, "Table should be marked as unlogged");
 
-    // Note: The unlogged property might be stored in storage_parameters or as a separate field
-    // This depends on how the introspection is implemented
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_unlogged_table_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE UNLOGGED TABLE unlogged_cache (key text PRIMARY KEY, value text);",
+    )
+    .await?;
+    execute_sql(connection, "CREATE TABLE logged_table (id integer PRIMARY KEY);").await?;
+    execute_sql(connection, "CREATE TEMPORARY TABLE session_scratch (id integer);").await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("unlogged_cache").unwrap();
+    assert!(tbl.unlogged);
+    assert!(!schema.tables.get("logged_table").unwrap().unlogged);
+    assert!(
+        !schema.tables.contains_key("session_scratch"),
+        "Temporary tables should not be introspected"
+    );
+
+    // Regenerate the table and make sure it is still unlogged
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.starts_with("CREATE UNLOGGED TABLE \"unlogged_cache\""));
+
+    execute_sql(connection, "DROP TABLE unlogged_cache;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    assert!(schema.tables.get("unlogged_cache").unwrap().unlogged);
 
     // Clean up
     db.cleanup().await?;
@@ -1350,4 +1392,1132 @@ async fn test_introspect_table_with_complex_column_types() -> Result<(), Box<dyn
     // Clean up
     db.cleanup().await?;
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[tokio::test]
+async fn test_introspect_table_grant_with_grant_option() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Roles are cluster-wide, so make sure a previous run didn't leave one behind
+    execute_sql(connection, "DROP ROLE IF EXISTS test_grant_option_role;").await?;
+    execute_sql(connection, "CREATE ROLE test_grant_option_role;").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE test_grant_option_table (id integer PRIMARY KEY, name text);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "GRANT SELECT ON test_grant_option_table TO test_grant_option_role WITH GRANT OPTION;",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "GRANT INSERT ON test_grant_option_table TO test_grant_option_role;",
+    )
+    .await?;
+
+    // Introspect the database
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("test_grant_option_table").unwrap();
+    debug!("Grants: {:?}", tbl.grants);
+
+    let grantable = tbl
+        .grants
+        .iter()
+        .find(|g| g.grantee == "test_grant_option_role" && g.with_grant_option)
+        .expect("SELECT should be granted WITH GRANT OPTION");
+    assert_eq!(grantable.privileges, vec!["SELECT".to_string()]);
+
+    let plain = tbl
+        .grants
+        .iter()
+        .find(|g| g.grantee == "test_grant_option_role" && !g.with_grant_option)
+        .expect("INSERT should be granted without GRANT OPTION");
+    assert_eq!(plain.privileges, vec!["INSERT".to_string()]);
+
+    // Regenerate the table and make sure the grant option survives a round trip
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    assert!(sql.contains(
+        "GRANT SELECT ON TABLE \"test_grant_option_table\" TO \"test_grant_option_role\" WITH GRANT OPTION;"
+    ));
+
+    execute_sql(connection, "DROP TABLE test_grant_option_table;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let round_tripped = schema.tables.get("test_grant_option_table").unwrap();
+    assert_eq!(round_tripped.grants, tbl.grants);
+
+    // Clean up
+    execute_sql(connection, "DROP TABLE test_grant_option_table;").await?;
+    execute_sql(connection, "DROP ROLE test_grant_option_role;").await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_table_grant_to_public() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE test_public_grant_table (id integer PRIMARY KEY, name text);",
+    )
+    .await?;
+    execute_sql(connection, "GRANT SELECT ON test_public_grant_table TO PUBLIC;").await?;
+
+    // Introspect the database
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("test_public_grant_table").unwrap();
+    debug!("Grants: {:?}", tbl.grants);
+
+    let public = tbl
+        .grants
+        .iter()
+        .find(|g| g.grantee == "PUBLIC")
+        .expect("SELECT should be granted to PUBLIC");
+    assert_eq!(public.privileges, vec!["SELECT".to_string()]);
+    assert!(!public.with_grant_option);
+
+    // PUBLIC is a keyword, not a role, so it must not be quoted
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    assert!(sql.contains("GRANT SELECT ON TABLE \"test_public_grant_table\" TO PUBLIC;"));
+
+    execute_sql(connection, "DROP TABLE test_public_grant_table;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let round_tripped = schema.tables.get("test_public_grant_table").unwrap();
+    assert_eq!(round_tripped.grants, tbl.grants);
+
+    // Clean up
+    execute_sql(connection, "DROP TABLE test_public_grant_table;").await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_column_grant() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Roles are cluster-wide, so make sure a previous run didn't leave one behind
+    execute_sql(connection, "DROP ROLE IF EXISTS test_column_grant_role;").await?;
+    execute_sql(connection, "CREATE ROLE test_column_grant_role;").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE test_column_grant_table (id integer PRIMARY KEY, name text, secret text);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "GRANT UPDATE (name) ON test_column_grant_table TO test_column_grant_role;",
+    )
+    .await?;
+
+    // Introspect the database
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("test_column_grant_table").unwrap();
+    debug!("Column grants: {:?}", tbl.columns);
+
+    // Column privileges must not leak into the table-level grants
+    assert!(tbl.grants.is_empty());
+    let name = tbl.columns.iter().find(|c| c.name == "name").unwrap();
+    assert_eq!(name.grants.len(), 1);
+    assert_eq!(name.grants[0].grantee, "test_column_grant_role");
+    assert_eq!(name.grants[0].privileges, vec!["UPDATE".to_string()]);
+    let secret = tbl.columns.iter().find(|c| c.name == "secret").unwrap();
+    assert!(secret.grants.is_empty());
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    assert!(sql.contains(
+        "GRANT UPDATE (\"name\") ON TABLE \"test_column_grant_table\" TO \"test_column_grant_role\";"
+    ));
+
+    execute_sql(connection, "DROP TABLE test_column_grant_table;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let round_tripped = schema.tables.get("test_column_grant_table").unwrap();
+    assert_eq!(round_tripped.columns, tbl.columns);
+
+    // Clean up
+    execute_sql(connection, "DROP TABLE test_column_grant_table;").await?;
+    execute_sql(connection, "DROP ROLE test_column_grant_role;").await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_partition_method_ignores_column_names() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Key columns named after other partition methods
+    execute_sql(
+        connection,
+        "CREATE TABLE price_bands (id integer, price_range text) PARTITION BY LIST (price_range);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE hashed_lists (id integer, list_hash integer) PARTITION BY RANGE (list_hash);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let bands = schema.tables.get("price_bands").unwrap().partition_by.as_ref().unwrap();
+    assert_eq!(bands.method, shem_core::PartitionMethod::List);
+    assert_eq!(bands.columns, vec!["price_range".to_string()]);
+    let hashed = schema.tables.get("hashed_lists").unwrap().partition_by.as_ref().unwrap();
+    assert_eq!(hashed.method, shem_core::PartitionMethod::Range);
+    assert_eq!(hashed.columns, vec!["list_hash".to_string()]);
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_default_partition() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE measurements (id integer, region text) PARTITION BY LIST (region);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE measurements_eu PARTITION OF measurements FOR VALUES IN ('eu');",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE measurements_other PARTITION OF measurements DEFAULT;",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let parent = schema.tables.get("measurements").unwrap();
+    let listed = schema.tables.get("measurements_eu").unwrap();
+    let default = schema.tables.get("measurements_other").unwrap();
+    debug!("Partition bounds: {:?} {:?}", listed.partition_bound, default.partition_bound);
+
+    assert_eq!(parent.partition_bound, None);
+    assert_eq!(listed.partition_bound.as_deref(), Some("FOR VALUES IN ('eu')"));
+    assert_eq!(default.partition_bound.as_deref(), Some("DEFAULT"));
+    assert_eq!(default.inherits, vec!["measurements".to_string()]);
+
+    // Regenerate the whole partition tree and make sure the bounds survive a round trip
+    let generator = PostgresSqlGenerator;
+    let statements = vec![
+        generator.generate_create_table(parent)?,
+        generator.generate_create_table(listed)?,
+        generator.generate_create_table(default)?,
+    ];
+    assert_eq!(
+        statements[2],
+        "CREATE TABLE \"measurements_other\" PARTITION OF \"measurements\" DEFAULT;"
+    );
+
+    execute_sql(connection, "DROP TABLE measurements;").await?;
+    for statement in &statements {
+        execute_sql(connection, statement.trim_end_matches(';')).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let round_tripped = schema.tables.get("measurements_other").unwrap();
+    assert_eq!(round_tripped.partition_bound.as_deref(), Some("DEFAULT"));
+    assert_eq!(
+        schema.tables.get("measurements").unwrap().partition_by,
+        parent.partition_by
+    );
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_column_storage() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::ColumnStorage;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE documents (id integer PRIMARY KEY, body text, title text);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "ALTER TABLE documents ALTER COLUMN body SET STORAGE EXTERNAL;",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("documents").unwrap();
+    let column = |name: &str| tbl.columns.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(column("body").storage, Some(ColumnStorage::External));
+    // Columns using their type's default storage are left unset
+    assert_eq!(column("title").storage, None);
+    assert_eq!(column("id").storage, None);
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("ALTER TABLE \"documents\" ALTER COLUMN \"body\" SET STORAGE EXTERNAL;"));
+    assert!(!sql.contains("\"title\" SET STORAGE"));
+
+    execute_sql(connection, "DROP TABLE documents;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("documents").unwrap();
+    let body = tbl.columns.iter().find(|c| c.name == "body").unwrap();
+    assert_eq!(body.storage, Some(ColumnStorage::External));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_column_compression() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE archived_pages (id integer PRIMARY KEY, body text COMPRESSION lz4, raw text COMPRESSION pglz, title text);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("archived_pages").unwrap();
+    let column = |name: &str| tbl.columns.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(column("body").compression.as_deref(), Some("lz4"));
+    assert_eq!(column("raw").compression.as_deref(), Some("pglz"));
+    assert_eq!(column("title").compression, None);
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("\"body\" text COMPRESSION lz4"));
+    assert!(!sql.contains("\"title\" text COMPRESSION"));
+
+    execute_sql(connection, "DROP TABLE archived_pages;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("archived_pages").unwrap();
+    let body = tbl.columns.iter().find(|c| c.name == "body").unwrap();
+    assert_eq!(body.compression.as_deref(), Some("lz4"));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_index_operator_class() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(connection, "CREATE EXTENSION IF NOT EXISTS pg_trgm;").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE articles (id integer PRIMARY KEY, body text);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE INDEX articles_body_trgm_idx ON articles USING gin (body gin_trgm_ops);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("articles").unwrap();
+    let index = |name: &str| tbl.indexes.iter().find(|i| i.name == name).unwrap();
+    let trgm = index("articles_body_trgm_idx");
+    assert_eq!(trgm.columns[0].opclass.as_deref(), Some("gin_trgm_ops"));
+    // Default operator classes are implied and not recorded
+    assert_eq!(index("articles_pkey").columns[0].opclass, None);
+
+    let sql = PostgresSqlGenerator.create_index(trgm)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("USING gin (\"body\" gin_trgm_ops)"));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_inherited_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE vehicles (id integer NOT NULL, name text DEFAULT 'unnamed');",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE trucks (payload integer) INHERITS (vehicles);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let trucks = schema.tables.get("trucks").unwrap();
+    assert_eq!(trucks.inherits, vec!["vehicles".to_string()]);
+    let inherited = |name: &str| {
+        trucks
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .inherited
+    };
+    assert!(inherited("id"));
+    assert!(inherited("name"));
+    assert!(!inherited("payload"));
+    let vehicles = schema.tables.get("vehicles").unwrap();
+    assert!(vehicles.columns.iter().all(|c| !c.inherited));
+
+    // Only the local column is declared, the rest comes from INHERITS
+    let sql = PostgresSqlGenerator.generate_create_table(trucks)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.starts_with(
+        "CREATE TABLE \"trucks\" (\n    \"payload\" integer\n) INHERITS (\"vehicles\");"
+    ));
+    assert!(!sql.contains("\"id\" integer"));
+
+    // Recreating the child from the generated SQL yields the same columns
+    execute_sql(connection, "DROP TABLE trucks;").await?;
+    for statement in sql.split_inclusive(";\n") {
+        execute_sql(connection, statement).await?;
+    }
+    let schema = connection.introspect().await?;
+    assert_eq!(schema.tables.get("trucks").unwrap().columns, trucks.columns);
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_numeric_precision_and_scale() -> Result<(), Box<dyn std::error::Error>> {
+    use shem_core::normalize::types_equivalent;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE prices (amount NUMERIC, whole NUMERIC(10), exact DECIMAL(10, 2));",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("prices").unwrap();
+    let type_name = |name: &str| {
+        tbl.columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .type_name
+            .clone()
+    };
+    assert_eq!(type_name("amount"), "numeric");
+    assert_eq!(type_name("whole"), "numeric(10,0)");
+    assert_eq!(type_name("exact"), "numeric(10,2)");
+
+    // The declared spellings compare equal to what format_type reports
+    assert!(types_equivalent(&type_name("amount"), "NUMERIC"));
+    assert!(types_equivalent(&type_name("whole"), "NUMERIC(10)"));
+    assert!(types_equivalent(&type_name("exact"), "DECIMAL(10, 2)"));
+    assert!(!types_equivalent(&type_name("amount"), "NUMERIC(10, 2)"));
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_large_object_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE attachments (id integer PRIMARY KEY, content oid NOT NULL, thumbnail bytea, checksum bytea DEFAULT '\\x00'::bytea);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "INSERT INTO attachments (id, content, thumbnail) VALUES (1, lo_from_bytea(0, '\\xdeadbeef'::bytea), '\\xcafe'::bytea);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("attachments").unwrap();
+    let column = |name: &str| tbl.columns.iter().find(|c| c.name == name).unwrap();
+    assert_eq!(column("content").type_name, "oid");
+    assert!(!column("content").nullable);
+    assert_eq!(column("thumbnail").type_name, "bytea");
+    assert_eq!(column("checksum").default.as_deref(), Some("'\\x00'::bytea"));
+
+    let rows = connection
+        .query("SELECT content, thumbnail FROM attachments")
+        .await?;
+    assert!(rows[0]["content"].is_u64());
+    assert_eq!(rows[0]["thumbnail"], "yv4=");
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("\"content\" oid NOT NULL"));
+
+    execute_sql(connection, "SELECT lo_unlink(content) FROM attachments;").await?;
+    execute_sql(connection, "DROP TABLE attachments;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("attachments").unwrap();
+    let content = tbl.columns.iter().find(|c| c.name == "content").unwrap();
+    assert_eq!(content.type_name, "oid");
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_force_row_level_security() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE tenant_notes (id integer PRIMARY KEY, tenant_id integer NOT NULL);",
+    )
+    .await?;
+    execute_sql(connection, "CREATE TABLE tenant_files (id integer PRIMARY KEY);").await?;
+    execute_sql(connection, "ALTER TABLE tenant_notes ENABLE ROW LEVEL SECURITY;").await?;
+    execute_sql(connection, "ALTER TABLE tenant_notes FORCE ROW LEVEL SECURITY;").await?;
+    execute_sql(connection, "ALTER TABLE tenant_files ENABLE ROW LEVEL SECURITY;").await?;
+
+    let schema = connection.introspect().await?;
+    let notes = schema.tables.get("tenant_notes").unwrap();
+    assert!(notes.row_security);
+    assert!(notes.force_row_security);
+    let files = schema.tables.get("tenant_files").unwrap();
+    assert!(files.row_security);
+    assert!(!files.force_row_security);
+
+    let sql = PostgresSqlGenerator.generate_create_table(notes)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("ALTER TABLE \"tenant_notes\" ENABLE ROW LEVEL SECURITY;"));
+    assert!(sql.contains("ALTER TABLE \"tenant_notes\" FORCE ROW LEVEL SECURITY;"));
+
+    // Forcing an already enabled table only toggles FORCE
+    let mut forced = files.clone();
+    forced.force_row_security = true;
+    let (up, down) = PostgresSqlGenerator.generate_alter_table(files, &forced)?;
+    assert_eq!(up, vec!["ALTER TABLE \"tenant_files\" FORCE ROW LEVEL SECURITY"]);
+    assert_eq!(down, vec!["ALTER TABLE \"tenant_files\" NO FORCE ROW LEVEL SECURITY"]);
+
+    execute_sql(connection, "DROP TABLE tenant_notes;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+
+    let schema = connection.introspect().await?;
+    let notes = schema.tables.get("tenant_notes").unwrap();
+    assert!(notes.row_security);
+    assert!(notes.force_row_security);
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_prefer_serial() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::IntrospectOptions;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+    let options = IntrospectOptions {
+        prefer_serial: true,
+        ..IntrospectOptions::default()
+    };
+
+    execute_sql(
+        connection,
+        "CREATE TABLE events (id bigserial PRIMARY KEY, seq_no integer NOT NULL DEFAULT 0);",
+    )
+    .await?;
+
+    // Without the option the pattern stays spelled out
+    let schema = connection.introspect().await?;
+    let id = &schema.tables.get("events").unwrap().columns[0];
+    assert_eq!(id.type_name, "bigint");
+    assert_eq!(
+        id.default.as_deref(),
+        Some("nextval('events_id_seq'::regclass)")
+    );
+    assert!(schema.sequences.contains_key("events_id_seq"));
+
+    let schema = connection.introspect_with_options(&options).await?;
+    let tbl = schema.tables.get("events").unwrap();
+    assert_eq!(tbl.columns[0].type_name, "bigserial");
+    assert_eq!(tbl.columns[0].default, None);
+    assert_eq!(tbl.columns[1].type_name, "integer");
+    assert!(!schema.sequences.contains_key("events_id_seq"));
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains("\"id\" bigserial NOT NULL"));
+
+    // Replaying the bigserial declaration recreates the same column and sequence
+    execute_sql(connection, "DROP TABLE events;").await?;
+    for statement in sql.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+        execute_sql(connection, statement).await?;
+    }
+    let schema = connection.introspect_with_options(&options).await?;
+    let tbl = schema.tables.get("events").unwrap();
+    assert_eq!(tbl.columns[0].type_name, "bigserial");
+    assert!(schema.sequences.is_empty());
+
+    // Clean up
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_objects_in_tablespace() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Note: This may fail if the user doesn't have CREATE TABLESPACE privileges
+    let result = execute_sql(
+        connection,
+        "CREATE TABLESPACE objects_tablespace LOCATION '/tmp/objects_tablespace';",
+    )
+    .await;
+
+    match result {
+        Ok(_) => {
+            execute_sql(
+                connection,
+                "CREATE TABLE stored (id integer, name text) TABLESPACE objects_tablespace;",
+            )
+            .await?;
+            execute_sql(
+                connection,
+                "CREATE INDEX stored_name_idx ON stored (name) TABLESPACE objects_tablespace;",
+            )
+            .await?;
+            execute_sql(connection, "CREATE TABLE plain (id integer);").await?;
+            execute_sql(
+                connection,
+                "CREATE MATERIALIZED VIEW stored_names TABLESPACE objects_tablespace AS SELECT name FROM stored;",
+            )
+            .await?;
+
+            let schema = connection.introspect().await?;
+
+            let stored = schema.tables.get("stored").unwrap();
+            assert_eq!(stored.tablespace.as_deref(), Some("objects_tablespace"));
+            assert_eq!(
+                stored.indexes[0].tablespace.as_deref(),
+                Some("objects_tablespace")
+            );
+            assert_eq!(schema.tables.get("plain").unwrap().tablespace, None);
+            assert_eq!(
+                schema.materialized_views.get("stored_names").unwrap().tablespace.as_deref(),
+                Some("objects_tablespace")
+            );
+
+            // Clean up
+            execute_sql(connection, "DROP MATERIALIZED VIEW stored_names;").await?;
+            execute_sql(connection, "DROP TABLE stored;").await?;
+            execute_sql(connection, "DROP TABLESPACE objects_tablespace;").await?;
+        }
+        Err(e) => {
+            debug!("Tablespace creation failed (likely due to permissions): {e:?}");
+        }
+    }
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_same_table_name_in_two_schemas() -> Result<(), Box<dyn std::error::Error>>
+{
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(connection, "CREATE SCHEMA archive;").await?;
+    execute_sql(connection, "CREATE TABLE orders (id integer, total numeric);").await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE archive.orders (id integer, archived_at timestamp);",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+
+    // Objects outside public are keyed by schema-qualified name, so neither replaces the other
+    let current = schema.tables.get("orders").unwrap();
+    assert_eq!(current.schema.as_deref(), Some("public"));
+    assert!(current.columns.iter().any(|c| c.name == "total"));
+
+    let archived = schema.tables.get("archive.orders").unwrap();
+    assert_eq!(archived.schema.as_deref(), Some("archive"));
+    assert!(archived.columns.iter().any(|c| c.name == "archived_at"));
+
+    // Clean up
+    execute_sql(connection, "DROP SCHEMA archive CASCADE;").await?;
+    execute_sql(connection, "DROP TABLE orders;").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_deferrable_primary_key() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE ledger (
+            id integer,
+            code text,
+            CONSTRAINT ledger_pkey PRIMARY KEY (id) DEFERRABLE INITIALLY DEFERRED,
+            CONSTRAINT ledger_code_key UNIQUE (code) DEFERRABLE
+        );",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let table = schema.tables.get("ledger").unwrap();
+
+    let pkey = table.constraints.iter().find(|c| c.name == "ledger_pkey").unwrap();
+    assert!(pkey.deferrable);
+    assert!(pkey.initially_deferred);
+    assert_eq!(pkey.definition, "PRIMARY KEY (id) DEFERRABLE INITIALLY DEFERRED");
+
+    let unique = table.constraints.iter().find(|c| c.name == "ledger_code_key").unwrap();
+    assert!(unique.deferrable);
+    assert!(!unique.initially_deferred);
+    assert_eq!(unique.definition, "UNIQUE (code) DEFERRABLE");
+
+    // Clean up
+    execute_sql(connection, "DROP TABLE ledger;").await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_table_and_index_fillfactor_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        "CREATE TABLE counters (id integer NOT NULL, hits integer) WITH (fillfactor = 70);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE INDEX counters_hits_idx ON counters (hits) WITH (fillfactor = 90) WHERE hits > 0;",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("counters").unwrap();
+    assert_eq!(tbl.storage_parameters.get("fillfactor").map(String::as_str), Some("70"));
+    let index = tbl.indexes.iter().find(|i| i.name == "counters_hits_idx").unwrap();
+    assert_eq!(index.storage_parameters.get("fillfactor").map(String::as_str), Some("90"));
+
+    let create_table = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", create_table);
+    assert!(create_table.contains(") WITH (fillfactor = 70);"));
+    // create_index has no table to name, so the placeholder is filled in here
+    let create_index = PostgresSqlGenerator
+        .create_index(index)?
+        .replace(" ON table_name ", " ON counters ");
+    debug!("Generated SQL: {}", create_index);
+    assert!(create_index.contains("(\"hits\") WITH (fillfactor = 90) WHERE (hits > 0)"));
+
+    // Replaying the statements keeps both fillfactors
+    let replay = TestDb::new().await?;
+    execute_sql(&replay.conn, &create_table).await?;
+    execute_sql(&replay.conn, &create_index).await?;
+    let replayed = replay.conn.introspect().await?;
+    let replayed_tbl = replayed.tables.get("counters").unwrap();
+    assert_eq!(replayed_tbl.storage_parameters, tbl.storage_parameters);
+    let replayed_index = replayed_tbl
+        .indexes
+        .iter()
+        .find(|i| i.name == "counters_hits_idx")
+        .unwrap();
+    assert_eq!(replayed_index.storage_parameters, index.storage_parameters);
+
+    // Clean up
+    replay.cleanup().await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_column_collation_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    execute_sql(
+        connection,
+        r#"CREATE TABLE labels (code text COLLATE "C" NOT NULL, name text);"#,
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("labels").unwrap();
+    let collation = |name: &str| {
+        tbl.columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap()
+            .collation
+            .clone()
+    };
+    assert_eq!(collation("code").as_deref(), Some("C"));
+    // The type's default collation is implied and not recorded
+    assert_eq!(collation("name"), None);
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains(r#""code" text COLLATE "C" NOT NULL"#));
+
+    // Replaying the table keeps the collation
+    let replay = TestDb::new().await?;
+    execute_sql(&replay.conn, &sql).await?;
+    let replayed = replay.conn.introspect().await?;
+    assert_eq!(replayed.tables.get("labels").unwrap().columns, tbl.columns);
+
+    // Clean up
+    replay.cleanup().await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_table_without_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Dropping every user column leaves only the system columns behind
+    execute_sql(connection, "CREATE TABLE hollow (a integer, b text);").await?;
+    execute_sql(connection, "ALTER TABLE hollow DROP COLUMN a, DROP COLUMN b;").await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("hollow").unwrap();
+    assert!(tbl.columns.is_empty());
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.starts_with(r#"CREATE TABLE "hollow" ();"#));
+
+    // The generated statement is valid and recreates the empty table
+    let replay = TestDb::new().await?;
+    execute_sql(&replay.conn, &sql).await?;
+    let replayed = replay.conn.introspect().await?;
+    assert!(replayed.tables.get("hollow").unwrap().columns.is_empty());
+
+    // Clean up
+    replay.cleanup().await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_typed_table_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    let type_sql = "CREATE TYPE person_type AS (id integer, name text, nickname text);";
+    execute_sql(connection, type_sql).await?;
+    execute_sql(
+        connection,
+        "CREATE TABLE people OF person_type (id WITH OPTIONS PRIMARY KEY, name WITH OPTIONS NOT NULL DEFAULT 'anonymous');",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("people").unwrap();
+    assert_eq!(tbl.of_type.as_deref(), Some("person_type"));
+    // Columns still mirror the type so they can be compared like any other table
+    let names: Vec<&str> = tbl.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "name", "nickname"]);
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.starts_with(r#"CREATE TABLE "people" OF person_type ("#));
+    assert!(sql.contains(r#""name" WITH OPTIONS NOT NULL DEFAULT 'anonymous'::text"#));
+    assert!(!sql.contains(r#""nickname""#));
+
+    // Replaying after the type recreates the same typed table
+    let replay = TestDb::new().await?;
+    execute_sql(&replay.conn, type_sql).await?;
+    execute_sql(&replay.conn, &sql).await?;
+    let replayed = replay.conn.introspect().await?;
+    let replayed_tbl = replayed.tables.get("people").unwrap();
+    assert_eq!(replayed_tbl.of_type, tbl.of_type);
+    assert_eq!(replayed_tbl.columns, tbl.columns);
+
+    // Clean up
+    replay.cleanup().await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_exclusion_constraint_with_opclass_round_trip()
+-> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::ConstraintKind;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // inet_ops isn't inet's default GiST operator class, so it has to be kept
+    execute_sql(
+        connection,
+        r#"CREATE TABLE subnets (
+            network inet NOT NULL,
+            active_during tstzrange NOT NULL,
+            CONSTRAINT subnets_no_overlap EXCLUDE USING gist (network inet_ops WITH &&, active_during WITH &&)
+        );"#,
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let tbl = schema.tables.get("subnets").unwrap();
+    let exclusion = tbl
+        .constraints
+        .iter()
+        .find(|c| c.name == "subnets_no_overlap")
+        .unwrap();
+    assert!(matches!(exclusion.kind, ConstraintKind::Exclusion));
+    assert_eq!(
+        exclusion.definition,
+        "EXCLUDE USING gist (network inet_ops WITH &&, active_during WITH &&)"
+    );
+    // The backing index records the operator class per column
+    let index = tbl
+        .indexes
+        .iter()
+        .find(|i| i.name == "subnets_no_overlap")
+        .unwrap();
+    let opclasses: Vec<Option<&str>> = index
+        .columns
+        .iter()
+        .map(|c| c.opclass.as_deref())
+        .collect();
+    assert_eq!(opclasses, vec![Some("inet_ops"), None]);
+
+    let sql = PostgresSqlGenerator.generate_create_table(tbl)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains(
+        r#"CONSTRAINT "subnets_no_overlap" EXCLUDE USING gist (network inet_ops WITH &&"#
+    ));
+
+    // Replaying the table keeps the operator class and the constraint name
+    let replay = TestDb::new().await?;
+    execute_sql(&replay.conn, &sql).await?;
+    let replayed = replay.conn.introspect().await?;
+    let replayed_tbl = replayed.tables.get("subnets").unwrap();
+    assert_eq!(replayed_tbl.constraints, tbl.constraints);
+    assert_eq!(replayed_tbl.indexes, tbl.indexes);
+
+    // Clean up
+    replay.cleanup().await?;
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_alter_column_nullability_and_default_migrate_both_ways()
+-> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let before = TestDb::new().await?;
+    let after = TestDb::new().await?;
+
+    execute_sql(
+        &before.conn,
+        "CREATE TABLE members (email text, nickname text NOT NULL, plan text DEFAULT 'free', locale text DEFAULT 'en');",
+    )
+    .await?;
+    execute_sql(
+        &after.conn,
+        "CREATE TABLE members (email text NOT NULL, nickname text, plan text DEFAULT 'trial', locale text);",
+    )
+    .await?;
+    let old_table = before.conn.introspect().await?.tables["members"].clone();
+    let new_table = after.conn.introspect().await?.tables["members"].clone();
+
+    let (up, down) = PostgresSqlGenerator.generate_alter_table(&old_table, &new_table)?;
+    assert_eq!(up.len(), 4);
+
+    // Applying the up statements reaches the new columns, the down ones return
+    for statement in &up {
+        execute_sql(&before.conn, statement).await?;
+    }
+    let migrated = before.conn.introspect().await?;
+    assert_eq!(migrated.tables["members"].columns, new_table.columns);
+
+    for statement in &down {
+        execute_sql(&before.conn, statement).await?;
+    }
+    let reverted = before.conn.introspect().await?;
+    assert_eq!(reverted.tables["members"].columns, old_table.columns);
+
+    // Clean up
+    after.cleanup().await?;
+    before.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_alter_table_constraint_changes_migrate_both_ways()
+-> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let before = TestDb::new().await?;
+    let after = TestDb::new().await?;
+
+    execute_sql(
+        &before.conn,
+        "CREATE TABLE members (email text CONSTRAINT members_email_key UNIQUE, legacy_code text CONSTRAINT members_legacy_code_check CHECK (legacy_code <> ''), age integer);",
+    )
+    .await?;
+    execute_sql(
+        &after.conn,
+        "CREATE TABLE members (email text, age integer CONSTRAINT members_age_check CHECK (age >= 0));",
+    )
+    .await?;
+    let old_table = before.conn.introspect().await?.tables["members"].clone();
+    let new_table = after.conn.introspect().await?.tables["members"].clone();
+
+    // The dropped column's check goes first, before the column takes it along
+    let (up, down) = PostgresSqlGenerator.generate_alter_table(&old_table, &new_table)?;
+    for statement in &up {
+        execute_sql(&before.conn, statement).await?;
+    }
+    let migrated = before.conn.introspect().await?;
+    assert_eq!(migrated.tables["members"].constraints, new_table.constraints);
+
+    for statement in &down {
+        execute_sql(&before.conn, statement).await?;
+    }
+    let reverted = before.conn.introspect().await?;
+    let mut constraints = reverted.tables["members"].constraints.clone();
+    let mut expected = old_table.constraints.clone();
+    constraints.sort_by(|a, b| a.name.cmp(&b.name));
+    expected.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(constraints, expected);
+
+    // Clean up
+    after.cleanup().await?;
+    before.cleanup().await?;
+    Ok(())
+}
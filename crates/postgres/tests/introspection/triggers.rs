@@ -211,6 +211,56 @@ async fn test_introspect_trigger_for_each_statement() -> Result<(), Box<dyn std:
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_trigger_update_of_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Create a table and a trigger function
+    connection.execute(r#"CREATE TABLE test_trigger_cols (id serial PRIMARY KEY, balance numeric, "Status" text);"#).await?;
+    connection.execute(r#"
+        CREATE OR REPLACE FUNCTION test_trigger_cols_func()
+        RETURNS trigger AS $$
+        BEGIN
+            RETURN NEW;
+        END;
+        $$ LANGUAGE plpgsql;
+    "#).await?;
+
+    // Create a trigger that only fires for updates of two columns
+    connection.execute(r#"
+        CREATE TRIGGER test_cols_trigger
+        AFTER INSERT OR UPDATE OF balance, "Status" ON test_trigger_cols
+        FOR EACH ROW
+        EXECUTE FUNCTION test_trigger_cols_func();
+    "#).await?;
+
+    // Introspect the database
+    let schema = connection.introspect().await?;
+
+    // Verify the column list was introspected
+    let trig = schema.triggers.values().find(|t| t.name == "test_cols_trigger").expect("Should find trigger");
+    debug!("Trigger columns: {:?}", trig);
+    assert!(trig.events.iter().any(|e| matches!(e, TriggerEvent::Insert)));
+    assert!(trig.events.iter().any(|e| matches!(e, TriggerEvent::Update)));
+    assert_eq!(trig.update_columns, vec!["balance".to_string(), "Status".to_string()]);
+
+    // Recreating the trigger from the generated SQL keeps the column list
+    let sql = PostgresSqlGenerator.create_trigger(trig)?;
+    debug!("Generated SQL: {}", sql);
+    assert!(sql.contains(r#"UPDATE OF "balance", "Status""#));
+    connection.execute("DROP TRIGGER test_cols_trigger ON test_trigger_cols;").await?;
+    connection.execute(&sql).await?;
+    let schema = connection.introspect().await?;
+    let recreated = schema.triggers.values().find(|t| t.name == "test_cols_trigger").expect("Should find trigger");
+    assert_eq!(recreated.update_columns, trig.update_columns);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_trigger_with_comment() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -310,4 +360,79 @@ async fn test_introspect_constraint_trigger() -> Result<(), Box<dyn std::error::
     }
 
     Ok(())
-} 
\ No newline at end of file
+} 
+#[tokio::test]
+async fn test_introspect_plain_trigger_is_not_a_constraint_trigger() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    connection.execute("CREATE TABLE plain_trigger_table (id integer);").await?;
+    connection.execute(r#"
+        CREATE FUNCTION plain_trigger_func() RETURNS trigger AS $$
+        BEGIN
+            RETURN NEW;
+        END;
+        $$ LANGUAGE plpgsql;
+    "#).await?;
+    connection.execute(r#"
+        CREATE TRIGGER plain_trigger BEFORE INSERT ON plain_trigger_table
+        FOR EACH ROW EXECUTE FUNCTION plain_trigger_func();
+    "#).await?;
+    connection.execute(r#"
+        CREATE CONSTRAINT TRIGGER deferred_check AFTER INSERT ON plain_trigger_table
+        DEFERRABLE INITIALLY DEFERRED
+        FOR EACH ROW EXECUTE FUNCTION plain_trigger_func();
+    "#).await?;
+
+    let schema = connection.introspect().await?;
+
+    // pg_trigger.tgconstraint is 0 rather than NULL for ordinary triggers
+    let names: Vec<&str> = schema
+        .constraint_triggers
+        .values()
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["deferred_check"]);
+    assert!(schema.triggers.values().any(|t| t.name == "plain_trigger"));
+
+    db.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_introspect_same_trigger_and_rule_name_on_two_tables() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // Trigger and rule names are only unique per table
+    connection.execute("CREATE TABLE projects (id SERIAL PRIMARY KEY);").await?;
+    connection.execute("CREATE TABLE invoices (id SERIAL PRIMARY KEY);").await?;
+    connection.execute(r#"
+        CREATE FUNCTION audit_change() RETURNS trigger LANGUAGE plpgsql AS $$
+        BEGIN
+            RETURN NEW;
+        END;
+        $$;
+    "#).await?;
+    connection.execute("CREATE TRIGGER audit AFTER INSERT ON projects FOR EACH ROW EXECUTE FUNCTION audit_change();").await?;
+    connection.execute("CREATE TRIGGER audit AFTER UPDATE ON invoices FOR EACH ROW EXECUTE FUNCTION audit_change();").await?;
+    connection.execute("CREATE RULE protect AS ON DELETE TO projects DO INSTEAD NOTHING;").await?;
+    connection.execute("CREATE RULE protect AS ON DELETE TO invoices DO INSTEAD NOTHING;").await?;
+
+    let schema = connection.introspect().await?;
+    debug!("Introspected triggers: {:?}", schema.triggers);
+
+    let projects = schema.triggers.get("projects.audit").expect("Trigger on projects should exist");
+    assert_eq!(projects.table, "projects");
+    assert_eq!(projects.events, vec![TriggerEvent::Insert]);
+    let invoices = schema.triggers.get("invoices.audit").expect("Trigger on invoices should exist");
+    assert_eq!(invoices.table, "invoices");
+    assert_eq!(invoices.events, vec![TriggerEvent::Update]);
+
+    assert_eq!(schema.rules.get("projects.protect").map(|r| r.table.as_str()), Some("projects"));
+    assert_eq!(schema.rules.get("invoices.protect").map(|r| r.table.as_str()), Some("invoices"));
+
+    Ok(())
+}
@@ -171,6 +171,75 @@ async fn test_introspect_view_with_cascaded_check_option() -> Result<(), Box<dyn
     Ok(())
 }
 
+#[tokio::test]
+async fn test_introspect_view_check_options_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use postgres::PostgresSqlGenerator;
+    use shem_core::traits::SqlGenerator;
+
+    env_logger::try_init().ok();
+    let db = TestDb::new().await?;
+    let connection = &db.conn;
+
+    // A LOCAL view stacked on a CASCADED one, each checking its own condition
+    execute_sql(
+        connection,
+        "CREATE TABLE stock (id SERIAL PRIMARY KEY, quantity integer, price numeric);",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE VIEW in_stock AS SELECT * FROM stock WHERE quantity > 0 WITH CASCADED CHECK OPTION;",
+    )
+    .await?;
+    execute_sql(
+        connection,
+        "CREATE VIEW cheap_in_stock AS SELECT * FROM in_stock WHERE price < 10 WITH LOCAL CHECK OPTION;",
+    )
+    .await?;
+
+    let schema = connection.introspect().await?;
+    let in_stock = schema.views.get("in_stock").expect("View should exist");
+    let cheap = schema.views.get("cheap_in_stock").expect("View should exist");
+    assert_eq!(in_stock.check_option, CheckOption::Cascaded);
+    assert_eq!(cheap.check_option, CheckOption::Local);
+
+    // Recreating both views from the generated SQL keeps their check options
+    let in_stock_sql = PostgresSqlGenerator.create_view(in_stock)?;
+    let cheap_sql = PostgresSqlGenerator.create_view(cheap)?;
+    debug!("Generated SQL: {}\n{}", in_stock_sql, cheap_sql);
+    assert!(in_stock_sql.ends_with(" WITH CASCADED CHECK OPTION;"));
+    assert!(cheap_sql.ends_with(" WITH LOCAL CHECK OPTION;"));
+    execute_sql(connection, "DROP VIEW cheap_in_stock, in_stock;").await?;
+    execute_sql(connection, &in_stock_sql).await?;
+    execute_sql(connection, &cheap_sql).await?;
+
+    let schema = connection.introspect().await?;
+    assert_eq!(
+        schema.views.get("in_stock").expect("View should exist").check_option,
+        CheckOption::Cascaded
+    );
+    assert_eq!(
+        schema.views.get("cheap_in_stock").expect("View should exist").check_option,
+        CheckOption::Local
+    );
+
+    // Inserts through the LOCAL view are checked against its own condition and,
+    // through the CASCADED view below it, against that view's condition
+    execute_sql(connection, "INSERT INTO cheap_in_stock (quantity, price) VALUES (1, 5);").await?;
+    assert!(
+        execute_sql(connection, "INSERT INTO cheap_in_stock (quantity, price) VALUES (0, 5);")
+            .await
+            .is_err()
+    );
+    assert!(
+        execute_sql(connection, "INSERT INTO cheap_in_stock (quantity, price) VALUES (1, 50);")
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_introspect_view_with_security_barrier() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::try_init().ok();
@@ -11,6 +11,7 @@ fn test_create_collation() {
         lc_ctype: None,
         provider: CollationProvider::Icu,
         deterministic: true,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -32,6 +33,7 @@ fn test_create_collation_not_deterministic() {
         lc_ctype: None,
         provider: CollationProvider::Libc,
         deterministic: false,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -53,6 +55,7 @@ fn test_create_collation_with_reserved_keyword() {
         lc_ctype: None,
         provider: CollationProvider::Icu,
         deterministic: true,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -74,6 +77,7 @@ fn test_drop_collation() {
         lc_ctype: None,
         provider: CollationProvider::Icu,
         deterministic: true,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -92,6 +96,7 @@ fn test_drop_collation_with_schema() {
         lc_ctype: None,
         provider: CollationProvider::Icu,
         deterministic: true,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -120,6 +125,7 @@ fn test_create_collation_common_locales() {
             lc_ctype: None,
             provider: provider.clone(),
             deterministic: true,
+            comment: None,
         };
 
         let generator = PostgresSqlGenerator;
@@ -35,6 +35,7 @@ fn test_create_function() {
         parallel_safety: ParallelSafety::Safe,
         cost: Some(1.0),
         rows: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -75,6 +76,7 @@ fn test_drop_function() {
         parallel_safety: ParallelSafety::Safe,
         cost: None,
         rows: None,
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.drop_function(&func).unwrap();
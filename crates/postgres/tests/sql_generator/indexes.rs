@@ -20,6 +20,7 @@ fn test_create_index() {
         where_clause: Some("email IS NOT NULL".to_string()),
         tablespace: None,
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -49,6 +50,7 @@ fn test_create_unique_index() {
         where_clause: None,
         tablespace: None,
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -84,6 +86,7 @@ fn test_create_index_multiple_columns() {
         where_clause: None,
         tablespace: Some("fast_space".to_string()),
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -122,6 +125,7 @@ fn test_create_index_different_methods() {
             where_clause: None,
             tablespace: None,
             storage_parameters: std::collections::HashMap::new(),
+            include: vec![],
         };
 
         let generator = PostgresSqlGenerator;
@@ -149,6 +153,7 @@ fn test_create_index_with_reserved_keyword() {
         where_clause: None,
         tablespace: None,
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -177,6 +182,7 @@ fn test_drop_index() {
         where_clause: None,
         tablespace: None,
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -203,6 +209,7 @@ fn test_create_index_partial() {
         where_clause: Some("active = true AND deleted_at IS NULL".to_string()),
         tablespace: None,
         storage_parameters: std::collections::HashMap::new(),
+        include: vec![],
     };
 
     let generator = PostgresSqlGenerator;
@@ -12,6 +12,7 @@ fn test_create_rule() {
         instead: true,
         condition: Some("OLD.status = 'inactive'".to_string()),
         actions: vec!["DO NOTHING".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -34,6 +35,7 @@ fn test_create_rule_also() {
         instead: false,
         condition: None,
         actions: vec!["INSERT INTO audit_log (table_name, action, old_data, new_data) VALUES ('users', 'UPDATE', row_to_json(OLD), row_to_json(NEW))".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -56,6 +58,7 @@ fn test_create_rule_insert() {
         instead: true,
         condition: Some("NEW.email IS NULL".to_string()),
         actions: vec!["DO NOTHING".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -78,6 +81,7 @@ fn test_create_rule_delete() {
         instead: true,
         condition: None,
         actions: vec!["UPDATE users SET deleted_at = NOW() WHERE id = OLD.id".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -100,6 +104,7 @@ fn test_create_rule_with_reserved_keyword() {
         instead: false,
         condition: None,
         actions: vec!["DO NOTHING".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -121,6 +126,7 @@ fn test_drop_rule() {
         instead: false,
         condition: None,
         actions: vec!["DO NOTHING".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -139,6 +145,7 @@ fn test_drop_rule_with_schema() {
         instead: false,
         condition: None,
         actions: vec!["DO NOTHING".to_string()],
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -149,3 +156,87 @@ fn test_drop_rule_with_schema() {
         "DROP RULE IF EXISTS public.my_rule ON public.my_table CASCADE;"
     );
 }
+
+#[test]
+fn test_driver_generator_override() {
+    use postgres::PostgresDriver;
+    use shem_core::schema::{Column, Table};
+    use shem_core::{DatabaseDriver, SqlGeneratorOverride};
+    use std::sync::Arc;
+
+    /// Uppercases everything outside quoted identifiers and literals
+    #[derive(Debug)]
+    struct UppercaseKeywords;
+
+    impl SqlGeneratorOverride for UppercaseKeywords {
+        fn rewrite(&self, sql: String) -> String {
+            let mut quote = None;
+            sql.chars()
+                .map(|c| match (quote, c) {
+                    (None, '"' | '\'') => {
+                        quote = Some(c);
+                        c
+                    }
+                    (Some(open), _) if c == open => {
+                        quote = None;
+                        c
+                    }
+                    (None, _) => c.to_ascii_uppercase(),
+                    (Some(_), _) => c,
+                })
+                .collect()
+        }
+    }
+
+    let table = Table {
+        name: "accounts".to_string(),
+        schema: None,
+        columns: vec![Column {
+            name: "status".to_string(),
+            type_name: "text".to_string(),
+            nullable: false,
+            default: Some("'active'".to_string()),
+            identity: None,
+            generated: None,
+            comment: None,
+            collation: None,
+            storage: None,
+            compression: None,
+            inherited: false,
+            grants: Vec::new(),
+        }],
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+
+    let plain = PostgresDriver::new().sql_generator();
+    assert!(
+        plain
+            .generate_create_table(&table)
+            .unwrap()
+            .contains("\"status\" text")
+    );
+
+    let driver = PostgresDriver::new().with_generator_override(Arc::new(UppercaseKeywords));
+    let generator = driver.sql_generator();
+    assert_eq!(
+        generator.generate_create_table(&table).unwrap(),
+        "CREATE TABLE \"accounts\" (\n    \"status\" TEXT NOT NULL DEFAULT 'active'\n);"
+    );
+    assert_eq!(
+        generator.generate_drop_table(&table).unwrap(),
+        "DROP TABLE IF EXISTS \"accounts\" CASCADE;"
+    );
+}
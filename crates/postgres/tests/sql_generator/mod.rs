@@ -8,6 +8,7 @@ pub mod triggers;
 pub mod policies;
 pub mod indexes;
 pub mod extensions;
-pub mod misc; 
+pub mod misc;
+pub mod roles;
 
 //pub mod collation;
\ No newline at end of file
@@ -13,6 +13,7 @@ fn test_create_policy() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("user_id = current_user_id()".to_string()),
         check: Some("user_id = current_user_id()".to_string()),
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -36,6 +37,7 @@ fn test_create_policy_select_only() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("active = true".to_string()),
         check: None,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -59,6 +61,7 @@ fn test_create_policy_insert_only() {
         roles: vec!["PUBLIC".to_string()],
         using: None,
         check: Some("email IS NOT NULL".to_string()),
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -82,6 +85,7 @@ fn test_create_policy_update_only() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("user_id = current_user_id()".to_string()),
         check: Some("user_id = current_user_id()".to_string()),
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -105,6 +109,7 @@ fn test_create_policy_delete_only() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("user_id = current_user_id()".to_string()),
         check: None,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -128,6 +133,7 @@ fn test_create_policy_specific_roles() {
         roles: vec!["admin".to_string(), "superuser".to_string()],
         using: Some("true".to_string()),
         check: Some("true".to_string()),
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -151,6 +157,7 @@ fn test_create_policy_with_reserved_keyword() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("user_id = current_user_id()".to_string()),
         check: None,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -173,6 +180,7 @@ fn test_drop_policy() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("true".to_string()),
         check: None,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -192,6 +200,7 @@ fn test_drop_policy_with_schema() {
         roles: vec!["PUBLIC".to_string()],
         using: Some("true".to_string()),
         check: None,
+        comment: None,
     };
 
     let generator = PostgresSqlGenerator;
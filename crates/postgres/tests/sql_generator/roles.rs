@@ -12,10 +12,12 @@ fn test_create_role_basic() {
         inherit: true,
         login: true,
         replication: false,
+        bypass_rls: false,
         connection_limit: None,
         password: None,
         valid_until: None,
         member_of: vec![],
+        settings: vec![],
     };
     let sql = PostgresSqlGenerator.create_role(&role).unwrap();
     assert_eq!(sql, "CREATE ROLE \"test_user\" INHERIT LOGIN;");
@@ -31,10 +33,12 @@ fn test_create_role_with_options() {
         inherit: false,
         login: true,
         replication: true,
+        bypass_rls: false,
         connection_limit: Some(10),
         password: Some("secret".to_string()),
         valid_until: Some("2025-01-01".to_string()),
         member_of: vec!["group1".to_string(), "group2".to_string()],
+        settings: vec![],
     };
     let sql = PostgresSqlGenerator.create_role(&role).unwrap();
     assert!(sql.contains("SUPERUSER"));
@@ -48,6 +52,56 @@ fn test_create_role_with_options() {
     assert!(sql.contains("IN ROLE group1, group2"));
 }
 
+#[test]
+fn test_create_role_noinherit_bypassrls() {
+    let role = Role {
+        name: "auditor".to_string(),
+        superuser: false,
+        createdb: false,
+        createrole: false,
+        inherit: false,
+        login: true,
+        replication: false,
+        bypass_rls: true,
+        connection_limit: None,
+        password: None,
+        valid_until: None,
+        member_of: vec![],
+        settings: vec![],
+    };
+    let sql = PostgresSqlGenerator.create_role(&role).unwrap();
+    assert_eq!(sql, "CREATE ROLE \"auditor\" NOINHERIT LOGIN BYPASSRLS;");
+}
+
+#[test]
+fn test_create_role_with_settings() {
+    let role = Role {
+        name: "app".to_string(),
+        superuser: false,
+        createdb: false,
+        createrole: false,
+        inherit: true,
+        login: true,
+        replication: false,
+        bypass_rls: false,
+        connection_limit: None,
+        password: None,
+        valid_until: None,
+        member_of: vec![],
+        settings: vec![
+            ("search_path".to_string(), "app, \"$user\", public".to_string()),
+            ("statement_timeout".to_string(), "5s".to_string()),
+        ],
+    };
+    let sql = PostgresSqlGenerator.create_role(&role).unwrap();
+    assert_eq!(
+        sql,
+        "CREATE ROLE \"app\" INHERIT LOGIN;\n\
+         ALTER ROLE \"app\" SET search_path TO 'app', '$user', 'public';\n\
+         ALTER ROLE \"app\" SET statement_timeout TO '5s';"
+    );
+}
+
 #[test]
 fn test_drop_role() {
     let role = Role {
@@ -58,10 +112,12 @@ fn test_drop_role() {
         inherit: true,
         login: true,
         replication: false,
+        bypass_rls: false,
         connection_limit: None,
         password: None,
         valid_until: None,
         member_of: vec![],
+        settings: vec![],
     };
     let sql = PostgresSqlGenerator.drop_role(&role).unwrap();
     assert_eq!(sql, "DROP ROLE IF EXISTS \"test_user\" CASCADE;");
@@ -16,6 +16,7 @@ fn test_create_sequence_basic() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -45,6 +46,7 @@ fn test_create_sequence_with_owned_by() {
         cycle: true,
         owned_by: Some("users.id".to_string()),
         comment: Some("User ID sequence".to_string()),
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -75,6 +77,7 @@ fn test_create_sequence_no_limits() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -104,6 +107,7 @@ fn test_drop_sequence() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -126,6 +130,7 @@ fn test_drop_sequence_with_schema() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -148,6 +153,7 @@ fn test_alter_sequence_change_all_properties() {
         cycle: false,
         owned_by: Some("users.id".to_string()),
         comment: None,
+        owner: None,
     };
 
     let new_sequence = Sequence {
@@ -162,6 +168,7 @@ fn test_alter_sequence_change_all_properties() {
         cycle: true,
         owned_by: Some("users.id".to_string()),
         comment: Some("Updated user ID sequence".to_string()),
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -194,6 +201,7 @@ fn test_alter_sequence_no_changes() {
         cycle: false,
         owned_by: Some("users.id".to_string()),
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -217,6 +225,7 @@ fn test_alter_sequence_remove_limits() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let new_sequence = Sequence {
@@ -231,6 +240,7 @@ fn test_alter_sequence_remove_limits() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
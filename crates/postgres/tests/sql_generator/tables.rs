@@ -19,6 +19,8 @@ fn test_generate_create_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "default".to_string(),
@@ -31,6 +33,8 @@ fn test_generate_create_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "email".to_string(),
@@ -43,6 +47,8 @@ fn test_generate_create_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
         ],
         constraints: vec![
@@ -67,6 +73,13 @@ fn test_generate_create_table() {
         inherits: vec![],
         partition_by: None,
         storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -100,6 +113,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "name".to_string(),
@@ -112,6 +127,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "email".to_string(),
@@ -124,6 +141,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "to_drop".to_string(),
@@ -136,6 +155,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
         ],
         constraints: vec![Constraint {
@@ -151,6 +172,13 @@ fn test_generate_alter_table() {
         inherits: vec![],
         partition_by: None,
         storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
     };
 
     // New table with modified columns and constraints
@@ -177,6 +205,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "name".to_string(),
@@ -189,6 +219,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "email".to_string(),
@@ -204,6 +236,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
             Column {
                 name: "new_column".to_string(),
@@ -216,6 +250,8 @@ fn test_generate_alter_table() {
                 collation: None,
                 storage: None,
                 compression: None,
+                inherited: false,
+                grants: Vec::new(),
             },
         ],
         constraints: vec![Constraint {
@@ -231,6 +267,13 @@ fn test_generate_alter_table() {
         inherits: vec![],
         partition_by: None,
         storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -260,3 +303,382 @@ fn test_generate_alter_table() {
         up_sql.contains("ALTER TABLE users ADD CONSTRAINT new_constraint CHECK (LENGTH(name) > 0)")
     );
 }
+
+#[test]
+fn test_generate_alter_table_idempotent() {
+    use shem_core::GenerateOptions;
+
+    let column = |name: &str| Column {
+        name: name.to_string(),
+        type_name: "text".to_string(),
+        nullable: true,
+        default: None,
+        identity: None,
+        generated: None,
+        comment: None,
+        collation: None,
+        storage: None,
+        compression: None,
+        inherited: false,
+        grants: Vec::new(),
+    };
+    let table = |columns: Vec<Column>| Table {
+        name: "users".to_string(),
+        schema: None,
+        columns,
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+    let old_table = table(vec![column("legacy")]);
+    let new_table = table(vec![column("email")]);
+    let generator = PostgresSqlGenerator;
+
+    let (up, down) = generator
+        .generate_alter_table_with_options(
+            &old_table,
+            &new_table,
+            &GenerateOptions {
+                idempotent: true,
+                ..GenerateOptions::default()
+            },
+        )
+        .unwrap();
+    assert!(
+        up.contains(&"ALTER TABLE \"users\" ADD COLUMN IF NOT EXISTS \"email\" text".to_string())
+    );
+    assert!(up.contains(&"ALTER TABLE \"users\" DROP COLUMN IF EXISTS \"legacy\"".to_string()));
+    assert!(down.contains(&"ALTER TABLE \"users\" DROP COLUMN IF EXISTS \"email\"".to_string()));
+    assert!(
+        down.contains(
+            &"ALTER TABLE \"users\" ADD COLUMN IF NOT EXISTS \"legacy\" text".to_string()
+        )
+    );
+
+    // Without the flag the plain forms are emitted
+    let (up, _) = generator
+        .generate_alter_table(&old_table, &new_table)
+        .unwrap();
+    assert!(up.contains(&"ALTER TABLE \"users\" ADD COLUMN \"email\" text".to_string()));
+    assert!(up.contains(&"ALTER TABLE \"users\" DROP COLUMN \"legacy\"".to_string()));
+}
+
+#[test]
+fn test_generate_alter_column_type_with_using() {
+    use shem_core::GenerateOptions;
+
+    let table = |type_name: &str| Table {
+        name: "orders".to_string(),
+        schema: None,
+        columns: vec![Column {
+            name: "quantity".to_string(),
+            type_name: type_name.to_string(),
+            nullable: true,
+            default: None,
+            identity: None,
+            generated: None,
+            comment: None,
+            collation: None,
+            storage: None,
+            compression: None,
+            inherited: false,
+            grants: Vec::new(),
+        }],
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+    let generator = PostgresSqlGenerator;
+
+    // Columns are cast to the new type, and back again on rollback
+    let (up, down) = generator
+        .generate_alter_table(&table("integer"), &table("bigint"))
+        .unwrap();
+    assert_eq!(
+        up,
+        vec![
+            "ALTER TABLE \"orders\" ALTER COLUMN \"quantity\" TYPE bigint USING \"quantity\"::bigint"
+                .to_string()
+        ]
+    );
+    assert_eq!(
+        down,
+        vec![
+            "ALTER TABLE \"orders\" ALTER COLUMN \"quantity\" TYPE integer USING \"quantity\"::integer"
+                .to_string()
+        ]
+    );
+
+    // A configured expression replaces the cast for conversions it can't do
+    let options = GenerateOptions {
+        column_using: [(
+            "orders.quantity".to_string(),
+            "trim(quantity)::integer".to_string(),
+        )]
+        .into_iter()
+        .collect(),
+        ..GenerateOptions::default()
+    };
+    let (up, _) = generator
+        .generate_alter_table_with_options(&table("text"), &table("integer"), &options)
+        .unwrap();
+    assert_eq!(
+        up,
+        vec![
+            "ALTER TABLE \"orders\" ALTER COLUMN \"quantity\" TYPE integer USING trim(quantity)::integer"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_generate_alter_column_nullability_and_default() {
+    let column = |name: &str, nullable: bool, default: Option<&str>| Column {
+        name: name.to_string(),
+        type_name: "text".to_string(),
+        nullable,
+        default: default.map(str::to_string),
+        identity: None,
+        generated: None,
+        comment: None,
+        collation: None,
+        storage: None,
+        compression: None,
+        inherited: false,
+        grants: Vec::new(),
+    };
+    let table = |columns: Vec<Column>| Table {
+        name: "users".to_string(),
+        schema: None,
+        columns,
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+    let old_table = table(vec![
+        column("email", true, None),
+        column("nickname", false, None),
+        column("status", true, None),
+        column("plan", true, Some("'free'::text")),
+        column("locale", true, Some("'en'::text")),
+    ]);
+    let new_table = table(vec![
+        column("email", false, None),
+        column("nickname", true, None),
+        column("status", true, Some("'active'::text")),
+        column("plan", true, Some("'trial'::text")),
+        column("locale", true, None),
+    ]);
+
+    let (up, down) = PostgresSqlGenerator
+        .generate_alter_table(&old_table, &new_table)
+        .unwrap();
+    assert_eq!(
+        up,
+        vec![
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" SET NOT NULL",
+            "ALTER TABLE \"users\" ALTER COLUMN \"nickname\" DROP NOT NULL",
+            "ALTER TABLE \"users\" ALTER COLUMN \"status\" SET DEFAULT 'active'::text",
+            "ALTER TABLE \"users\" ALTER COLUMN \"plan\" SET DEFAULT 'trial'::text",
+            "ALTER TABLE \"users\" ALTER COLUMN \"locale\" DROP DEFAULT",
+        ]
+    );
+    assert_eq!(
+        down,
+        vec![
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" DROP NOT NULL",
+            "ALTER TABLE \"users\" ALTER COLUMN \"nickname\" SET NOT NULL",
+            "ALTER TABLE \"users\" ALTER COLUMN \"status\" DROP DEFAULT",
+            "ALTER TABLE \"users\" ALTER COLUMN \"plan\" SET DEFAULT 'free'::text",
+            "ALTER TABLE \"users\" ALTER COLUMN \"locale\" SET DEFAULT 'en'::text",
+        ]
+    );
+
+    // Equivalent spellings of the same default are left alone
+    let (up, _) = PostgresSqlGenerator
+        .generate_alter_table(
+            &table(vec![column("plan", true, Some("'free'::text"))]),
+            &table(vec![column("plan", true, Some("'free'"))]),
+        )
+        .unwrap();
+    assert!(up.is_empty());
+}
+
+#[test]
+fn test_generate_alter_table_constraint_changes() {
+    let constraint = |name: &str, kind: ConstraintKind, definition: &str| Constraint {
+        name: name.to_string(),
+        kind,
+        definition: definition.to_string(),
+        deferrable: false,
+        initially_deferred: false,
+    };
+    let table = |constraints: Vec<Constraint>| Table {
+        name: "users".to_string(),
+        schema: None,
+        columns: vec![],
+        constraints,
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+    let old_table = table(vec![
+        constraint("users_email_key", ConstraintKind::Unique, "UNIQUE (email)"),
+        constraint("users_age_check", ConstraintKind::Check, "CHECK ((age >= 0))"),
+    ]);
+    let new_table = table(vec![
+        constraint("users_adult_age", ConstraintKind::Check, "CHECK ((age >= 0))"),
+        constraint("users_name_check", ConstraintKind::Check, "CHECK ((name <> ''::text))"),
+    ]);
+
+    // The renamed check is dropped and added again under its new name
+    let (up, down) = PostgresSqlGenerator
+        .generate_alter_table(&old_table, &new_table)
+        .unwrap();
+    assert_eq!(
+        up,
+        vec![
+            "ALTER TABLE \"users\" DROP CONSTRAINT \"users_email_key\"",
+            "ALTER TABLE \"users\" DROP CONSTRAINT \"users_age_check\"",
+            "ALTER TABLE \"users\" ADD CONSTRAINT \"users_adult_age\" CHECK ((age >= 0))",
+            "ALTER TABLE \"users\" ADD CONSTRAINT \"users_name_check\" CHECK ((name <> ''::text))",
+        ]
+    );
+    assert_eq!(
+        down,
+        vec![
+            "ALTER TABLE \"users\" DROP CONSTRAINT \"users_adult_age\"",
+            "ALTER TABLE \"users\" DROP CONSTRAINT \"users_name_check\"",
+            "ALTER TABLE \"users\" ADD CONSTRAINT \"users_email_key\" UNIQUE (email)",
+            "ALTER TABLE \"users\" ADD CONSTRAINT \"users_age_check\" CHECK ((age >= 0))",
+        ]
+    );
+
+    // Unchanged constraints produce nothing
+    let (up, down) = PostgresSqlGenerator
+        .generate_alter_table(&old_table, &old_table)
+        .unwrap();
+    assert!(up.is_empty() && down.is_empty());
+}
+
+#[test]
+fn test_diff_qualifies_non_public_tables() {
+    use shem_core::Schema;
+    use shem_core::GenerateOptions;
+    use shem_core::migration::generate_migration_with;
+    use shem_core::schema::QualifiedName;
+
+    let table = |schema: &str, columns: &[&str]| Table {
+        name: "orders".to_string(),
+        schema: Some(schema.to_string()),
+        columns: columns
+            .iter()
+            .map(|name| Column {
+                name: name.to_string(),
+                type_name: "integer".to_string(),
+                nullable: true,
+                default: None,
+                identity: None,
+                generated: None,
+                comment: None,
+                collation: None,
+                storage: None,
+                compression: None,
+                inherited: false,
+                grants: vec![],
+            })
+            .collect(),
+        constraints: vec![],
+        indexes: vec![],
+        comment: None,
+        tablespace: None,
+        inherits: vec![],
+        partition_by: None,
+        storage_parameters: std::collections::HashMap::new(),
+        grants: vec![],
+        owner: None,
+        unlogged: false,
+        partition_bound: None,
+        row_security: false,
+        force_row_security: false,
+        of_type: None,
+    };
+    let schema_with = |tables: Vec<Table>| {
+        let mut schema = Schema::new();
+        for table in tables {
+            let key = QualifiedName::key(&table.schema, &table.name);
+            schema.tables.insert(key, table);
+        }
+        schema
+    };
+    let diff = |from: &Schema, to: &Schema| {
+        generate_migration_with(from, to, &PostgresSqlGenerator, &GenerateOptions::default())
+            .unwrap()
+            .statements
+            .join("\n")
+    };
+
+    // A new app.orders next to an unchanged public.orders is created in its schema
+    let public_only = schema_with(vec![table("public", &["id"])]);
+    let with_app = schema_with(vec![table("public", &["id"]), table("app", &["id"])]);
+    let up = diff(&public_only, &with_app);
+    assert!(up.contains("CREATE TABLE \"app\".\"orders\""), "{up}");
+    assert!(!up.contains("CREATE TABLE \"orders\""), "{up}");
+
+    // Changing app.orders alters only that table
+    let altered = schema_with(vec![table("public", &["id"]), table("app", &["id", "total"])]);
+    let up = diff(&with_app, &altered);
+    assert!(
+        up.contains("ALTER TABLE \"app\".\"orders\" ADD COLUMN \"total\" integer"),
+        "{up}"
+    );
+    assert!(!up.contains("ALTER TABLE \"orders\""), "{up}");
+
+    // Removing app.orders drops it from its schema
+    let up = diff(&with_app, &public_only);
+    assert!(up.contains("DROP TABLE IF EXISTS \"app\".\"orders\""), "{up}");
+    assert!(!up.contains("DROP TABLE IF EXISTS \"orders\""), "{up}");
+}
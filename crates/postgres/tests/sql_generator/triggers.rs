@@ -10,6 +10,7 @@ fn test_create_trigger_basic() {
         schema: None,
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -33,6 +34,7 @@ fn test_create_trigger_with_arguments() {
         schema: None,
         timing: TriggerTiming::After,
         events: vec![TriggerEvent::Update],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec!["arg1".to_string(), "arg2".to_string()],
         condition: None,
@@ -56,6 +58,7 @@ fn test_create_trigger_with_condition() {
         schema: None,
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: Some("NEW.id > 0".to_string()),
@@ -80,6 +83,7 @@ fn test_create_trigger_for_each_statement() {
         schema: None,
         timing: TriggerTiming::After,
         events: vec![TriggerEvent::Delete],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -103,6 +107,7 @@ fn test_create_trigger_multiple_events() {
         schema: None,
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert, TriggerEvent::Update],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -126,6 +131,7 @@ fn test_create_trigger_with_schema() {
         schema: Some("test_schema".to_string()),
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -149,6 +155,7 @@ fn test_create_trigger_with_comment() {
         schema: None,
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -173,6 +180,7 @@ fn test_drop_trigger() {
         schema: None,
         timing: TriggerTiming::Before,
         events: vec![TriggerEvent::Insert],
+        update_columns: vec![],
         function: "test_function".to_string(),
         arguments: vec![],
         condition: None,
@@ -9,6 +9,7 @@ fn test_generate_create_enum_type() {
         schema: Some("public".to_string()),
         values: vec!["active".to_string(), "inactive".to_string(), "pending".to_string()],
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -27,6 +28,7 @@ fn test_generate_create_enum_type_no_schema() {
         schema: None,
         values: vec!["low".to_string(), "medium".to_string(), "high".to_string()],
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -45,6 +47,7 @@ fn test_alter_enum() {
         schema: Some("public".to_string()),
         values: vec!["active".to_string(), "inactive".to_string()],
         comment: None,
+        owner: None,
     };
 
     let new_enum = EnumType {
@@ -52,6 +55,7 @@ fn test_alter_enum() {
         schema: Some("public".to_string()),
         values: vec!["active".to_string(), "completed".to_string(), "cancelled".to_string()],
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -72,6 +76,7 @@ fn test_alter_enum_no_changes() {
         schema: Some("public".to_string()),
         values: vec!["active".to_string(), "inactive".to_string()],
         comment: None,
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -97,6 +102,7 @@ fn test_create_domain() {
         default: Some("'noreply@example.com'".to_string()),
         not_null: true,
         comment: Some("Email address domain with validation".to_string()),
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -118,6 +124,7 @@ fn test_drop_domain() {
         default: None,
         not_null: false,
         comment: None,
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.drop_domain(&dom).unwrap();
@@ -138,6 +145,7 @@ fn test_create_sequence() {
         cycle: false,
         owned_by: Some("users.id".to_string()),
         comment: Some("User ID sequence".to_string()),
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -167,6 +175,7 @@ fn test_drop_sequence() {
         cycle: false,
         owned_by: None,
         comment: None,
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.drop_sequence(&seq).unwrap();
@@ -187,6 +196,7 @@ fn test_alter_sequence() {
         cycle: false,
         owned_by: Some("users.id".to_string()),
         comment: None,
+        owner: None,
     };
 
     let new_sequence = Sequence {
@@ -201,6 +211,7 @@ fn test_alter_sequence() {
         cycle: true,
         owned_by: Some("users.id".to_string()),
         comment: Some("Updated user ID sequence".to_string()),
+        owner: None,
     };
 
     let generator = PostgresSqlGenerator;
@@ -12,6 +12,8 @@ fn test_create_view() {
         comment: Some("Active users summary view".to_string()),
         security_barrier: false,
         columns: vec!["id".to_string(), "name".to_string(), "email".to_string()],
+        owner: None,
+        column_comments: std::collections::HashMap::new(),
     };
 
     let generator = PostgresSqlGenerator;
@@ -32,6 +34,8 @@ fn test_drop_view() {
         comment: None,
         security_barrier: false,
         columns: vec![],
+        owner: None,
+        column_comments: std::collections::HashMap::new(),
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.drop_view(&view).unwrap();
@@ -50,6 +54,8 @@ fn test_create_materialized_view_with_data() {
         storage_parameters: std::collections::HashMap::new(),
         indexes: vec![],
         populate_with_data: true,
+        column_comments: std::collections::HashMap::new(),
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.create_materialized_view(&view).unwrap();
@@ -68,6 +74,8 @@ fn test_create_materialized_view_with_no_data() {
         storage_parameters: std::collections::HashMap::new(),
         indexes: vec![],
         populate_with_data: false,
+        column_comments: std::collections::HashMap::new(),
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.create_materialized_view(&view).unwrap();
@@ -86,6 +94,8 @@ fn test_create_materialized_view_with_reserved_keyword() {
         storage_parameters: std::collections::HashMap::new(),
         indexes: vec![],
         populate_with_data: true,
+        column_comments: std::collections::HashMap::new(),
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.create_materialized_view(&view).unwrap();
@@ -104,6 +114,8 @@ fn test_drop_materialized_view() {
         storage_parameters: std::collections::HashMap::new(),
         indexes: vec![],
         populate_with_data: true,
+        column_comments: std::collections::HashMap::new(),
+        owner: None,
     };
     let generator = PostgresSqlGenerator;
     let sql = generator.drop_materialized_view(&view).unwrap();
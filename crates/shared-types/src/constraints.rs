@@ -7,6 +7,8 @@ pub enum TableConstraint {
     PrimaryKey {
         columns: Vec<String>,
         name: Option<String>,
+        deferrable: bool,
+        initially_deferred: bool,
     },
     ForeignKey {
         columns: Vec<String>,
@@ -16,6 +18,8 @@ pub enum TableConstraint {
     Unique {
         columns: Vec<String>,
         name: Option<String>,
+        deferrable: bool,
+        initially_deferred: bool,
     },
     Check {
         expression: Expression,